@@ -43,6 +43,11 @@ fn main() {
         println!("cargo:rustc-cfg=has_tpmu_sensitive_create")
     }
 
+    let has_act_req = VersionReq::parse(">=3.0.0").unwrap();
+    if has_act_req.matches(&tss_version) {
+        println!("cargo:rustc-cfg=has_act")
+    }
+
     #[cfg(feature = "generate-bindings")]
     {
         let has_esys_tr_get_tpm_handle_req = VersionReq::parse(">=2.4.0").unwrap();
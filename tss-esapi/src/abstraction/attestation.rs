@@ -0,0 +1,108 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module for provisioning the keys used for attestation
+use crate::{
+    abstraction::{ak, ek, AsymmetricAlgorithmSelection, DefaultKey},
+    handles::KeyHandle,
+    interface_types::algorithm::{HashingAlgorithm, SignatureSchemeAlgorithm},
+    structures::{Auth, Name, Private, Public},
+    Context, Result,
+};
+
+/// The parameters of an Attestation Key template.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AkTemplate {
+    /// The name hashing algorithm, also used by the signing scheme.
+    pub hash_alg: HashingAlgorithm,
+    /// The asymmetric algorithm of the key.
+    pub key_alg: AsymmetricAlgorithmSelection,
+    /// The signing scheme of the key.
+    pub sign_alg: SignatureSchemeAlgorithm,
+}
+
+/// The result of [provision_ak].
+#[derive(Debug, Clone)]
+pub struct AkProvisionResult {
+    /// The handle of the loaded Endorsement Key.
+    pub ek_handle: KeyHandle,
+    /// The public area of the Endorsement Key.
+    pub ek_public: Public,
+    /// The handle of the loaded Attestation Key.
+    pub ak_handle: KeyHandle,
+    /// The public area of the Attestation Key.
+    pub ak_public: Public,
+    /// The private area of the Attestation Key, which allows
+    /// loading it again with [ak::load_ak].
+    pub ak_private: Private,
+    /// The name of the Attestation Key.
+    pub ak_name: Name,
+}
+
+/// Provisions an Attestation Key under the Endorsement Key.
+///
+/// # Details
+/// The Endorsement Key is created from the default template of `ek_alg`,
+/// then the Attestation Key is created from `ak_template` and loaded under it.
+/// The policy session satisfying the endorsement policy of the Endorsement Key
+/// is handled by this function.
+///
+/// Both keys are left loaded: the Endorsement Key is needed for activating
+/// credentials of the Attestation Key. The Attestation Key can be made persistent
+/// with [Context::evict_control].
+///
+/// # Errors
+/// * the errors of [ek::create_ek_object], [ak::create_ak] and [ak::load_ak]
+///   are returned. The keys loaded by this function are flushed on error.
+pub fn provision_ak(
+    context: &mut Context,
+    ek_alg: AsymmetricAlgorithmSelection,
+    ak_template: AkTemplate,
+    ak_auth_value: Option<Auth>,
+) -> Result<AkProvisionResult> {
+    let ek_handle = ek::create_ek_object(context, ek_alg, DefaultKey)?;
+    provision_ak_under_ek(context, ek_handle, ak_template, ak_auth_value).map_err(|e| {
+        let _ = context.flush_context(ek_handle.into());
+        e
+    })
+}
+
+fn provision_ak_under_ek(
+    context: &mut Context,
+    ek_handle: KeyHandle,
+    ak_template: AkTemplate,
+    ak_auth_value: Option<Auth>,
+) -> Result<AkProvisionResult> {
+    let (ek_public, _, _) = context.execute_without_session(|ctx| ctx.read_public(ek_handle))?;
+    let ak = ak::create_ak(
+        context,
+        ek_handle,
+        ak_template.hash_alg,
+        ak_template.key_alg,
+        ak_template.sign_alg,
+        ak_auth_value.clone(),
+        DefaultKey,
+    )?;
+    let ak_handle = ak::load_ak(
+        context,
+        ek_handle,
+        ak_auth_value,
+        ak.out_private.clone(),
+        ak.out_public.clone(),
+    )?;
+    let ak_name = context
+        .execute_without_session(|ctx| ctx.read_public(ak_handle))
+        .map(|(_, ak_name, _)| ak_name)
+        .map_err(|e| {
+            let _ = context.flush_context(ak_handle.into());
+            e
+        })?;
+    Ok(AkProvisionResult {
+        ek_handle,
+        ek_public,
+        ak_handle,
+        ak_public: ak.out_public,
+        ak_private: ak.out_private,
+        ak_name,
+    })
+}
@@ -0,0 +1,56 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module for signing with ECDAA keys
+//!
+//! An ECDAA signature is made in two steps: a commit, in which the TPM
+//! generates an ephemeral value, and the signing operation itself, which
+//! references the commit by its counter.
+use crate::{
+    handles::KeyHandle,
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Digest, EcDaaScheme, Signature, SignatureScheme},
+    Context, Result,
+};
+
+/// Signs `message_digest` with the ECDAA key loaded at `key_handle`.
+///
+/// # Details
+/// A commit is made with [Context::commit], without any of the optional
+/// points, and the returned commit counter is used in the ECDAA scheme
+/// given to [Context::sign]. A commit can only be used once, so a new one
+/// is made for every signature. The signature is made without validation
+/// ticket, so the key must not be restricted.
+///
+/// The sessions of the context are used to authorize the use of the key
+/// in both commands.
+///
+/// Protocols needing the points computed during the commit should use
+/// [Context::commit] and [Context::sign] directly.
+///
+/// # Arguments
+/// * `hashing_algorithm` - The hashing algorithm used to compute
+///   `message_digest`, it must be the one of the scheme of the key if
+///   the key has a scheme.
+///
+/// # Returns
+/// The [Signature::EcDaa] made by the TPM.
+///
+/// # Errors
+/// * the errors of [Context::commit] and [Context::sign] are returned.
+pub fn sign_ecdaa(
+    context: &mut Context,
+    key_handle: KeyHandle,
+    hashing_algorithm: HashingAlgorithm,
+    message_digest: Digest,
+) -> Result<Signature> {
+    let (_, _, _, commit_counter) = context.commit(key_handle, None, None, None)?;
+    context.sign(
+        key_handle,
+        message_digest,
+        SignatureScheme::EcDaa {
+            scheme: EcDaaScheme::new(hashing_algorithm, commit_counter.value()),
+        },
+        None,
+    )
+}
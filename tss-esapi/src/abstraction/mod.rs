@@ -2,11 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ak;
+pub mod attestation;
 pub mod cipher;
+pub mod ecdaa;
 pub mod ek;
 pub mod nv;
 pub mod pcr;
+pub mod policy;
+pub mod primary;
 pub mod public;
+pub mod rng;
+pub mod signing;
 pub mod transient;
 
 use std::convert::TryFrom;
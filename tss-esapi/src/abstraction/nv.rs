@@ -7,24 +7,38 @@ use std::{
 };
 
 use crate::{
-    constants::{tss::*, CapabilityType, PropertyTag},
+    constants::{tss::*, CapabilityType, NvIndexType, PropertyTag},
     handles::{AuthHandle, NvIndexHandle, NvIndexTpmHandle, TpmHandle},
-    interface_types::reserved_handles::NvAuth,
-    structures::{CapabilityData, MaxNvBuffer, Name, NvPublic},
+    interface_types::{
+        reserved_handles::{NvAuth, Provision},
+        session_handles::AuthSession,
+    },
+    structures::{Auth, CapabilityData, MaxNvBuffer, Name, NvPinCounter, NvPublic},
     Context, Error, Result, WrapperErrorKind,
 };
 
 /// Allows reading an NV Index completely, regardless of the max TPM NV buffer size
+///
+/// The whole data area of the index is returned, even if only part of it
+/// has been written.
+///
+/// # Errors
+/// * if the NV index has not been written yet, a `NvIndexNotWritten` wrapper error
+///   is returned.
+///
+/// NOTE: This call _may_ close existing ESYS handles to the NV Index.
 pub fn read_full(
     context: &mut Context,
     auth_handle: NvAuth,
     nv_index_handle: NvIndexTpmHandle,
 ) -> Result<Vec<u8>> {
-    let mut rw = NvOpenOptions::ExistingIndex {
-        auth_handle,
-        nv_index_handle,
+    let (mut rw, nv_public) =
+        NvOpenOptions::open_existing_index(context, auth_handle, nv_index_handle)?;
+    if !nv_public.attributes().written() {
+        log::error!("Unable to read the NV index, it has not been written");
+        return Err(Error::local_error(WrapperErrorKind::NvIndexNotWritten));
     }
-    .open(context)?;
+
     let mut result = Vec::with_capacity(rw.size());
 
     let _ = rw.read_to_end(&mut result).map_err(|e| {
@@ -41,6 +55,150 @@ pub fn read_full(
     Ok(result)
 }
 
+/// Defines a PIN pass or PIN fail NV index and writes its initial PIN counter.
+///
+/// # Details
+/// The NV index type of `nv_public` must be either `PinPass` or `PinFail`.
+/// The index is written using the authorization of `auth_handle`, so
+/// `nv_public` needs to allow writes by either the owner or the platform.
+/// If the initial write fails the NV index is undefined again.
+///
+/// # Errors
+/// * if the NV index type is not a PIN type, an `InvalidParam` wrapper error
+///   is returned.
+pub fn define_pin_index(
+    context: &mut Context,
+    auth_handle: Provision,
+    auth: Option<Auth>,
+    nv_public: NvPublic,
+    pin_counter: NvPinCounter,
+) -> Result<NvIndexHandle> {
+    match nv_public.attributes().index_type()? {
+        NvIndexType::PinPass | NvIndexType::PinFail => {}
+        nv_index_type => {
+            log::error!("NV index type {:?} is not a PIN index type", nv_index_type);
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+    }
+
+    let nv_index_handle = context.nv_define_space(auth_handle, auth, nv_public)?;
+    context
+        .nv_write(
+            NvAuth::try_from(AuthHandle::from(auth_handle))?,
+            nv_index_handle,
+            pin_counter.try_into()?,
+            0,
+        )
+        .map_err(|e| {
+            let _ = context.nv_undefine_space(auth_handle, nv_index_handle);
+            e
+        })?;
+    Ok(nv_index_handle)
+}
+
+/// Reads the PIN counter of a PIN pass or PIN fail NV index.
+///
+/// # Details
+/// Reading the index using its own authorization value is an authorization
+/// attempt, which will affect the PIN count. Use the owner or platform
+/// authorization to read the PIN counter without affecting it.
+pub fn read_pin_counter(
+    context: &mut Context,
+    auth_handle: NvAuth,
+    nv_index_handle: NvIndexHandle,
+) -> Result<NvPinCounter> {
+    context
+        .nv_read(auth_handle, nv_index_handle, NvPinCounter::SIZE as u16, 0)
+        .and_then(NvPinCounter::try_from)
+}
+
+/// Reads the value of an NV counter index.
+///
+/// # Details
+/// A counter index cannot be read before it has been incremented
+/// for the first time. If the counter has not been incremented yet, it
+/// is incremented when `initialize` is set, and otherwise 0 is returned.
+/// Note that the first increment sets the counter to at least the largest
+/// value of any counter index of the TPM, so it does not start from 0.
+///
+/// The value is decoded from the 8 byte big-endian data of the index.
+///
+/// # Errors
+/// * if the NV index is not a counter index, an `InvalidParam` wrapper
+///   error is returned.
+pub fn read_counter(
+    context: &mut Context,
+    auth_handle: NvAuth,
+    nv_index_handle: NvIndexHandle,
+    initialize: bool,
+) -> Result<u64> {
+    let (nv_public, _) =
+        context.execute_without_session(|ctx| ctx.nv_read_public(nv_index_handle))?;
+    let nv_index_type = nv_public.attributes().index_type()?;
+    if nv_index_type != NvIndexType::Counter {
+        log::error!(
+            "NV index type {:?} is not a counter index type",
+            nv_index_type
+        );
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    }
+    if !nv_public.attributes().written() {
+        if !initialize {
+            return Ok(0);
+        }
+        context.nv_increment(auth_handle, nv_index_handle)?;
+    }
+    let data = context.nv_read(auth_handle, nv_index_handle, 8, 0)?;
+    let bytes = <[u8; 8]>::try_from(data.as_bytes()).map_err(|_| {
+        log::error!("The counter value is not 8 bytes long");
+        Error::local_error(WrapperErrorKind::WrongValueFromTpm)
+    })?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Writes data to an NV index, selecting the authorization from the index attributes.
+///
+/// # Details
+/// The authorization handle is selected as follows:
+/// * if the index has the POLICYWRITE attribute and the first session of the
+///   context is a policy session, the index itself is used.
+/// * if the index has the OWNERWRITE attribute, the owner is used.
+/// * if the index has the AUTHWRITE attribute, the index itself is used.
+/// * if the index has the PPWRITE attribute, the platform is used.
+///
+/// The data is written at offset 0, using the sessions of the context.
+///
+/// # Errors
+/// * if the index can only be written with a policy and the first session of
+///   the context is not a policy session, a `MissingAuthSession` wrapper error
+///   is returned.
+pub fn write_auto(
+    context: &mut Context,
+    nv_index_handle: NvIndexHandle,
+    data: MaxNvBuffer,
+) -> Result<()> {
+    let (nv_public, _) =
+        context.execute_without_session(|ctx| ctx.nv_read_public(nv_index_handle))?;
+    let attributes = nv_public.attributes();
+    let policy_session_set = matches!(context.sessions().0, Some(AuthSession::PolicySession(_)));
+    let auth_handle = if attributes.policy_write() && policy_session_set {
+        NvAuth::NvIndex(nv_index_handle)
+    } else if attributes.owner_write() {
+        NvAuth::Owner
+    } else if attributes.auth_write() {
+        NvAuth::NvIndex(nv_index_handle)
+    } else if attributes.pp_write() {
+        NvAuth::Platform
+    } else if attributes.policy_write() {
+        log::error!("The NV index can only be written using a policy session");
+        return Err(Error::local_error(WrapperErrorKind::MissingAuthSession));
+    } else {
+        log::error!("The NV index does not allow any write authorization");
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    };
+    context.nv_write(auth_handle, nv_index_handle, data, 0)
+}
+
 /// Returns the NvPublic and Name associated with an NV index TPM handle
 ///
 /// NOTE: This call _may_ close existing ESYS handles to the NV Index.
@@ -108,47 +266,58 @@ impl NvOpenOptions {
     ///
     /// The non-volatile storage index may be used for reading or writing or both.
     pub fn open<'a>(&self, context: &'a mut Context) -> Result<NvReaderWriter<'a>> {
-        let buffer_size = max_nv_buffer_size(context)?;
-
-        let (data_size, nv_idx, auth_handle) = match self {
+        match self {
             NvOpenOptions::ExistingIndex {
                 nv_index_handle,
                 auth_handle,
-            } => {
-                let nv_idx = TpmHandle::NvIndex(*nv_index_handle);
-                let nv_idx = context
-                    .execute_without_session(|ctx| ctx.tr_from_tpm_public(nv_idx))?
-                    .into();
-                (
-                    context
-                        .execute_without_session(|ctx| ctx.nv_read_public(nv_idx))
-                        .map(|(nvpub, _)| nvpub.data_size())?,
-                    nv_idx,
-                    auth_handle,
-                )
-            }
+            } => NvOpenOptions::open_existing_index(context, *auth_handle, *nv_index_handle)
+                .map(|(nv_reader_writer, _)| nv_reader_writer),
             NvOpenOptions::NewIndex {
                 nv_public,
                 auth_handle,
-            } => (
-                nv_public.data_size(),
-                context.nv_define_space(
+            } => {
+                let buffer_size = max_nv_buffer_size(context)?;
+                let nv_idx = context.nv_define_space(
                     AuthHandle::from(*auth_handle).try_into()?,
                     None,
                     nv_public.clone(),
-                )?,
-                auth_handle,
-            ),
-        };
+                )?;
+                Ok(NvReaderWriter {
+                    context,
+                    auth_handle: *auth_handle,
+                    buffer_size,
+                    nv_idx,
+                    data_size: nv_public.data_size(),
+                    offset: 0,
+                })
+            }
+        }
+    }
 
-        Ok(NvReaderWriter {
-            context,
-            auth_handle: *auth_handle,
-            buffer_size,
-            nv_idx,
-            data_size,
-            offset: 0,
-        })
+    /// Private function that opens an existing NV index and
+    /// also returns the public area of the NV index.
+    fn open_existing_index(
+        context: &mut Context,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexTpmHandle,
+    ) -> Result<(NvReaderWriter<'_>, NvPublic)> {
+        let buffer_size = max_nv_buffer_size(context)?;
+        let nv_idx = TpmHandle::NvIndex(nv_index_handle);
+        let nv_idx = context
+            .execute_without_session(|ctx| ctx.tr_from_tpm_public(nv_idx))?
+            .into();
+        let (nv_public, _) = context.execute_without_session(|ctx| ctx.nv_read_public(nv_idx))?;
+        Ok((
+            NvReaderWriter {
+                context,
+                auth_handle,
+                buffer_size,
+                nv_idx,
+                data_size: nv_public.data_size(),
+                offset: 0,
+            },
+            nv_public,
+        ))
     }
 }
 
@@ -3,7 +3,14 @@
 mod bank;
 mod data;
 
-use crate::{structures::PcrSelectionList, Context, Result};
+use crate::{
+    constants::CapabilityType,
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{CapabilityData, Digest, PcrSelectionList, PcrSelectionListBuilder},
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::collections::BTreeMap;
 
 pub use bank::PcrBank;
 pub use data::PcrData;
@@ -70,3 +77,73 @@ pub fn read_all(
     }
     Ok(pcr_data)
 }
+
+/// Function that reads all the PCRs in all the active PCR banks.
+///
+/// # Details
+/// The active PCR banks are discovered by querying the TPM for
+/// its assigned PCRs. Every PCR in every active bank is then read,
+/// using as many calls as the TPM requires.
+///
+/// # Returns
+/// A map from the hashing algorithm of each active bank to a map
+/// from PCR index to the value of the PCR.
+///
+/// # Example
+///
+/// ```rust
+/// # use tss_esapi::{Context, TctiNameConf};
+/// # // Create context
+/// # let mut context =
+/// #     Context::new(
+/// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+/// #     ).expect("Failed to create Context");
+/// #
+/// use tss_esapi::interface_types::algorithm::HashingAlgorithm;
+/// let pcr_banks = tss_esapi::abstraction::pcr::read_all_banks(&mut context)
+///     .expect("pcr::read_all_banks failed");
+/// if let Some(sha256_bank) = pcr_banks.get(&HashingAlgorithm::Sha256) {
+///     for (pcr_index, digest) in sha256_bank {
+///         println!("PCR {}: {:x?}", pcr_index, digest.as_bytes());
+///     }
+/// }
+/// ```
+pub fn read_all_banks(
+    context: &mut Context,
+) -> Result<BTreeMap<HashingAlgorithm, BTreeMap<u32, Digest>>> {
+    let assigned_pcrs = match context.get_capability(CapabilityType::AssignedPcr, 0, 1)? {
+        (CapabilityData::AssignedPcr(assigned_pcrs), _) => assigned_pcrs,
+        _ => {
+            error!("Received invalid capability data when querying assigned PCRs");
+            return Err(Error::local_error(WrapperErrorKind::WrongValueFromTpm));
+        }
+    };
+
+    let mut pcr_banks = BTreeMap::new();
+    // Banks without any assigned PCRs are inactive and are not read.
+    let active_selections = assigned_pcrs
+        .get_selections()
+        .iter()
+        .filter(|pcr_selection| !pcr_selection.is_empty())
+        .collect::<Vec<_>>();
+    if active_selections.is_empty() {
+        return Ok(pcr_banks);
+    }
+
+    let pcr_selection_list = active_selections
+        .iter()
+        .fold(PcrSelectionListBuilder::new(), |builder, pcr_selection| {
+            builder.with_selection(pcr_selection.hashing_algorithm(), &pcr_selection.selected())
+        })
+        .build()?;
+
+    for (hashing_algorithm, pcr_bank) in read_all(context, pcr_selection_list)? {
+        let pcr_values = pcr_banks
+            .entry(hashing_algorithm)
+            .or_insert_with(BTreeMap::new);
+        for (pcr_slot, digest) in &pcr_bank {
+            let _ = pcr_values.insert(u32::from(*pcr_slot).trailing_zeros(), digest.clone());
+        }
+    }
+    Ok(pcr_banks)
+}
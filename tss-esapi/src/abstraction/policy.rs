@@ -0,0 +1,274 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    constants::{CommandCode, SessionType},
+    handles::{AuthHandle, KeyHandle, SessionHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm, reserved_handles::Hierarchy, session_handles::PolicySession,
+    },
+    structures::{
+        Digest, DigestList, MaxBuffer, Name, Nonce, Private, Public, PublicBuilder,
+        SymmetricDefinition,
+    },
+    tss2_esys::TPM2_CC,
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::TryFrom;
+
+#[cfg(feature = "openssl")]
+use crate::{structures::PcrSelectionList, traits::Marshall};
+
+/// Computes the digest of a policy by running it in a trial session.
+///
+/// # Details
+/// A trial session is started using `hashing_algorithm`, the policy
+/// commands in `f` are executed against it and the resulting policy
+/// digest is read back using
+/// [policy_get_digest][Context::policy_get_digest]. The trial session
+/// is flushed before returning, regardless of the outcome of `f`.
+///
+/// # Example
+///
+/// ```rust
+/// # use tss_esapi::{Context, TctiNameConf};
+/// # // Create context
+/// # let mut context =
+/// #     Context::new(
+/// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+/// #     ).expect("Failed to create Context");
+/// use tss_esapi::{
+///     abstraction::policy, interface_types::algorithm::HashingAlgorithm,
+/// };
+/// let policy_digest = policy::compute_policy_digest(
+///     &mut context,
+///     HashingAlgorithm::Sha256,
+///     |ctx, policy_session| ctx.policy_auth_value(policy_session),
+/// )
+/// .expect("Failed to compute policy digest");
+/// assert_eq!(policy_digest.len(), 32);
+/// ```
+pub fn compute_policy_digest<F>(
+    context: &mut Context,
+    hashing_algorithm: HashingAlgorithm,
+    f: F,
+) -> Result<Digest>
+where
+    F: FnOnce(&mut Context, PolicySession) -> Result<()>,
+{
+    let trial_session = context
+        .execute_without_session(|ctx| {
+            ctx.start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_128_CFB,
+                hashing_algorithm,
+            )
+        })?
+        .ok_or_else(|| Error::local_error(WrapperErrorKind::WrongValueFromTpm))?;
+
+    context.execute_with_temporary_object(SessionHandle::from(trial_session).into(), |ctx, _| {
+        let policy_session = PolicySession::try_from(trial_session)?;
+        f(ctx, policy_session)?;
+        ctx.execute_without_session(|ctx| ctx.policy_get_digest(policy_session))
+    })
+}
+
+/// Creates a key which can only be used after satisfying a policy.
+///
+/// # Details
+/// The digest of the policy commands in `policy_fn` is computed with
+/// [compute_policy_digest], using the name hashing algorithm of the
+/// template, and set as the `authPolicy` of the template before the
+/// key is created under `parent`.
+///
+/// To use the key, the same policy commands have to be executed in a
+/// policy session. If the key should not also be usable with its
+/// authorization value, the template must not have the `userWithAuth`
+/// attribute set.
+///
+/// # Errors
+/// * if the template cannot be built, the error of
+///   [PublicBuilder::build] is returned.
+/// * errors of `policy_fn` are returned as they are.
+pub fn create_policy_bound_key<F>(
+    context: &mut Context,
+    parent: KeyHandle,
+    template_builder: PublicBuilder,
+    policy_fn: F,
+) -> Result<(Private, Public)>
+where
+    F: FnOnce(&mut Context, PolicySession) -> Result<()>,
+{
+    let name_hashing_algorithm = template_builder.clone().build()?.name_hashing_algorithm();
+    let policy_digest = compute_policy_digest(context, name_hashing_algorithm, policy_fn)?;
+    let public = template_builder.with_auth_policy(policy_digest).build()?;
+    let result = context.create(parent, public, None, None, None, None)?;
+    Ok((result.out_private, result.out_public))
+}
+
+/// Requires the authorization of the owner hierarchy in a policy.
+///
+/// # Details
+/// Runs [policy_secret][Context::policy_secret] against the owner
+/// hierarchy, with an empty nonce, cpHash and policyRef and without
+/// expiration, which makes the policy usable by anyone knowing the
+/// owner hierarchy authorization value.
+///
+/// The first session set in the context is used to authorize the owner
+/// hierarchy, it can for example be a password or HMAC session.
+///
+/// # Errors
+/// * if no session has been set in the context, a `MissingAuthSession`
+///   wrapper error is returned.
+pub fn policy_secret_owner(context: &mut Context, policy_session: PolicySession) -> Result<()> {
+    if context.sessions().0.is_none() {
+        error!("A session is needed to authorize the owner hierarchy");
+        return Err(Error::local_error(WrapperErrorKind::MissingAuthSession));
+    }
+    context
+        .policy_secret(
+            policy_session,
+            AuthHandle::Owner,
+            Nonce::default(),
+            Digest::default(),
+            Nonce::default(),
+            None,
+        )
+        .map(|_| ())
+}
+
+/// Computes the command parameter hash (cpHash) of a command.
+///
+/// # Details
+/// The cpHash is computed by the TPM, using the `hashing_algorithm`, over
+/// the `command_code`, the [Name]s of the handles of the command in the
+/// order in which they appear in the command and the marshalled
+/// `parameters` of the command:
+///
+/// cpHash ≔ H(commandCode || name1 || name2 || ... || parameters)
+///
+/// The result can be given to [policy_cp_hash][Context::policy_cp_hash]
+/// in order to only authorize this exact command.
+///
+/// # Errors
+/// * if the data to hash is larger than a [MaxBuffer], a
+///   `WrongParamSize` wrapper error is returned.
+pub fn compute_cp_hash(
+    context: &mut Context,
+    hashing_algorithm: HashingAlgorithm,
+    command_code: CommandCode,
+    names: &[Name],
+    parameters: &[u8],
+) -> Result<Digest> {
+    let mut data = TPM2_CC::from(command_code).to_be_bytes().to_vec();
+    for name in names {
+        data.extend_from_slice(name.value());
+    }
+    data.extend_from_slice(parameters);
+    hash_with_null_hierarchy(context, hashing_algorithm, data)
+}
+
+/// Computes the name hash (nameHash) of the handles of a command.
+///
+/// # Details
+/// The nameHash is computed by the TPM, using the `hashing_algorithm`,
+/// over the [Name]s of the handles of the command in the order in which
+/// they appear in the command:
+///
+/// nameHash ≔ H(name1 || name2 || ...)
+///
+/// The result can be given to [policy_name_hash][Context::policy_name_hash]
+/// in order to only authorize commands acting on these objects, regardless
+/// of their parameters.
+///
+/// # Errors
+/// * if the names are larger than a [MaxBuffer], a `WrongParamSize`
+///   wrapper error is returned.
+pub fn compute_name_hash(
+    context: &mut Context,
+    hashing_algorithm: HashingAlgorithm,
+    names: &[Name],
+) -> Result<Digest> {
+    let data = names
+        .iter()
+        .flat_map(|name| name.value().iter().copied())
+        .collect::<Vec<u8>>();
+    hash_with_null_hierarchy(context, hashing_algorithm, data)
+}
+
+/// Satisfies a PolicyOR using the branch the policy session is currently on.
+///
+/// # Details
+/// The current policy digest of `policy_session` is read using
+/// [policy_get_digest][Context::policy_get_digest] and, if it is one of
+/// the `branches`, [policy_or][Context::policy_or] is called with the
+/// full list of branches, as the TPM requires.
+///
+/// # Errors
+/// * if the current policy digest is not one of the `branches`, an
+///   `InvalidParam` wrapper error is returned without sending the
+///   PolicyOR command.
+/// * the errors of [DigestList::policy_or_branches] are returned.
+pub fn satisfy_or(
+    context: &mut Context,
+    policy_session: PolicySession,
+    branches: &[Digest],
+) -> Result<()> {
+    let digest_list = DigestList::policy_or_branches(branches.to_vec())?;
+    let current_digest =
+        context.execute_without_session(|ctx| ctx.policy_get_digest(policy_session))?;
+    if !branches.contains(&current_digest) {
+        error!("The current policy digest does not match any of the PolicyOR branches");
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    }
+    context.policy_or(policy_session, digest_list)
+}
+
+/// Computes, in software, the digest of a policy made of a single PolicyPCR.
+///
+/// # Details
+/// The policy digest is extended from the initial, all zero, policy
+/// digest the same way the TPM does it for
+/// [policy_pcr][Context::policy_pcr]:
+///
+/// policyDigest ≔ H(0...0 || TPM_CC_PolicyPCR || pcrs || pcrDigest)
+///
+/// where `pcrs` is the marshalled `selection` and `pcrDigest` is the
+/// digest of the concatenated values of the selected PCRs. No TPM is
+/// needed, which allows verifiers to compute the expected digest offline.
+///
+/// # Errors
+/// * if `hashing_algorithm` is [HashingAlgorithm::Null], an `InvalidParam`
+///   wrapper error is returned.
+/// * the errors of [HashingAlgorithm::digest] are returned.
+#[cfg(feature = "openssl")]
+pub fn pcr_policy_digest(
+    hashing_algorithm: HashingAlgorithm,
+    selection: &PcrSelectionList,
+    pcr_digest: &Digest,
+) -> Result<Digest> {
+    let digest_size = hashing_algorithm
+        .digest_size()
+        .ok_or_else(|| Error::local_error(WrapperErrorKind::InvalidParam))?;
+    let mut data = vec![0u8; digest_size];
+    data.extend_from_slice(&TPM2_CC::from(CommandCode::PolicyPcr).to_be_bytes());
+    data.extend_from_slice(&selection.marshall()?);
+    data.extend_from_slice(pcr_digest.as_bytes());
+    hashing_algorithm.digest(&data)
+}
+
+/// Hashes `data` using the TPM, without producing a ticket.
+fn hash_with_null_hierarchy(
+    context: &mut Context,
+    hashing_algorithm: HashingAlgorithm,
+    data: Vec<u8>,
+) -> Result<Digest> {
+    let data = MaxBuffer::try_from(data)?;
+    context
+        .execute_without_session(|ctx| ctx.hash(data, hashing_algorithm, Hierarchy::Null))
+        .map(|(digest, _)| digest)
+}
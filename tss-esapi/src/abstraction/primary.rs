@@ -0,0 +1,43 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module for creating primary keys deterministically
+//!
+//! Primary keys are derived from the primary seed of their hierarchy,
+//! so creating a primary key twice from the same template yields the
+//! same key. This module avoids recreating such keys.
+use crate::{
+    interface_types::reserved_handles::Hierarchy,
+    structures::{CreatePrimaryKeyResult, Public},
+    traits::Marshall,
+    Context, Result,
+};
+
+/// Creates a primary key in the hierarchy from the template, or returns
+/// the primary key previously created from the same template.
+///
+/// # Details
+/// The primary keys created by this function are cached in the context,
+/// keyed by hierarchy and template. While the key created from the template
+/// is still loaded, repeated calls return the cached result instead of
+/// creating the key again. The key is created without auth value, initial
+/// data, outside info or creation PCRs.
+///
+/// The sessions of the context are used for authorizing the creation.
+///
+/// # Errors
+/// * if the template cannot be marshalled, the error is returned.
+/// * the errors of [Context::create_primary] are returned.
+pub fn create_primary_deterministic(
+    context: &mut Context,
+    hierarchy: Hierarchy,
+    template: Public,
+) -> Result<CreatePrimaryKeyResult> {
+    let marshalled_template = template.marshall()?;
+    if let Some(result) = context.cached_primary_key(hierarchy, &marshalled_template) {
+        return Ok(result);
+    }
+    let result = context.create_primary(hierarchy, template, None, None, None, None)?;
+    context.cache_primary_key(hierarchy, marshalled_template, &result)?;
+    Ok(result)
+}
@@ -14,6 +14,17 @@ use picky_asn1_x509::{
 };
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "serde_json")]
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+#[cfg(feature = "openssl")]
+use openssl::{
+    bn::BigNum,
+    ec::{EcGroup, EcKey},
+    nid::Nid,
+    pkey::PKey,
+    rsa::Rsa,
+};
+
 /// Can be converted from [`crate::structures::Public`] when not a fully constructed
 /// [`picky_asn1_x509::SubjectPublicKeyInfo`] is required.
 ///
@@ -100,6 +111,129 @@ fn public_to_decoded_key(public: &Public) -> Result<DecodedKey, Error> {
     }
 }
 
+/// Converts [`crate::structures::Public::Rsa`] and [`crate::structures::Public::Ecc`] to an
+/// [`openssl::pkey::PKey`] holding the public key.
+///
+/// # Details
+/// In RSA conversion the TPM zero exponent is replaced with `65537`. ECC keys are
+/// created on the named curve matching the curve of the TPM key.
+///
+/// # Errors
+/// * if other instances of [`crate::structures::Public`] are used, or the ECC curve has
+///   no OpenSSL equivalent, `UnsupportedParam` will be returned.
+/// * if OpenSSL rejects the key material `InvalidParam` will be returned.
+#[cfg(feature = "openssl")]
+pub fn to_openssl_pkey(public: &Public) -> Result<PKey<openssl::pkey::Public>, Error> {
+    match public {
+        Public::Rsa {
+            unique, parameters, ..
+        } => {
+            let exponent = match parameters.exponent() {
+                RsaExponent::ZERO_EXPONENT => 65537,
+                _ => parameters.exponent().value(),
+            };
+            BigNum::from_slice(unique.as_bytes())
+                .and_then(|modulus| {
+                    Rsa::from_public_components(modulus, BigNum::from_u32(exponent)?)
+                })
+                .and_then(PKey::from_rsa)
+                .map_err(openssl_error)
+        }
+        Public::Ecc {
+            unique, parameters, ..
+        } => {
+            let nid = curve_nid(parameters.ecc_curve())?;
+            EcGroup::from_curve_name(nid)
+                .and_then(|group| {
+                    EcKey::from_public_key_affine_coordinates(
+                        &group,
+                        &BigNum::from_slice(unique.x().as_bytes())?,
+                        &BigNum::from_slice(unique.y().as_bytes())?,
+                    )
+                })
+                .and_then(PKey::from_ec_key)
+                .map_err(openssl_error)
+        }
+        _ => Err(Error::local_error(WrapperErrorKind::UnsupportedParam)),
+    }
+}
+
+/// Converts [`crate::structures::Public::Rsa`] and [`crate::structures::Public::Ecc`] to a
+/// JSON Web Key (RFC 7517).
+///
+/// # Details
+/// RSA keys are represented with the `kty`, `n` and `e` members and ECC keys with the `kty`,
+/// `crv`, `x` and `y` members, all values being base64url encoded without padding. In RSA
+/// conversion the TPM zero exponent is replaced with `65537`.
+///
+/// # Errors
+/// * if other instances of [`crate::structures::Public`] are used, or the ECC curve has
+///   no JWK name, `UnsupportedParam` will be returned.
+#[cfg(feature = "serde_json")]
+pub fn to_jwk(public: &Public) -> Result<serde_json::Value, Error> {
+    match public {
+        Public::Rsa {
+            unique, parameters, ..
+        } => {
+            let exponent = match parameters.exponent() {
+                RsaExponent::ZERO_EXPONENT => 65537,
+                _ => parameters.exponent().value(),
+            }
+            .to_be_bytes();
+            let leading_zeros = exponent.iter().take_while(|&&byte| byte == 0).count();
+            Ok(serde_json::json!({
+                "kty": "RSA",
+                "n": URL_SAFE_NO_PAD.encode(unique.as_bytes()),
+                "e": URL_SAFE_NO_PAD.encode(&exponent[leading_zeros..]),
+            }))
+        }
+        Public::Ecc {
+            unique, parameters, ..
+        } => Ok(serde_json::json!({
+            "kty": "EC",
+            "crv": curve_jwk_name(parameters.ecc_curve())?,
+            "x": URL_SAFE_NO_PAD.encode(unique.x().as_bytes()),
+            "y": URL_SAFE_NO_PAD.encode(unique.y().as_bytes()),
+        })),
+        _ => Err(Error::local_error(WrapperErrorKind::UnsupportedParam)),
+    }
+}
+
+// Map TPM supported ECC curves to their respective JWK names
+#[cfg(feature = "serde_json")]
+fn curve_jwk_name(ecc_curve: EccCurve) -> Result<&'static str, Error> {
+    match ecc_curve {
+        EccCurve::NistP256 => Ok("P-256"),
+        EccCurve::NistP384 => Ok("P-384"),
+        EccCurve::NistP521 => Ok("P-521"),
+        _ => Err(Error::local_error(WrapperErrorKind::UnsupportedParam)),
+    }
+}
+
+#[cfg(feature = "openssl")]
+fn openssl_error(error: openssl::error::ErrorStack) -> Error {
+    log::error!(
+        "Failed to convert the public key to an OpenSSL key: {}",
+        error
+    );
+    Error::local_error(WrapperErrorKind::InvalidParam)
+}
+
+// Map TPM supported ECC curves to their respective OpenSSL NIDs
+#[cfg(feature = "openssl")]
+fn curve_nid(ecc_curve: EccCurve) -> Result<Nid, Error> {
+    match ecc_curve {
+        EccCurve::NistP192 => Ok(Nid::X9_62_PRIME192V1),
+        EccCurve::NistP224 => Ok(Nid::SECP224R1),
+        EccCurve::NistP256 => Ok(Nid::X9_62_PRIME256V1),
+        EccCurve::NistP384 => Ok(Nid::SECP384R1),
+        EccCurve::NistP521 => Ok(Nid::SECP521R1),
+        EccCurve::BnP256 | EccCurve::BnP638 | EccCurve::Sm2P256 => {
+            Err(Error::local_error(WrapperErrorKind::UnsupportedParam))
+        }
+    }
+}
+
 // Taken from https://github.com/parallaxsecond/parsec/blob/561235f3cc37bcff3d9a6cb29c84eeae5d55100b/src/providers/tpm/utils.rs#L319
 // Points on elliptic curves are represented as defined in section 2.3.3 of https://www.secg.org/sec1-v2.pdf
 // The (uncompressed) representation is [ 0x04 || x || y ] where x and y are the coordinates of the point
@@ -0,0 +1,158 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+//! Module for using the TPM as a random number generator.
+use crate::{
+    structures::{Digest, SensitiveData},
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+#[cfg(feature = "rand")]
+use rand_core::{impls, CryptoRng, RngCore};
+use std::convert::TryFrom;
+
+/// Maximum number of bytes TPMs are required to accept in a
+/// single call to TPM2_StirRandom (MAX_SYM_DATA).
+const MAX_STIR_RANDOM_SIZE: usize = 128;
+
+/// Stirs `extra_entropy` into the state of the TPM random number
+/// generator, then draws `num_bytes` random bytes from it.
+///
+/// # Details
+/// The TPM reseeds its random number generator on reset, but does not
+/// report whether its state is fresh. As a defense in depth measure,
+/// this mixes entropy gathered by the caller into the state, using
+/// [Context::stir_random], before drawing the bytes using
+/// [Context::get_random]. The reference implementation also reseeds
+/// from the TPM entropy source when stirred, so `extra_entropy` may be
+/// empty.
+///
+/// Entropy larger than what TPMs are required to accept in a single
+/// call is stirred in multiple calls, and more bytes than a digest can
+/// hold are drawn in multiple calls. The TPM may return fewer bytes than
+/// requested, in which case the remaining bytes are drawn in further
+/// calls.
+///
+/// # Errors
+/// * if the TPM returns no bytes, or more bytes than requested, a
+///   `WrongValueFromTpm` wrapper error is returned.
+pub fn reseed_and_get_random(
+    context: &mut Context,
+    extra_entropy: &[u8],
+    num_bytes: usize,
+) -> Result<Vec<u8>> {
+    if extra_entropy.is_empty() {
+        context.stir_random(SensitiveData::default())?;
+    }
+    for entropy in extra_entropy.chunks(MAX_STIR_RANDOM_SIZE) {
+        context.stir_random(SensitiveData::try_from(entropy.to_vec())?)?;
+    }
+
+    let mut random_bytes = Vec::with_capacity(num_bytes);
+    while random_bytes.len() < num_bytes {
+        let remaining = std::cmp::min(num_bytes - random_bytes.len(), Digest::MAX_SIZE);
+        let drawn_bytes = context.get_random(remaining)?;
+        if drawn_bytes.is_empty() || drawn_bytes.len() > remaining {
+            error!(
+                "The TPM returned {} random bytes when {} were requested",
+                drawn_bytes.len(),
+                remaining
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongValueFromTpm));
+        }
+        random_bytes.extend_from_slice(drawn_bytes.as_bytes());
+    }
+    Ok(random_bytes)
+}
+
+/// Random number generator drawing its bytes from the TPM.
+///
+/// # Details
+/// The bytes are retrieved using [Context::get_random], which returns
+/// at most a digest worth of bytes per call, so larger buffers are
+/// filled with multiple calls. The sessions of the context are used
+/// for the calls.
+///
+/// # Example
+///
+/// ```rust
+/// # use tss_esapi::{Context, TctiNameConf};
+/// # // Create context
+/// # let mut context =
+/// #     Context::new(
+/// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+/// #     ).expect("Failed to create Context");
+/// use rand_core::RngCore;
+/// use tss_esapi::abstraction::rng::TpmRng;
+///
+/// let mut rng = TpmRng::new(&mut context);
+/// let mut buffer = [0u8; 100];
+/// rng.try_fill_bytes(&mut buffer)
+///     .expect("Failed to get random bytes");
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug)]
+pub struct TpmRng<'a>(&'a mut Context);
+
+#[cfg(feature = "rand")]
+impl<'a> TpmRng<'a> {
+    /// Creates a new TpmRng using `context`.
+    pub fn new(context: &'a mut Context) -> Self {
+        TpmRng(context)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl RngCore for TpmRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    /// Fills `dest` with random bytes from the TPM.
+    ///
+    /// # Panics
+    /// Panics if the random bytes cannot be retrieved from the TPM,
+    /// use [TpmRng::try_fill_bytes] to handle the errors.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("Failed to get random bytes from the TPM: {}", e);
+        }
+    }
+
+    /// Fills `dest` with random bytes from the TPM.
+    ///
+    /// # Errors
+    /// The errors returned by [Context::get_random] are wrapped in the
+    /// returned error.
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand_core::Error> {
+        for chunk in dest.chunks_mut(Digest::MAX_SIZE) {
+            let mut filled = 0;
+            while filled < chunk.len() {
+                let remaining = chunk.len() - filled;
+                let random_bytes = self
+                    .0
+                    .get_random(remaining)
+                    .map_err(rand_core::Error::new)?;
+                if random_bytes.is_empty() || random_bytes.len() > remaining {
+                    error!(
+                        "The TPM returned {} random bytes when {} were requested",
+                        random_bytes.len(),
+                        remaining
+                    );
+                    return Err(rand_core::Error::new(Error::local_error(
+                        WrapperErrorKind::WrongValueFromTpm,
+                    )));
+                }
+                chunk[filled..filled + random_bytes.len()].copy_from_slice(&random_bytes);
+                filled += random_bytes.len();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl CryptoRng for TpmRng<'_> {}
@@ -0,0 +1,293 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    handles::{KeyHandle, ObjectHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm, ecc::EccCurve, reserved_handles::Hierarchy,
+        session_handles::AuthSession,
+    },
+    structures::{
+        Digest, EccParameter, EccScheme, EccSignature, HashcheckTicket, KeyedHashScheme, MaxBuffer,
+        Public, RsaScheme, Signature, SignatureScheme,
+    },
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use picky_asn1::wrapper::IntegerAsn1;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// The `Ecdsa-Sig-Value` structure defined in RFC 3279.
+#[derive(Serialize, Deserialize)]
+struct EcdsaSigValue {
+    r: IntegerAsn1,
+    s: IntegerAsn1,
+}
+
+/// Signs `digest` with the key loaded at `key_handle`, using the signing
+/// scheme of the key.
+///
+/// # Details
+/// The signing scheme is taken from the scheme in `key_public`, which must
+/// be the public area of the loaded key. If the key has a null scheme then
+/// `scheme_hint` is used instead.
+///
+/// No validation ticket is provided, so the key must not be restricted.
+///
+/// # Errors
+/// * if the key has a null scheme and no `scheme_hint` is given, a
+///   `ParamsMissing` wrapper error is returned.
+/// * if the scheme of the key is not a signing scheme, an `InvalidParam`
+///   wrapper error is returned.
+pub fn sign_with_key(
+    context: &mut Context,
+    key_handle: KeyHandle,
+    key_public: &Public,
+    digest: Digest,
+    scheme_hint: Option<SignatureScheme>,
+) -> Result<Signature> {
+    let scheme = match signature_scheme(key_public)? {
+        SignatureScheme::Null => scheme_hint.ok_or_else(|| {
+            error!("The key has a null scheme and no signing scheme was provided");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?,
+        scheme => scheme,
+    };
+    context.sign(key_handle, digest, scheme, None)
+}
+
+/// Hashes `message` on the TPM and signs the digest with the key loaded at `key_handle`.
+///
+/// # Details
+/// The message is hashed with the hashing algorithm of the signing scheme
+/// in `key_public`, which must be the public area of the loaded key. The
+/// message is hashed with [Context::hash] if it fits in a [MaxBuffer] and with
+/// a hash sequence otherwise. The digest is computed in the owner hierarchy, so
+/// the returned ticket allows signing with restricted keys.
+///
+/// The key is authorized using the sessions of the context.
+///
+/// # Errors
+/// * if the key has a null scheme, a `ParamsMissing` wrapper error is returned.
+/// * if the scheme of the key is not a signing scheme, an `InvalidParam`
+///   wrapper error is returned.
+pub fn hash_and_sign(
+    context: &mut Context,
+    key_handle: KeyHandle,
+    key_public: &Public,
+    message: &[u8],
+) -> Result<Signature> {
+    let scheme = signature_scheme(key_public)?;
+    if scheme == SignatureScheme::Null {
+        error!("The key has a null scheme, the hashing algorithm cannot be determined");
+        return Err(Error::local_error(WrapperErrorKind::ParamsMissing));
+    }
+    let hashing_algorithm = scheme.signing_scheme()?;
+    let (digest, ticket) = if message.len() <= MaxBuffer::MAX_SIZE {
+        context.execute_without_session(|ctx| {
+            ctx.hash(
+                MaxBuffer::from_bytes(message)?,
+                hashing_algorithm,
+                Hierarchy::Owner,
+            )
+        })?
+    } else {
+        hash_with_sequence(context, hashing_algorithm, message)?
+    };
+    context.sign(key_handle, digest, scheme, ticket)
+}
+
+/// Hashes `message` in the owner hierarchy using a hash sequence.
+fn hash_with_sequence(
+    context: &mut Context,
+    hashing_algorithm: HashingAlgorithm,
+    message: &[u8],
+) -> Result<(Digest, HashcheckTicket)> {
+    let sequence_handle =
+        context.execute_without_session(|ctx| ctx.hash_sequence_start(hashing_algorithm, None))?;
+    context
+        .execute_with_session(Some(AuthSession::Password), |ctx| {
+            complete_sequence(ctx, sequence_handle, message)
+        })
+        .map_err(|e| {
+            let _ = context.flush_context(sequence_handle);
+            e
+        })
+}
+
+/// Adds `message` to the hash sequence and completes it in the owner hierarchy.
+fn complete_sequence(
+    context: &mut Context,
+    sequence_handle: ObjectHandle,
+    message: &[u8],
+) -> Result<(Digest, HashcheckTicket)> {
+    let mut chunks = message.chunks(MaxBuffer::MAX_SIZE).peekable();
+    while let Some(chunk) = chunks.next() {
+        if chunks.peek().is_none() {
+            return context.sequence_complete(
+                sequence_handle,
+                MaxBuffer::from_bytes(chunk)?,
+                Hierarchy::Owner,
+            );
+        }
+        context.sequence_update(sequence_handle, MaxBuffer::from_bytes(chunk)?)?;
+    }
+    context.sequence_complete(sequence_handle, MaxBuffer::default(), Hierarchy::Owner)
+}
+
+/// Returns the signing scheme of the key with the public area `key_public`.
+///
+/// # Errors
+/// * if the scheme of the key is not a signing scheme, an `InvalidParam`
+///   wrapper error is returned.
+pub fn signature_scheme(key_public: &Public) -> Result<SignatureScheme> {
+    match key_public {
+        Public::Rsa { parameters, .. } => match parameters.rsa_scheme() {
+            RsaScheme::RsaSsa(scheme) => Ok(SignatureScheme::RsaSsa { scheme }),
+            RsaScheme::RsaPss(scheme) => Ok(SignatureScheme::RsaPss { scheme }),
+            RsaScheme::Null => Ok(SignatureScheme::Null),
+            rsa_scheme => {
+                error!("RSA scheme {:?} is not a signing scheme", rsa_scheme);
+                Err(Error::local_error(WrapperErrorKind::InvalidParam))
+            }
+        },
+        Public::Ecc { parameters, .. } => match parameters.ecc_scheme() {
+            EccScheme::EcDsa(scheme) => Ok(SignatureScheme::EcDsa { scheme }),
+            EccScheme::Sm2(scheme) => Ok(SignatureScheme::Sm2 { scheme }),
+            EccScheme::EcSchnorr(scheme) => Ok(SignatureScheme::EcSchnorr { scheme }),
+            EccScheme::EcDaa(scheme) => Ok(SignatureScheme::EcDaa { scheme }),
+            EccScheme::Null => Ok(SignatureScheme::Null),
+            ecc_scheme => {
+                error!("ECC scheme {:?} is not a signing scheme", ecc_scheme);
+                Err(Error::local_error(WrapperErrorKind::InvalidParam))
+            }
+        },
+        Public::KeyedHash { parameters, .. } => match parameters.keyed_hash_scheme() {
+            KeyedHashScheme::Hmac { hmac_scheme } => Ok(SignatureScheme::Hmac {
+                scheme: hmac_scheme,
+            }),
+            KeyedHashScheme::Null => Ok(SignatureScheme::Null),
+            keyed_hash_scheme => {
+                error!(
+                    "Keyed hash scheme {:?} is not a signing scheme",
+                    keyed_hash_scheme
+                );
+                Err(Error::local_error(WrapperErrorKind::InvalidParam))
+            }
+        },
+        Public::SymCipher { .. } => {
+            error!("Symmetric cipher keys cannot be used for signing");
+            Err(Error::local_error(WrapperErrorKind::InvalidParam))
+        }
+    }
+}
+
+/// Encodes an ECDSA signature as a DER `Ecdsa-Sig-Value`.
+///
+/// # Details
+/// This is the format expected by most cryptographic libraries (e.g. OpenSSL)
+/// when verifying ECDSA signatures.
+///
+/// # Errors
+/// * if `signature` is not an ECDSA signature, an `InvalidParam` wrapper
+///   error is returned.
+pub fn ecdsa_to_der(signature: &Signature) -> Result<Vec<u8>> {
+    let (r, s) = signature.as_ecdsa().ok_or_else(|| {
+        error!("Only ECDSA signatures can be encoded as an Ecdsa-Sig-Value");
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    })?;
+    let sig_value = EcdsaSigValue {
+        r: integer_from_unsigned(r.as_bytes()),
+        s: integer_from_unsigned(s.as_bytes()),
+    };
+    picky_asn1_der::to_vec(&sig_value).map_err(|e| {
+        error!("Failed to encode ECDSA signature: {}", e);
+        Error::local_error(WrapperErrorKind::InternalError)
+    })
+}
+
+/// Decodes a DER `Ecdsa-Sig-Value` into an ECDSA signature.
+///
+/// # Details
+/// The `r` and `s` values are padded with leading zeros to the size
+/// of `curve`. The DER encoding does not carry the digest algorithm,
+/// so it has to be provided in `hashing_algorithm`.
+///
+/// # Errors
+/// * if `der` is not a valid `Ecdsa-Sig-Value`, if one of the integers
+///   is negative or not minimally encoded, or if one of them is larger
+///   than the size of `curve`, an `InvalidParam` wrapper error is returned.
+pub fn der_to_ecdsa(
+    der: &[u8],
+    curve: EccCurve,
+    hashing_algorithm: HashingAlgorithm,
+) -> Result<Signature> {
+    let sig_value: EcdsaSigValue = picky_asn1_der::from_bytes(der).map_err(|e| {
+        error!("Failed to decode ECDSA signature: {}", e);
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    })?;
+    let size = ecc_parameter_size(curve);
+    Ok(Signature::EcDsa(EccSignature::create(
+        hashing_algorithm,
+        ecc_parameter_from_integer(&sig_value.r, size)?,
+        ecc_parameter_from_integer(&sig_value.s, size)?,
+    )?))
+}
+
+/// Creates a minimally encoded, non-negative DER integer from
+/// big-endian unsigned bytes.
+fn integer_from_unsigned(bytes: &[u8]) -> IntegerAsn1 {
+    let bytes = match bytes.iter().position(|byte| *byte != 0) {
+        Some(start) => bytes[start..].to_vec(),
+        None => vec![0],
+    };
+    // Adds a leading zero if the most significant bit is set.
+    IntegerAsn1::from_bytes_be_unsigned(bytes)
+}
+
+/// Converts a DER integer into an ECC parameter of `size` bytes.
+fn ecc_parameter_from_integer(integer: &IntegerAsn1, size: usize) -> Result<EccParameter> {
+    let bytes = integer.as_signed_bytes_be();
+    match bytes {
+        [] => {
+            error!("ECDSA signature contains an empty integer");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        [first, ..] if first & 0x80 != 0 => {
+            error!("ECDSA signature contains a negative integer");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        [0, second, ..] if second & 0x80 == 0 => {
+            error!("ECDSA signature contains an integer that is not minimally encoded");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        _ => {}
+    }
+    let unsigned = match bytes.iter().position(|byte| *byte != 0) {
+        Some(start) => &bytes[start..],
+        None => &[],
+    };
+    if unsigned.len() > size {
+        error!(
+            "ECDSA signature integer of {} bytes is larger than the curve size of {} bytes",
+            unsigned.len(),
+            size
+        );
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    }
+    let mut padded = vec![0; size - unsigned.len()];
+    padded.extend_from_slice(unsigned);
+    EccParameter::try_from(padded)
+}
+
+/// Returns the size in bytes of the parameters of `curve`.
+const fn ecc_parameter_size(curve: EccCurve) -> usize {
+    match curve {
+        EccCurve::NistP192 => 24,
+        EccCurve::NistP224 => 28,
+        EccCurve::NistP256 | EccCurve::BnP256 | EccCurve::Sm2P256 => 32,
+        EccCurve::NistP384 => 48,
+        EccCurve::NistP521 => 66,
+        EccCurve::BnP638 => 80,
+    }
+}
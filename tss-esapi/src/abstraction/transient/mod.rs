@@ -35,8 +35,10 @@ use std::convert::{AsMut, AsRef, TryFrom, TryInto};
 use zeroize::Zeroize;
 
 mod key_attestation;
+mod persistent;
 
 pub use key_attestation::MakeCredParams;
+pub use persistent::get_or_create_persistent_primary;
 
 /// Parameters for the kinds of keys supported by the context
 #[derive(Debug, Clone, Copy)]
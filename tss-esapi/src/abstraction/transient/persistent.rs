@@ -0,0 +1,113 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::tss::TPM2_RC_HANDLE,
+    handles::{KeyHandle, PersistentTpmHandle, TpmHandle},
+    interface_types::{
+        data_handles::Persistent,
+        reserved_handles::{Hierarchy, Provision},
+    },
+    structures::Public,
+    Context, Result,
+};
+use log::info;
+
+/// Returns a handle to a primary key stored at the `persistent` handle,
+/// creating it if needed.
+///
+/// # Details
+/// If an object already exists at `persistent` and its public area was
+/// created from `template`, the handle to that object is returned. If the
+/// object does not match `template` it is evicted, and a new primary key is
+/// created in the hierarchy of `auth` and made persistent at `persistent`.
+///
+/// The public areas are compared on everything except the unique field,
+/// which is computed by the TPM when the primary key is created.
+///
+/// An authorization session for `auth` must be set on the context, as it
+/// is needed both for creating the primary key and for making it persistent.
+///
+/// # Errors
+/// * if reading the public area of the persistent object fails for any other
+///   reason than the handle not existing, the error is returned.
+/// * if creating the primary key or making it persistent fails, the error
+///   is returned.
+pub fn get_or_create_persistent_primary(
+    context: &mut Context,
+    auth: Provision,
+    persistent: PersistentTpmHandle,
+    template: Public,
+) -> Result<KeyHandle> {
+    let existing = context.execute_without_session(|ctx| {
+        ctx.tr_from_tpm_public(TpmHandle::Persistent(persistent))
+            .and_then(|object_handle| {
+                ctx.read_public(object_handle.into())
+                    .map(|(public, _, _)| (object_handle, public))
+            })
+    });
+
+    match existing {
+        Ok((object_handle, public)) => {
+            if matches_template(&public, &template) {
+                return Ok(object_handle.into());
+            }
+            info!("Persistent object does not match the template, re-creating it");
+            let _ = context.evict_control(auth, object_handle, Persistent::from(persistent))?;
+        }
+        Err(e) if e.is_rc(TPM2_RC_HANDLE) => {}
+        Err(e) => return Err(e),
+    }
+
+    let hierarchy = match auth {
+        Provision::Owner => Hierarchy::Owner,
+        Provision::Platform => Hierarchy::Platform,
+    };
+    let primary_key_handle = context
+        .create_primary(hierarchy, template, None, None, None, None)?
+        .key_handle;
+    let result = context.evict_control(
+        auth,
+        primary_key_handle.into(),
+        Persistent::from(persistent),
+    );
+    context.flush_context(primary_key_handle.into())?;
+    result.map(KeyHandle::from)
+}
+
+/// Checks if `public` could have been created from `template`.
+fn matches_template(public: &Public, template: &Public) -> bool {
+    public.object_attributes() == template.object_attributes()
+        && public.name_hashing_algorithm() == template.name_hashing_algorithm()
+        && public.auth_policy() == template.auth_policy()
+        && match (public, template) {
+            (
+                Public::Rsa { parameters, .. },
+                Public::Rsa {
+                    parameters: template_parameters,
+                    ..
+                },
+            ) => parameters == template_parameters,
+            (
+                Public::Ecc { parameters, .. },
+                Public::Ecc {
+                    parameters: template_parameters,
+                    ..
+                },
+            ) => parameters == template_parameters,
+            (
+                Public::KeyedHash { parameters, .. },
+                Public::KeyedHash {
+                    parameters: template_parameters,
+                    ..
+                },
+            ) => parameters == template_parameters,
+            (
+                Public::SymCipher { parameters, .. },
+                Public::SymCipher {
+                    parameters: template_parameters,
+                    ..
+                },
+            ) => parameters == template_parameters,
+            _ => false,
+        }
+}
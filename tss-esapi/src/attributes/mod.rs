@@ -1,4 +1,5 @@
 //! Module for representation of attributes
+use std::fmt;
 
 /// Representation of the attributes defined in the
 /// Attribute structures -> TPMA_OBJECT section of
@@ -21,9 +22,30 @@ pub mod algorithm;
 
 pub mod command_code;
 
+/// Representation of the attributes defined in the
+/// Attribute structures -> TPMA_ACT section of
+/// the specification.
+#[cfg(has_act)]
+pub mod act;
+
+#[cfg(has_act)]
+pub use act::ActAttributes;
 pub use algorithm::AlgorithmAttributes;
 pub use command_code::CommandCodeAttributes;
 pub use locality::{LocalityAttributes, LocalityAttributesBuilder};
 pub use nv_index::{NvIndexAttributes, NvIndexAttributesBuilder};
 pub use object::{ObjectAttributes, ObjectAttributesBuilder};
 pub use session::{SessionAttributes, SessionAttributesBuilder, SessionAttributesMask};
+
+/// Writes the names of the set flags separated by ` | `,
+/// or `none` if no flag is set.
+fn fmt_flags(f: &mut fmt::Formatter<'_>, flags: &[(bool, &str)]) -> fmt::Result {
+    let mut set_flags = flags.iter().filter(|(set, _)| *set).map(|(_, name)| name);
+    match set_flags.next() {
+        Some(first) => {
+            write!(f, "{}", first)?;
+            set_flags.try_for_each(|name| write!(f, " | {}", name))
+        }
+        None => write!(f, "none"),
+    }
+}
@@ -8,13 +8,12 @@ use crate::{
 
 use bitfield::bitfield;
 use log::error;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 bitfield! {
     /// Bitfield representing the nv index attributes.
     #[derive(Copy, Clone, Eq, PartialEq)]
     pub struct NvIndexAttributes(TPMA_NV);
-    impl Debug;
     // NV Index Attributes
     pub pp_write, _: 0;
     _, set_pp_write: 0;
@@ -112,6 +111,51 @@ impl NvIndexAttributes {
     pub const fn builder() -> NvIndexAttributesBuilder {
         NvIndexAttributesBuilder::new()
     }
+
+    /// The attributes paired with their names in the specification.
+    fn flags(&self) -> [(bool, &'static str); 21] {
+        [
+            (self.pp_write(), "PPWRITE"),
+            (self.owner_write(), "OWNERWRITE"),
+            (self.auth_write(), "AUTHWRITE"),
+            (self.policy_write(), "POLICYWRITE"),
+            (self.policy_delete(), "POLICY_DELETE"),
+            (self.write_locked(), "WRITELOCKED"),
+            (self.write_all(), "WRITEALL"),
+            (self.write_define(), "WRITEDEFINE"),
+            (self.write_stclear(), "WRITE_STCLEAR"),
+            (self.global_lock(), "GLOBALLOCK"),
+            (self.pp_read(), "PPREAD"),
+            (self.owner_read(), "OWNERREAD"),
+            (self.auth_read(), "AUTHREAD"),
+            (self.policy_read(), "POLICYREAD"),
+            (self.no_da(), "NO_DA"),
+            (self.orderly(), "ORDERLY"),
+            (self.clear_stclear(), "CLEAR_STCLEAR"),
+            (self.read_locked(), "READLOCKED"),
+            (self.written(), "WRITTEN"),
+            (self.platform_create(), "PLATFORMCREATE"),
+            (self.read_stclear(), "READ_STCLEAR"),
+        ]
+    }
+}
+
+/// Lists the names of the attributes that are set followed by
+/// the index type, e.g. `OWNERWRITE | OWNERREAD (TPM_NT: Ordinary)`.
+impl fmt::Display for NvIndexAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_flags(f, &self.flags())?;
+        match self.index_type() {
+            Ok(index_type) => write!(f, " (TPM_NT: {:?})", index_type),
+            Err(_) => write!(f, " (TPM_NT: {:#X})", self.tss_index_type()),
+        }
+    }
+}
+
+impl fmt::Debug for NvIndexAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NvIndexAttributes(0x{:08X}: {})", self.0, self)
+    }
 }
 
 impl TryFrom<TPMA_NV> for NvIndexAttributes {
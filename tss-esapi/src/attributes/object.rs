@@ -1,11 +1,11 @@
 use crate::{tss2_esys::TPMA_OBJECT, Result};
 use bitfield::bitfield;
+use std::fmt;
 
 bitfield! {
     /// Bitfield representing the object attributes.
     #[derive(Copy, Clone, Eq, PartialEq)]
     pub struct ObjectAttributes(TPMA_OBJECT);
-    impl Debug;
     // Object attribute flags
     pub fixed_tpm, _: 1;
     _, set_fixed_tpm: 1;
@@ -63,6 +63,38 @@ impl ObjectAttributes {
     pub const fn builder() -> ObjectAttributesBuilder {
         ObjectAttributesBuilder::new()
     }
+
+    /// The attributes paired with their names in the specification.
+    fn flags(&self) -> [(bool, &'static str); 12] {
+        [
+            (self.fixed_tpm(), "fixedTPM"),
+            (self.st_clear(), "stClear"),
+            (self.fixed_parent(), "fixedParent"),
+            (self.sensitive_data_origin(), "sensitiveDataOrigin"),
+            (self.user_with_auth(), "userWithAuth"),
+            (self.admin_with_policy(), "adminWithPolicy"),
+            (self.no_da(), "noDA"),
+            (self.encrypted_duplication(), "encryptedDuplication"),
+            (self.restricted(), "restricted"),
+            (self.decrypt(), "decrypt"),
+            (self.sign_encrypt(), "sign"),
+            (self.x509_sign(), "x509sign"),
+        ]
+    }
+}
+
+/// Lists the names of the attributes that are set, e.g.
+/// `fixedTPM | fixedParent | restricted | sign`.
+impl fmt::Display for ObjectAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_flags(f, &self.flags())
+    }
+}
+
+impl fmt::Debug for ObjectAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ObjectAttributes(0x{:08X}: {})", self.0, self)
+    }
 }
 
 impl From<ObjectAttributes> for TPMA_OBJECT {
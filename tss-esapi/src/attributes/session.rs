@@ -1,6 +1,6 @@
 use crate::{tss2_esys::TPMA_SESSION, Error, Result, WrapperErrorKind};
 use bitfield::bitfield;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 // SESSION ATTRIBUTES
 
@@ -8,7 +8,6 @@ bitfield! {
     /// Struct representing the session attributes.
     #[derive(Copy, Clone, Eq, PartialEq)]
     pub struct SessionAttributes(TPMA_SESSION);
-    impl Debug;
 
     _, set_continue_session: 0;
     pub continue_session, _: 0;
@@ -38,6 +37,32 @@ impl SessionAttributes {
         }
         Ok(())
     }
+
+    /// The attributes paired with their names in the specification.
+    fn flags(&self) -> [(bool, &'static str); 6] {
+        [
+            (self.continue_session(), "continueSession"),
+            (self.audit_exclusive(), "auditExclusive"),
+            (self.audit_reset(), "auditReset"),
+            (self.decrypt(), "decrypt"),
+            (self.encrypt(), "encrypt"),
+            (self.audit(), "audit"),
+        ]
+    }
+}
+
+/// Lists the names of the attributes that are set, e.g.
+/// `continueSession | decrypt | encrypt`.
+impl fmt::Display for SessionAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_flags(f, &self.flags())
+    }
+}
+
+impl fmt::Debug for SessionAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SessionAttributes(0x{:02X}: {})", self.0, self)
+    }
 }
 
 impl TryFrom<TPMA_SESSION> for SessionAttributes {
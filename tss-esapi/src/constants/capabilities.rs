@@ -14,7 +14,7 @@ use crate::{
 };
 
 // Enum representing the different TPM Capability Type values.
-#[derive(FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
 pub enum CapabilityType {
     Algorithms = TPM2_CAP_ALGS,
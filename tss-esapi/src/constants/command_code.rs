@@ -6,7 +6,7 @@ use crate::{traits::impl_mu_simple, tss2_esys::TPM2_CC, Error, Result, WrapperEr
 use log::error;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 use structure::CommandCodeStructure;
 
 /// Enum representing the command code constants.
@@ -151,4 +151,129 @@ impl From<CommandCode> for TPM2_CC {
     }
 }
 
+/// Displays the name of the command as it appears
+/// in the specification (e.g. `TPM2_Create`).
+impl fmt::Display for CommandCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CommandCode::NvUndefineSpaceSpecial => "TPM2_NV_UndefineSpaceSpecial",
+            CommandCode::EvictControl => "TPM2_EvictControl",
+            CommandCode::HierarchyControl => "TPM2_HierarchyControl",
+            CommandCode::NvUndefineSpace => "TPM2_NV_UndefineSpace",
+            CommandCode::ChangeEps => "TPM2_ChangeEPS",
+            CommandCode::ChangePps => "TPM2_ChangePPS",
+            CommandCode::Clear => "TPM2_Clear",
+            CommandCode::ClearControl => "TPM2_ClearControl",
+            CommandCode::ClockSet => "TPM2_ClockSet",
+            CommandCode::HierarchyChangeAuth => "TPM2_HierarchyChangeAuth",
+            CommandCode::NvDefineSpace => "TPM2_NV_DefineSpace",
+            CommandCode::PcrAllocate => "TPM2_PCR_Allocate",
+            CommandCode::PcrSetAuthPolicy => "TPM2_PCR_SetAuthPolicy",
+            CommandCode::PpCommands => "TPM2_PP_Commands",
+            CommandCode::SetPrimaryPolicy => "TPM2_SetPrimaryPolicy",
+            CommandCode::FieldUpgradeStart => "TPM2_FieldUpgradeStart",
+            CommandCode::ClockRateAdjust => "TPM2_ClockRateAdjust",
+            CommandCode::CreatePrimary => "TPM2_CreatePrimary",
+            CommandCode::NvGlobalWriteLock => "TPM2_NV_GlobalWriteLock",
+            CommandCode::GetCommandAuditDigest => "TPM2_GetCommandAuditDigest",
+            CommandCode::NvIncrement => "TPM2_NV_Increment",
+            CommandCode::NvSetBits => "TPM2_NV_SetBits",
+            CommandCode::NvExtend => "TPM2_NV_Extend",
+            CommandCode::NvWrite => "TPM2_NV_Write",
+            CommandCode::NvWriteLock => "TPM2_NV_WriteLock",
+            CommandCode::DictionaryAttackLockReset => "TPM2_DictionaryAttackLockReset",
+            CommandCode::DictionaryAttackParameters => "TPM2_DictionaryAttackParameters",
+            CommandCode::NvChangeAuth => "TPM2_NV_ChangeAuth",
+            CommandCode::PcrEvent => "TPM2_PCR_Event",
+            CommandCode::PcrReset => "TPM2_PCR_Reset",
+            CommandCode::SequenceComplete => "TPM2_SequenceComplete",
+            CommandCode::SetAlgorithmSet => "TPM2_SetAlgorithmSet",
+            CommandCode::SetCommandCodeAuditStatus => "TPM2_SetCommandCodeAuditStatus",
+            CommandCode::FieldUpgradeData => "TPM2_FieldUpgradeData",
+            CommandCode::IncrementalSelfTest => "TPM2_IncrementalSelfTest",
+            CommandCode::SelfTest => "TPM2_SelfTest",
+            CommandCode::Startup => "TPM2_Startup",
+            CommandCode::Shutdown => "TPM2_Shutdown",
+            CommandCode::StirRandom => "TPM2_StirRandom",
+            CommandCode::ActivateCredential => "TPM2_ActivateCredential",
+            CommandCode::Certify => "TPM2_Certify",
+            CommandCode::PolicyNv => "TPM2_PolicyNV",
+            CommandCode::CertifyCreation => "TPM2_CertifyCreation",
+            CommandCode::Duplicate => "TPM2_Duplicate",
+            CommandCode::GetTime => "TPM2_GetTime",
+            CommandCode::GetSessionAuditDigest => "TPM2_GetSessionAuditDigest",
+            CommandCode::NvRead => "TPM2_NV_Read",
+            CommandCode::NvReadLock => "TPM2_NV_ReadLock",
+            CommandCode::ObjectChangeAuth => "TPM2_ObjectChangeAuth",
+            CommandCode::PolicySecret => "TPM2_PolicySecret",
+            CommandCode::Rewrap => "TPM2_Rewrap",
+            CommandCode::Create => "TPM2_Create",
+            CommandCode::EcdhZGen => "TPM2_ECDH_ZGen",
+            CommandCode::Hmac => "TPM2_HMAC",
+            CommandCode::Import => "TPM2_Import",
+            CommandCode::Load => "TPM2_Load",
+            CommandCode::Quote => "TPM2_Quote",
+            CommandCode::RsaDecrypt => "TPM2_RSA_Decrypt",
+            CommandCode::HmacStart => "TPM2_HMAC_Start",
+            CommandCode::SequenceUpdate => "TPM2_SequenceUpdate",
+            CommandCode::Sign => "TPM2_Sign",
+            CommandCode::Unseal => "TPM2_Unseal",
+            CommandCode::PolicySigned => "TPM2_PolicySigned",
+            CommandCode::ContextLoad => "TPM2_ContextLoad",
+            CommandCode::ContextSave => "TPM2_ContextSave",
+            CommandCode::EcdhKeyGen => "TPM2_ECDH_KeyGen",
+            CommandCode::EncryptDecrypt => "TPM2_EncryptDecrypt",
+            CommandCode::FlushContext => "TPM2_FlushContext",
+            CommandCode::LoadExternal => "TPM2_LoadExternal",
+            CommandCode::MakeCredential => "TPM2_MakeCredential",
+            CommandCode::NvReadPublic => "TPM2_NV_ReadPublic",
+            CommandCode::PolicyAuthorize => "TPM2_PolicyAuthorize",
+            CommandCode::PolicyAuthValue => "TPM2_PolicyAuthValue",
+            CommandCode::PolicyCommandCode => "TPM2_PolicyCommandCode",
+            CommandCode::PolicyCounterTimer => "TPM2_PolicyCounterTimer",
+            CommandCode::PolicyCpHash => "TPM2_PolicyCpHash",
+            CommandCode::PolicyLocality => "TPM2_PolicyLocality",
+            CommandCode::PolicyNameHash => "TPM2_PolicyNameHash",
+            CommandCode::PolicyOr => "TPM2_PolicyOR",
+            CommandCode::PolicyTicket => "TPM2_PolicyTicket",
+            CommandCode::ReadPublic => "TPM2_ReadPublic",
+            CommandCode::RsaEncrypt => "TPM2_RSA_Encrypt",
+            CommandCode::StartAuthSession => "TPM2_StartAuthSession",
+            CommandCode::VerifySignature => "TPM2_VerifySignature",
+            CommandCode::EccParameters => "TPM2_ECC_Parameters",
+            CommandCode::FirmwareRead => "TPM2_FirmwareRead",
+            CommandCode::GetCapability => "TPM2_GetCapability",
+            CommandCode::GetRandom => "TPM2_GetRandom",
+            CommandCode::GetTestResult => "TPM2_GetTestResult",
+            CommandCode::Hash => "TPM2_Hash",
+            CommandCode::PcrRead => "TPM2_PCR_Read",
+            CommandCode::PolicyPcr => "TPM2_PolicyPCR",
+            CommandCode::PolicyRestart => "TPM2_PolicyRestart",
+            CommandCode::ReadClock => "TPM2_ReadClock",
+            CommandCode::PcrExtend => "TPM2_PCR_Extend",
+            CommandCode::PcrSetAuthValue => "TPM2_PCR_SetAuthValue",
+            CommandCode::NvCertify => "TPM2_NV_Certify",
+            CommandCode::EventSequenceComplete => "TPM2_EventSequenceComplete",
+            CommandCode::HashSequenceStart => "TPM2_HashSequenceStart",
+            CommandCode::PolicyPhysicalPresence => "TPM2_PolicyPhysicalPresence",
+            CommandCode::PolicyDuplicationSelect => "TPM2_PolicyDuplicationSelect",
+            CommandCode::PolicyGetDigest => "TPM2_PolicyGetDigest",
+            CommandCode::TestParms => "TPM2_TestParms",
+            CommandCode::Commit => "TPM2_Commit",
+            CommandCode::PolicyPassword => "TPM2_PolicyPassword",
+            CommandCode::ZGen2Phase => "TPM2_ZGen_2Phase",
+            CommandCode::EcEphemeral => "TPM2_EC_Ephemeral",
+            CommandCode::PolicyNvWritten => "TPM2_PolicyNvWritten",
+            CommandCode::PolicyTemplate => "TPM2_PolicyTemplate",
+            CommandCode::CreateLoaded => "TPM2_CreateLoaded",
+            CommandCode::PolicyAuthorizeNv => "TPM2_PolicyAuthorizeNV",
+            CommandCode::EncryptDecrypt2 => "TPM2_EncryptDecrypt2",
+            CommandCode::AcGetCapability => "TPM2_AC_GetCapability",
+            CommandCode::AcSend => "TPM2_AC_Send",
+            CommandCode::PolicyAcSendSelect => "TPM2_Policy_AC_SendSelect",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 impl_mu_simple!(CommandCode, TPM2_CC);
@@ -0,0 +1,54 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::tss::{
+        TPM2_LOADED_SESSION_FIRST, TPM2_NV_INDEX_FIRST, TPM2_NV_INDEX_LAST, TPM2_PERMANENT_FIRST,
+        TPM2_PERMANENT_LAST, TPM2_PERSISTENT_FIRST, TPM2_PERSISTENT_LAST, TPM2_POLICY_SESSION_LAST,
+        TPM2_TRANSIENT_FIRST, TPM2_TRANSIENT_LAST,
+    },
+    tss2_esys::TPM2_HANDLE,
+};
+
+/// Enum representing the ranges of TPM handles that
+/// can be listed using the handles capability.
+///
+/// # Details
+/// The [HandleRange::Session] range covers the loaded
+/// HMAC and policy sessions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HandleRange {
+    Transient,
+    Persistent,
+    NvIndex,
+    Permanent,
+    Session,
+}
+
+impl HandleRange {
+    /// Returns the first handle in the range.
+    pub const fn first(&self) -> TPM2_HANDLE {
+        match self {
+            HandleRange::Transient => TPM2_TRANSIENT_FIRST,
+            HandleRange::Persistent => TPM2_PERSISTENT_FIRST,
+            HandleRange::NvIndex => TPM2_NV_INDEX_FIRST,
+            HandleRange::Permanent => TPM2_PERMANENT_FIRST,
+            HandleRange::Session => TPM2_LOADED_SESSION_FIRST,
+        }
+    }
+
+    /// Returns the last handle in the range.
+    pub const fn last(&self) -> TPM2_HANDLE {
+        match self {
+            HandleRange::Transient => TPM2_TRANSIENT_LAST,
+            HandleRange::Persistent => TPM2_PERSISTENT_LAST,
+            HandleRange::NvIndex => TPM2_NV_INDEX_LAST,
+            HandleRange::Permanent => TPM2_PERMANENT_LAST,
+            HandleRange::Session => TPM2_POLICY_SESSION_LAST,
+        }
+    }
+
+    /// Checks if the handle lies within the range.
+    pub const fn contains(&self, handle: TPM2_HANDLE) -> bool {
+        self.first() <= handle && handle <= self.last()
+    }
+}
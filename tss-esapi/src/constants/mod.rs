@@ -59,9 +59,12 @@ pub mod command_code;
 /// Constants -> TPM_PT_PCR section of the specification.
 pub mod pcr_property_tag;
 
+pub mod handle_range;
+
 pub use capabilities::CapabilityType;
 pub use command_code::CommandCode;
 pub use ecc::EccCurveIdentifier;
+pub use handle_range::HandleRange;
 pub use nv_index_type::NvIndexType;
 pub use pcr_property_tag::PcrPropertyTag;
 pub use property_tag::PropertyTag;
@@ -1,21 +1,39 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod buffer_capture;
 mod handle_manager;
+mod timeout_tcti;
 use crate::{
-    attributes::SessionAttributesBuilder,
-    constants::{CapabilityType, PropertyTag, SessionType},
+    attributes::{CommandCodeAttributes, SessionAttributesBuilder},
+    constants::{
+        tss::{TPM2_CC_Commit, TPM2_ALG_FIRST, TPM2_CC_FIRST, TPM2_ECC_NONE, TPMA_CC_V},
+        CapabilityType, CommandCode, PropertyTag, SessionType,
+    },
     handles::{ObjectHandle, SessionHandle},
-    interface_types::{algorithm::HashingAlgorithm, session_handles::AuthSession},
-    structures::{CapabilityData, SymmetricDefinition},
+    interface_types::{algorithm::HashingAlgorithm, ecc::EccCurve, session_handles::AuthSession},
+    structures::{
+        AlgorithmProperty, AlgorithmPropertyList, CapabilityData, CommandCodeAttributesList,
+        CommandCodeList, CommitCounter, EccCurveList, ResourceBudget, SymmetricDefinition,
+    },
     tcti_ldr::{TabrmdConfig, TctiContext, TctiNameConf},
     tss2_esys::*,
     Error, Result, ReturnCode, WrapperErrorKind as ErrorKind,
 };
+#[cfg(feature = "abstraction")]
+use crate::{
+    interface_types::reserved_handles::Hierarchy,
+    structures::{CreatePrimaryKeyResult, Name},
+};
+use buffer_capture::BufferCapture;
 use handle_manager::HandleManager;
-use log::{debug, error};
+use log::{debug, error, warn};
 use malloced::Malloced;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::ptr::null_mut;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use timeout_tcti::TimeoutTcti;
 
 /// Safe abstraction over an ESYS_CONTEXT.
 ///
@@ -55,12 +73,53 @@ pub struct Context {
     ),
     /// TCTI context handle associated with the ESYS context.
     /// As with the ESYS context, an optional Mbox wrapper allows the context to be deallocated.
-    _tcti_context: TctiContext,
+    tcti_context: Option<EsysTcti>,
+    /// Whether the calls to the TCTI are intercepted, see [Context::new_with_intercepted_tcti].
+    intercept_tcti: bool,
     /// Handle manager that keep tracks of the state of the handles and how they are to be
     /// disposed.
     handle_manager: HandleManager,
     /// A cache of determined TPM limits
     cached_tpm_properties: HashMap<PropertyTag, u32>,
+    /// A cache of the capability data that does not change while
+    /// the TPM is running, keyed by capability type and first property.
+    cached_capabilities: HashMap<(CapabilityType, u32), (CapabilityData, bool)>,
+    /// A cache of the ECC curves supported by the TPM.
+    cached_ecc_curves: Option<Vec<EccCurve>>,
+    /// A cache of the primary keys created from templates, keyed by hierarchy
+    /// and marshalled template, see `abstraction::primary`.
+    #[cfg(feature = "abstraction")]
+    cached_primary_keys: HashMap<(Hierarchy, Vec<u8>), (Name, CreatePrimaryKeyResult)>,
+    /// The capture of the command and response buffers, shared with
+    /// the TCTI so that it is kept when the context is reconnected.
+    buffer_capture: Arc<Mutex<BufferCapture>>,
+}
+
+/// The TCTI context given to ESYS.
+#[derive(Debug)]
+enum EsysTcti {
+    Direct(TctiContext),
+    /// The calls to the TCTI are intercepted, in order to
+    /// time out commands and capture the buffers.
+    Intercepted(Box<TimeoutTcti>),
+}
+
+impl EsysTcti {
+    /// Get access to the C pointer given to ESYS.
+    fn tcti_context_ptr(&mut self) -> *mut TSS2_TCTI_CONTEXT {
+        match self {
+            EsysTcti::Direct(tcti_context) => tcti_context.tcti_context_ptr(),
+            EsysTcti::Intercepted(timeout_tcti) => timeout_tcti.tcti_context_ptr(),
+        }
+    }
+
+    /// Returns the intercepting TCTI, if the calls are intercepted.
+    fn intercepted(&mut self) -> Option<&mut TimeoutTcti> {
+        match self {
+            EsysTcti::Direct(_) => None,
+            EsysTcti::Intercepted(timeout_tcti) => Some(timeout_tcti),
+        }
+    }
 }
 
 // Implementation of the TPM commands
@@ -88,33 +147,93 @@ impl Context {
     /// * if either `Tss2_TctiLdr_Initiialize` or `Esys_Initialize` fail, a corresponding
     /// Tss2ResponseCode will be returned
     pub fn new(tcti_name_conf: TctiNameConf) -> Result<Self> {
-        let mut esys_context = null_mut();
+        Context::new_with_tcti(TctiContext::initialize(tcti_name_conf)?)
+    }
 
-        let mut _tcti_context = TctiContext::initialize(tcti_name_conf)?;
+    /// Create a new ESYS context using an initialized TCTI context.
+    ///
+    /// # Details
+    /// This allows the TCTI context to be initialized separately
+    /// from the ESYS context.
+    ///
+    /// # Errors
+    /// * if `Esys_Initialize` fails, a corresponding Tss2ResponseCode will be returned
+    pub fn new_with_tcti(tcti_context: TctiContext) -> Result<Self> {
+        Context::create(tcti_context, false)
+    }
 
-        ReturnCode::ensure_success(
-            unsafe {
-                Esys_Initialize(
-                    &mut esys_context,
-                    _tcti_context.tcti_context_ptr(),
-                    null_mut(),
-                )
-            },
-            |ret| {
-                error!("Error when creating a new context: {:#010X}", ret);
-            },
-        )?;
+    /// Create a new ESYS context whose calls to the TCTI are intercepted.
+    ///
+    /// # Details
+    /// Intercepting the calls to the TCTI is required in order to time out
+    /// commands with [Context::execute_with_timeout].
+    ///
+    /// # Errors
+    /// * if `Esys_Initialize` fails, a corresponding Tss2ResponseCode will be returned
+    pub fn new_with_intercepted_tcti(tcti_context: TctiContext) -> Result<Self> {
+        Context::create(tcti_context, true)
+    }
 
-        let esys_context = unsafe { Some(Malloced::from_raw(esys_context)) };
+    /// Private function creating a context using an initialized TCTI context.
+    fn create(tcti_context: TctiContext, intercept_tcti: bool) -> Result<Self> {
+        let buffer_capture = Arc::new(Mutex::new(BufferCapture::default()));
+        let (esys_context, tcti_context) =
+            Context::initialize(tcti_context, intercept_tcti, buffer_capture.clone())?;
         Ok(Context {
-            esys_context,
+            esys_context: Some(esys_context),
             sessions: (None, None, None),
-            _tcti_context,
+            tcti_context: Some(tcti_context),
+            intercept_tcti,
             handle_manager: HandleManager::new(),
             cached_tpm_properties: HashMap::new(),
+            cached_capabilities: HashMap::new(),
+            cached_ecc_curves: None,
+            #[cfg(feature = "abstraction")]
+            cached_primary_keys: HashMap::new(),
+            buffer_capture,
         })
     }
 
+    /// Reconnects the context to the TPM using the desired TCTI
+    ///
+    /// # Details
+    /// The current ESYS and TCTI contexts are finalized and new ones are
+    /// initialized in their place. This allows the context to be used again
+    /// after the connection to the TPM has been lost, e.g. if the TABRMD
+    /// has been restarted.
+    ///
+    /// The cached TPM properties are kept, so the new connection must be to
+    /// the same TPM. The cached capabilities are discarded, see
+    /// [Context::refresh_capabilities]. All the handles and sessions of the context are lost,
+    /// they are forgotten without being flushed or closed.
+    ///
+    /// The calls to the new TCTI are intercepted if they were intercepted
+    /// before, see [Context::new_with_intercepted_tcti].
+    ///
+    /// # Errors
+    /// * if either `Tss2_TctiLdr_Initiialize` or `Esys_Initialize` fail, a corresponding
+    /// Tss2ResponseCode will be returned. The context is then left without a connection
+    /// to the TPM and every command will fail until it has been reconnected.
+    pub fn reconnect(&mut self, tcti_name_conf: TctiNameConf) -> Result<()> {
+        debug!("Reconnecting context.");
+        self.finalize();
+        self.sessions = (None, None, None);
+        self.handle_manager = HandleManager::new();
+        #[cfg(feature = "abstraction")]
+        self.cached_primary_keys.clear();
+        self.refresh_capabilities();
+
+        let (esys_context, tcti_context) = Context::initialize(
+            TctiContext::initialize(tcti_name_conf)?,
+            self.intercept_tcti,
+            self.buffer_capture.clone(),
+        )?;
+        self.esys_context = Some(esys_context);
+        self.tcti_context = Some(tcti_context);
+        debug!("Context reconnected.");
+        Ok(())
+    }
+
     /// Create a new ESYS context based on the TAB Resource Manager Daemon.
     /// The TABRMD will make sure that multiple users can use the TPM safely.
     ///
@@ -177,6 +296,80 @@ impl Context {
         self.sessions = session_handles;
     }
 
+    /// Set the sessions to be used in calls to ESAPI after checking
+    /// that they form a valid arrangement.
+    ///
+    /// # Arguments
+    /// * `session_handles` - The sessions to be used, see [Context::set_sessions].
+    /// * `requires_authorization` - Whether the commands that will be sent
+    ///                              with these sessions need an authorization
+    ///                              session.
+    ///
+    /// # Details
+    /// This is a checked alternative to [Context::set_sessions]. The sessions
+    /// are only set if the checks pass, otherwise the current sessions are
+    /// left untouched.
+    ///
+    /// ESAPI skips empty session slots, so a session set after an empty slot
+    /// is sent in an earlier position than the one it was set in, and may
+    /// end up authorizing a different handle. A warning is logged for such
+    /// arrangements.
+    ///
+    /// # Errors
+    /// * if `requires_authorization` is set and no first session has been
+    ///   provided a `MissingAuthSession` wrapper error is returned.
+    /// * if the same HMAC or policy session is provided more than once an
+    ///   `InconsistentParams` wrapper error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf, interface_types::session_handles::AuthSession};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// context
+    ///     .try_set_sessions((Some(AuthSession::Password), None, None), true)
+    ///     .expect("Failed to set sessions");
+    /// # assert_eq!((Some(AuthSession::Password), None, None), context.sessions());
+    /// ```
+    pub fn try_set_sessions(
+        &mut self,
+        session_handles: (
+            Option<AuthSession>,
+            Option<AuthSession>,
+            Option<AuthSession>,
+        ),
+        requires_authorization: bool,
+    ) -> Result<()> {
+        let sessions = [session_handles.0, session_handles.1, session_handles.2];
+        if requires_authorization && sessions[0].is_none() {
+            error!("An authorization session is required but the first session is missing");
+            return Err(Error::local_error(ErrorKind::MissingAuthSession));
+        }
+        for (index, session) in sessions.iter().enumerate() {
+            match session {
+                Some(AuthSession::Password) | None => {}
+                Some(_) => {
+                    if sessions[index + 1..].contains(session) {
+                        error!("The same session was provided more than once");
+                        return Err(Error::local_error(ErrorKind::InconsistentParams));
+                    }
+                }
+            }
+        }
+        if sessions
+            .windows(2)
+            .any(|pair| pair[0].is_none() && pair[1].is_some())
+        {
+            warn!("Sessions following an empty session slot will be sent in an earlier position");
+        }
+        self.set_sessions(session_handles);
+        Ok(())
+    }
+
     /// Clears any sessions that have been set
     ///
     /// This will result in the None handle being
@@ -318,6 +511,154 @@ impl Context {
         res
     }
 
+    /// Executes the closure, giving up if the TPM does not respond in time.
+    ///
+    /// # Details
+    /// The poll handles of the TCTI are used to wait for the responses to the
+    /// commands sent by the closure. If the TCTI does not provide poll handles,
+    /// the receive timeout of the TCTI is used instead. If the TCTI supports
+    /// neither, the responses are waited for without a timeout. Once `timeout`
+    /// has elapsed no more commands are sent, and the command waiting for a
+    /// response fails.
+    ///
+    /// A command cannot be taken back once it has been sent. So when the timeout
+    /// elapses, the TPM is given `timeout` once more to respond to the command.
+    /// If it does, the handles of the context are flushed. The connection to the
+    /// TPM is then closed, and the context has to be [reconnected][Context::reconnect]
+    /// before it can be used again.
+    ///
+    /// The context must have been created with [Context::new_with_intercepted_tcti].
+    ///
+    /// # Errors
+    /// * if the calls to the TCTI are not intercepted, an `UnsupportedParam`
+    /// wrapper error is returned.
+    /// * if the timeout elapses, a `Timeout` wrapper error is returned.
+    pub fn execute_with_timeout<F, T>(&mut self, timeout: Duration, f: F) -> Result<T>
+    where
+        // We only need to call f once, so it can be FnOnce
+        F: FnOnce(&mut Context) -> Result<T>,
+    {
+        if !self.intercept_tcti {
+            error!("Commands can only time out if the calls to the TCTI are intercepted");
+            return Err(Error::local_error(ErrorKind::UnsupportedParam));
+        }
+        if let Some(timeout_tcti) = self.tcti_context.as_mut().and_then(EsysTcti::intercepted) {
+            // A deadline too far in the future to be represented is never reached.
+            if let Some(deadline) = Instant::now().checked_add(timeout) {
+                timeout_tcti.set_deadline(deadline)?;
+            }
+        }
+
+        let res = f(self);
+
+        let timed_out = self
+            .tcti_context
+            .as_mut()
+            .and_then(EsysTcti::intercepted)
+            .map_or(false, |timeout_tcti| timeout_tcti.clear_deadline());
+        if timed_out {
+            self.flush_handles_after_timeout(timeout);
+            error!("Closing the connection to the TPM after a command timed out");
+            self.finalize();
+            return Err(Error::local_error(ErrorKind::Timeout));
+        }
+        res
+    }
+
+    /// Enables or disables the capture of the command and response buffers.
+    ///
+    /// # Details
+    /// While the capture is enabled, the hook set with
+    /// [Context::on_command_buffers] is invoked for every command
+    /// with the marshalled command and response, exactly as they
+    /// are exchanged with the TPM through the TCTI. This is meant
+    /// for debugging commands that fail unexpectedly.
+    ///
+    /// The buffers can only be captured if the calls to the TCTI are
+    /// intercepted, i.e. if the context was created with
+    /// [Context::new_with_intercepted_tcti].
+    ///
+    /// The parameters of the commands, which can be sensitive, are
+    /// redacted unless redaction is disabled, see [Context::set_buffer_redaction].
+    ///
+    /// # Errors
+    /// * if the calls to the TCTI are not intercepted, an `UnsupportedParam`
+    /// wrapper error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::sync::{Arc, Mutex};
+    /// # use tss_esapi::{tcti_ldr::TctiContext, Context, TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new_with_intercepted_tcti(
+    /// #         TctiContext::initialize(
+    /// #             TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #         ).expect("Failed to create TCTI context"),
+    /// #     ).expect("Failed to create Context");
+    /// let captured_buffers = Arc::new(Mutex::new(Vec::new()));
+    /// let hook_buffers = captured_buffers.clone();
+    /// context
+    ///     .on_command_buffers(Box::new(move |command, response| {
+    ///         hook_buffers
+    ///             .lock()
+    ///             .unwrap()
+    ///             .push((command.to_vec(), response.to_vec()));
+    ///     }))
+    ///     .expect("Failed to set the command buffers hook");
+    /// context
+    ///     .set_buffer_capture(true)
+    ///     .expect("Failed to enable the buffer capture");
+    /// let _random = context.get_random(8).expect("Failed to call get_random");
+    /// context
+    ///     .set_buffer_capture(false)
+    ///     .expect("Failed to disable the buffer capture");
+    /// assert_eq!(captured_buffers.lock().unwrap().len(), 1);
+    /// ```
+    pub fn set_buffer_capture(&mut self, enabled: bool) -> Result<()> {
+        self.ensure_buffers_can_be_captured()?;
+        self.buffer_capture
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_enabled(enabled);
+        Ok(())
+    }
+
+    /// Sets the hook invoked with the marshalled command and
+    /// response buffers while the capture is enabled.
+    ///
+    /// # Details
+    /// The hook replaces any previously set hook. If the hook panics,
+    /// the panic is caught and logged. See [Context::set_buffer_capture].
+    ///
+    /// # Errors
+    /// * if the calls to the TCTI are not intercepted, an `UnsupportedParam`
+    /// wrapper error is returned.
+    pub fn on_command_buffers(&mut self, hook: Box<dyn FnMut(&[u8], &[u8]) + Send>) -> Result<()> {
+        self.ensure_buffers_can_be_captured()?;
+        self.buffer_capture
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_hook(hook);
+        Ok(())
+    }
+
+    /// Enables or disables the redaction of the captured buffers.
+    ///
+    /// # Details
+    /// When redaction is enabled only the headers of the captured buffers
+    /// are kept, i.e. the tag, the size and the command or response code.
+    /// The handles, authorization areas and parameters are replaced by zeros,
+    /// so that no secrets are handed to the hook set with
+    /// [Context::on_command_buffers]. Redaction is enabled by default.
+    pub fn set_buffer_redaction(&mut self, enabled: bool) {
+        self.buffer_capture
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_redact(enabled);
+    }
+
     /// Execute the closure in f, and clear up the object after it's done before returning the result
     /// This is a convenience function that ensures object is always closed, even if an error occurs
     pub fn execute_with_temporary_object<F, T>(&mut self, object: ObjectHandle, f: F) -> Result<T>
@@ -331,6 +672,26 @@ impl Context {
         res
     }
 
+    /// Returns the number of handles tracked by the context.
+    ///
+    /// # Details
+    /// The tracked handles are the handles which will be flushed or
+    /// closed when the context is dropped.
+    pub fn tracked_handle_count(&self) -> usize {
+        self.handle_manager.open_handles_count()
+    }
+
+    /// Returns the number of tracked handles that occupy TPM memory.
+    ///
+    /// # Details
+    /// These are the handles of transient objects and sessions, which
+    /// will be flushed when the context is dropped. Keeping track of
+    /// them allows contexts to be saved before the resource manager
+    /// runs out of slots.
+    pub fn transient_handle_count(&self) -> usize {
+        self.handle_manager.handles_to_flush_count()
+    }
+
     /// Determine a TPM property
     ///
     /// # Details
@@ -386,34 +747,655 @@ impl Context {
         Ok(None)
     }
 
+    /// Retrieves the properties of all the algorithms implemented by the TPM.
+    ///
+    /// # Details
+    /// The algorithms are cached, see [Context::refresh_capabilities].
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let algorithms = context
+    ///     .get_algorithms()
+    ///     .expect("Failed to get the algorithms");
+    /// ```
+    pub fn get_algorithms(&mut self) -> Result<Vec<AlgorithmProperty>> {
+        let mut algorithms = Vec::new();
+        let mut next_algorithm = u32::from(TPM2_ALG_FIRST);
+        loop {
+            let (capability_data, more_data) = self.get_cached_capability(
+                CapabilityType::Algorithms,
+                next_algorithm,
+                AlgorithmPropertyList::MAX_SIZE as u32,
+            )?;
+            let algorithm_properties = match capability_data {
+                CapabilityData::Algorithms(algorithm_properties) => algorithm_properties,
+                _ => return Err(Error::WrapperError(ErrorKind::WrongValueFromTpm)),
+            };
+            let last_algorithm = match algorithm_properties.last() {
+                Some(algorithm_property) => {
+                    u32::from(TPM2_ALG_ID::from(algorithm_property.algorithm_identifier()))
+                }
+                None => break,
+            };
+            algorithms.extend(algorithm_properties);
+            if !more_data {
+                break;
+            }
+            next_algorithm = last_algorithm + 1;
+        }
+        Ok(algorithms)
+    }
+
+    /// Retrieves the ECC curves supported by the TPM.
+    ///
+    /// # Details
+    /// Curves that are not known by the crate are skipped.
+    /// The curves are cached, see [Context::refresh_capabilities].
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let ecc_curves = context
+    ///     .supported_ecc_curves()
+    ///     .expect("Failed to get the ECC curves");
+    /// ```
+    pub fn supported_ecc_curves(&mut self) -> Result<Vec<EccCurve>> {
+        if let Some(ecc_curves) = &self.cached_ecc_curves {
+            return Ok(ecc_curves.clone());
+        }
+        let mut ecc_curves = Vec::new();
+        let mut next_ecc_curve = u32::from(TPM2_ECC_NONE);
+        loop {
+            // The unparsed data is used, as the curves that are not known
+            // by the crate are skipped when the data is parsed.
+            let (capability_data, more_data) = self.execute_without_session(|ctx| {
+                ctx.get_capability_ffi(
+                    CapabilityType::EccCurves.into(),
+                    next_ecc_curve,
+                    EccCurveList::MAX_SIZE as u32,
+                )
+            })?;
+            if capability_data.capability != TPM2_CAP::from(CapabilityType::EccCurves) {
+                return Err(Error::WrapperError(ErrorKind::WrongValueFromTpm));
+            }
+            let tpml_ecc_curve = unsafe { capability_data.data.eccCurves };
+            let ecc_curve_list = EccCurveList::try_from(tpml_ecc_curve)?;
+            let last_ecc_curve = match tpml_ecc_curve
+                .eccCurves
+                .get(..tpml_ecc_curve.count as usize)
+                .and_then(<[TPM2_ECC_CURVE]>::last)
+            {
+                Some(&ecc_curve) => u32::from(ecc_curve),
+                None => break,
+            };
+            ecc_curves.extend(ecc_curve_list.into_inner().into_iter().map(EccCurve::from));
+            if !more_data {
+                break;
+            }
+            next_ecc_curve = last_ecc_curve + 1;
+        }
+        self.cached_ecc_curves = Some(ecc_curves.clone());
+        Ok(ecc_curves)
+    }
+
+    /// Retrieves the attributes of all the commands implemented by the TPM.
+    ///
+    /// # Details
+    /// The commands are cached, see [Context::refresh_capabilities].
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let commands = context
+    ///     .get_commands()
+    ///     .expect("Failed to get the commands");
+    /// ```
+    pub fn get_commands(&mut self) -> Result<Vec<CommandCodeAttributes>> {
+        let mut commands = Vec::new();
+        let mut next_command = TPM2_CC_FIRST;
+        loop {
+            let (capability_data, more_data) = self.get_cached_capability(
+                CapabilityType::Command,
+                next_command,
+                CommandCodeAttributesList::MAX_SIZE as u32,
+            )?;
+            let command_code_attributes = match capability_data {
+                CapabilityData::Commands(command_code_attributes) => command_code_attributes,
+                _ => return Err(Error::WrapperError(ErrorKind::WrongValueFromTpm)),
+            };
+            let last_command = match command_code_attributes.last() {
+                Some(attributes) if attributes.is_vendor_specific() => {
+                    TPMA_CC_V | u32::from(attributes.command_index())
+                }
+                Some(attributes) => u32::from(attributes.command_index()),
+                None => break,
+            };
+            commands.extend(command_code_attributes);
+            if !more_data {
+                break;
+            }
+            next_command = last_command + 1;
+        }
+        Ok(commands)
+    }
+
+    /// Retrieves the commands that require physical presence confirmation
+    /// when the platform is used for authorizing them.
+    ///
+    /// # Details
+    /// The list is not cached, as it is changed by `TPM2_PP_Commands`.
+    /// The list is empty if the TPM does not require physical presence
+    /// for any command.
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let pp_commands = context
+    ///     .pp_commands()
+    ///     .expect("Failed to get the physical presence commands");
+    /// ```
+    pub fn pp_commands(&mut self) -> Result<Vec<CommandCode>> {
+        self.get_command_code_list(CapabilityType::PpCommands)
+    }
+
+    /// Retrieves the commands that are audited.
+    ///
+    /// # Details
+    /// The list is not cached, as it is changed by
+    /// `TPM2_SetCommandCodeAuditStatus`.
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let audit_commands = context
+    ///     .audit_commands()
+    ///     .expect("Failed to get the audited commands");
+    /// ```
+    pub fn audit_commands(&mut self) -> Result<Vec<CommandCode>> {
+        self.get_command_code_list(CapabilityType::AuditCommands)
+    }
+
+    /// Retrieves the largest counter value returned by [Context::commit].
+    ///
+    /// # Details
+    /// The TPM does not report a property for the commit counter:
+    /// the counter is the least significant 16 bits of the commit
+    /// count of the TPM, so the value returned is [CommitCounter::MAX]
+    /// if the TPM implements `TPM2_Commit`.
+    ///
+    /// After this value the counter wraps around to 0. The counter and
+    /// the commit values are also not preserved across a TPM Reset. A
+    /// counter that is not larger than the one returned by a previous
+    /// commit therefore means that the previous commit values
+    /// must not be used anymore.
+    ///
+    /// # Errors
+    /// If the TPM does not implement `TPM2_Commit` then an
+    /// `UnsupportedParam` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let max_commit_id = context
+    ///     .max_commit_id()
+    ///     .expect("Failed to get the maximum commit id");
+    /// ```
+    pub fn max_commit_id(&mut self) -> Result<u16> {
+        let commit_implemented = self.get_commands()?.iter().any(|attributes| {
+            !attributes.is_vendor_specific()
+                && u32::from(attributes.command_index()) == TPM2_CC_Commit
+        });
+        if !commit_implemented {
+            error!("The TPM does not implement TPM2_Commit");
+            return Err(Error::local_error(ErrorKind::UnsupportedParam));
+        }
+        Ok(CommitCounter::MAX.value())
+    }
+
+    /// Retrieves the hashing algorithms of the active PCR banks.
+    ///
+    /// # Details
+    /// A PCR bank is active if it has at least one PCR allocated.
+    /// The PCR banks are cached, see [Context::refresh_capabilities].
+    ///
+    /// # Errors
+    /// If the TPM returns capability data of the wrong type
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let pcr_banks = context
+    ///     .get_pcr_banks()
+    ///     .expect("Failed to get the PCR banks");
+    /// ```
+    pub fn get_pcr_banks(&mut self) -> Result<Vec<HashingAlgorithm>> {
+        // All the PCR banks are returned in a single response.
+        let (capability_data, _) = self.get_cached_capability(CapabilityType::AssignedPcr, 0, 1)?;
+        match capability_data {
+            CapabilityData::AssignedPcr(assigned_pcrs) => Ok(assigned_pcrs
+                .get_selections()
+                .iter()
+                .filter(|pcr_selection| !pcr_selection.is_empty())
+                .map(|pcr_selection| pcr_selection.hashing_algorithm())
+                .collect()),
+            _ => Err(Error::WrapperError(ErrorKind::WrongValueFromTpm)),
+        }
+    }
+
+    /// Retrieves the vendor string of the TPM.
+    ///
+    /// # Details
+    /// The vendor string is made of the big-endian bytes of the
+    /// `VendorString1` to `VendorString4` properties, with any
+    /// trailing null characters removed.
+    ///
+    /// # Errors
+    /// If the TPM returns a vendor string that is not valid UTF-8
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let vendor_string = context
+    ///     .vendor_strings()
+    ///     .expect("Failed to get the vendor string");
+    /// ```
+    pub fn vendor_strings(&mut self) -> Result<String> {
+        self.get_tpm_property_string(&[
+            PropertyTag::VendorString1,
+            PropertyTag::VendorString2,
+            PropertyTag::VendorString3,
+            PropertyTag::VendorString4,
+        ])
+    }
+
+    /// Retrieves the four character manufacturer code of the TPM.
+    ///
+    /// # Details
+    /// Any trailing null characters are removed from the code.
+    ///
+    /// # Errors
+    /// If the TPM does not return a valid manufacturer code
+    /// then a `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let manufacturer = context
+    ///     .manufacturer()
+    ///     .expect("Failed to get the manufacturer");
+    /// ```
+    pub fn manufacturer(&mut self) -> Result<String> {
+        let manufacturer = self
+            .get_tpm_property(PropertyTag::Manufacturer)?
+            .ok_or_else(|| {
+                error!("The TPM did not return a manufacturer");
+                Error::WrapperError(ErrorKind::WrongValueFromTpm)
+            })?;
+        property_values_to_string(&[manufacturer])
+    }
+
+    /// Retrieves the number of transient objects and sessions
+    /// that can still be loaded in the TPM.
+    ///
+    /// # Details
+    /// Contrary to [Context::get_tpm_property], the values are not
+    /// cached as they change every time an object or a session is
+    /// loaded or flushed. They can be used to decide whether some
+    /// contexts need to be saved before loading more objects.
+    ///
+    /// # Errors
+    /// If the TPM does not return all the properties then a
+    /// `WrongValueFromTpm` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let budget = context
+    ///     .resource_budget()
+    ///     .expect("Failed to get the resource budget");
+    /// if budget.transient_available() == 0 {
+    ///     // Save the context of some objects before loading a new one.
+    /// }
+    /// ```
+    pub fn resource_budget(&mut self) -> Result<ResourceBudget> {
+        let (capabs, _) = self.execute_without_session(|ctx| {
+            ctx.get_capability(
+                CapabilityType::TpmProperties,
+                PropertyTag::HrLoadedAvail.into(),
+                4,
+            )
+        })?;
+        let props = match capabs {
+            CapabilityData::TpmProperties(props) => props,
+            _ => return Err(Error::WrapperError(ErrorKind::WrongValueFromTpm)),
+        };
+        let value_of = |property: PropertyTag| {
+            props.find(property).map(|tp| tp.value()).ok_or_else(|| {
+                error!("The TPM did not return the {:?} property", property);
+                Error::WrapperError(ErrorKind::WrongValueFromTpm)
+            })
+        };
+        Ok(ResourceBudget::new(
+            value_of(PropertyTag::HrTransientAvail)?,
+            value_of(PropertyTag::HrLoadedAvail)?,
+            value_of(PropertyTag::HrActiveAvail)?,
+        ))
+    }
+
+    /// Discards the cached capability data of the TPM.
+    ///
+    /// # Details
+    /// The algorithms, commands, ECC curves and PCR banks of the TPM
+    /// are cached the first time they are retrieved, because they do not
+    /// change while the TPM is running. The cache is discarded when the
+    /// context is reconnected or the TPM is cleared, this can be used to
+    /// force the capabilities to be queried again in any other case, e.g.
+    /// after the PCR banks have been allocated and the TPM restarted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// context.refresh_capabilities();
+    /// let pcr_banks = context
+    ///     .get_pcr_banks()
+    ///     .expect("Failed to get the PCR banks");
+    /// ```
+    pub fn refresh_capabilities(&mut self) {
+        self.cached_capabilities.clear();
+        self.cached_ecc_curves = None;
+    }
+
     // ////////////////////////////////////////////////////////////////////////
     //  Private Methods Section
     // ////////////////////////////////////////////////////////////////////////
 
-    /// Returns a mutable reference to the native ESYS context handle.
+    /// Returns the cached primary key created in the hierarchy from the
+    /// marshalled template, if it is still loaded.
+    #[cfg(feature = "abstraction")]
+    pub(crate) fn cached_primary_key(
+        &mut self,
+        hierarchy: Hierarchy,
+        template: &[u8],
+    ) -> Option<CreatePrimaryKeyResult> {
+        let cache_key = (hierarchy, template.to_vec());
+        let (name, result) = self.cached_primary_keys.get(&cache_key).cloned()?;
+        let object_handle = ObjectHandle::from(result.key_handle);
+        // The handle value might have been reused for another object
+        // after the primary key was flushed, so the name is compared.
+        if self.handle_manager.is_open(object_handle)
+            && self.tr_get_name(object_handle).ok().as_ref() == Some(&name)
+        {
+            Some(result)
+        } else {
+            let _ = self.cached_primary_keys.remove(&cache_key);
+            None
+        }
+    }
+
+    /// Caches the primary key created in the hierarchy from the
+    /// marshalled template.
+    #[cfg(feature = "abstraction")]
+    pub(crate) fn cache_primary_key(
+        &mut self,
+        hierarchy: Hierarchy,
+        template: Vec<u8>,
+        result: &CreatePrimaryKeyResult,
+    ) -> Result<()> {
+        let name = self.tr_get_name(result.key_handle.into())?;
+        let _ = self
+            .cached_primary_keys
+            .insert((hierarchy, template), (name, result.clone()));
+        Ok(())
+    }
+
+    /// Ensures that the calls to the TCTI are intercepted, which is
+    /// needed to capture the command and response buffers.
+    fn ensure_buffers_can_be_captured(&self) -> Result<()> {
+        if !self.intercept_tcti {
+            error!("Buffers can only be captured if the calls to the TCTI are intercepted");
+            return Err(Error::local_error(ErrorKind::UnsupportedParam));
+        }
+        Ok(())
+    }
+
+    /// Returns the big-endian bytes of the TPM properties as a string,
+    /// with any trailing null characters removed.
+    fn get_tpm_property_string(&mut self, properties: &[PropertyTag]) -> Result<String> {
+        let mut values = Vec::with_capacity(properties.len());
+        for &property in properties {
+            if let Some(value) = self.get_tpm_property(property)? {
+                values.push(value);
+            }
+        }
+        property_values_to_string(&values)
+    }
+
+    /// Returns all the command codes of a capability that
+    /// returns a list of command codes.
+    fn get_command_code_list(&mut self, capability: CapabilityType) -> Result<Vec<CommandCode>> {
+        let mut command_codes = Vec::new();
+        let mut next_command = TPM2_CC_FIRST;
+        loop {
+            let (capability_data, more_data) = self.execute_without_session(|ctx| {
+                ctx.get_capability(capability, next_command, CommandCodeList::MAX_SIZE as u32)
+            })?;
+            let command_code_list = match (capability, capability_data) {
+                (CapabilityType::PpCommands, CapabilityData::PpCommands(list))
+                | (CapabilityType::AuditCommands, CapabilityData::AuditCommands(list)) => list,
+                _ => return Err(Error::WrapperError(ErrorKind::WrongValueFromTpm)),
+            };
+            let last_command = match command_code_list.last() {
+                Some(&command_code) => TPM2_CC::from(command_code),
+                None => break,
+            };
+            command_codes.extend(command_code_list.into_inner());
+            if !more_data {
+                break;
+            }
+            next_command = last_command + 1;
+        }
+        Ok(command_codes)
+    }
+
+    /// Returns the capability data for the arguments of `get_capability`,
+    /// querying the TPM only if it has not been cached.
+    fn get_cached_capability(
+        &mut self,
+        capability: CapabilityType,
+        property: u32,
+        property_count: u32,
+    ) -> Result<(CapabilityData, bool)> {
+        if let Some(cached) = self.cached_capabilities.get(&(capability, property)) {
+            return Ok(cached.clone());
+        }
+        let response = self.execute_without_session(|ctx| {
+            ctx.get_capability(capability, property, property_count)
+        })?;
+        let _ = self
+            .cached_capabilities
+            .insert((capability, property), response.clone());
+        Ok(response)
+    }
+
+    /// Private function for initializing the ESYS and TCTI contexts.
+    fn initialize(
+        tcti_context: TctiContext,
+        intercept_tcti: bool,
+        buffer_capture: Arc<Mutex<BufferCapture>>,
+    ) -> Result<(Malloced<ESYS_CONTEXT>, EsysTcti)> {
+        let mut esys_context = null_mut();
+
+        let mut tcti_context = if intercept_tcti {
+            EsysTcti::Intercepted(TimeoutTcti::new(tcti_context, buffer_capture))
+        } else {
+            EsysTcti::Direct(tcti_context)
+        };
+
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_Initialize(
+                    &mut esys_context,
+                    tcti_context.tcti_context_ptr(),
+                    null_mut(),
+                )
+            },
+            |ret| {
+                error!("Error when creating a new context: {:#010X}", ret);
+            },
+        )?;
+
+        Ok((unsafe { Malloced::from_raw(esys_context) }, tcti_context))
+    }
+
+    /// Private function flushing the handles of the context after a command
+    /// timed out, once the TPM has responded to the command.
+    fn flush_handles_after_timeout(&mut self, timeout: Duration) {
+        let drained = match (
+            self.tcti_context.as_mut().and_then(EsysTcti::intercepted),
+            Instant::now().checked_add(timeout),
+        ) {
+            (Some(timeout_tcti), Some(deadline)) => timeout_tcti.drain_late_response(deadline),
+            (Some(_), None) => false,
+            (None, _) => return,
+        };
+        if !drained {
+            error!("The TPM did not respond to the command that timed out, the handles of the context cannot be flushed");
+            return;
+        }
+        for handle in self.handle_manager.handles_to_flush() {
+            debug!("Flushing handle {}", ESYS_TR::from(handle));
+            if let Err(e) = self.flush_context(handle) {
+                error!("Error when flushing handle after a timeout: {}", e);
+            }
+        }
+    }
+
+    /// Private function for finalizing the ESYS and TCTI contexts.
+    fn finalize(&mut self) {
+        if let Some(esys_context) = self.esys_context.take() {
+            unsafe { Esys_Finalize(&mut Malloced::<ESYS_CONTEXT>::into_raw(esys_context)) };
+        }
+        // The TCTI context is finalized when dropped, which
+        // must happen after the ESYS context has been finalized.
+        let _ = self.tcti_context.take();
+    }
+
+    /// Returns a pointer to the ESYS context.
+    ///
+    /// # Details
+    /// A null pointer is returned if the context is not connected,
+    /// which ESYS rejects with a bad reference error.
     fn mut_context(&mut self) -> *mut ESYS_CONTEXT {
         self.esys_context
             .as_mut()
-            .map(Malloced::<ESYS_CONTEXT>::as_mut_ptr)
-            .unwrap() // will only fail if called from Drop after .take()
+            .map_or_else(null_mut, Malloced::<ESYS_CONTEXT>::as_mut_ptr)
     }
 
     /// Private method for retrieving the ESYS session handle for
     /// the optional session 1.
-    fn optional_session_1(&self) -> ESYS_TR {
-        SessionHandle::from(self.sessions.0).into()
+    fn optional_session_1(&self) -> Result<ESYS_TR> {
+        self.session_handle(self.sessions.0)
     }
 
     /// Private method for retrieving the ESYS session handle for
     /// the optional session 2.
-    fn optional_session_2(&self) -> ESYS_TR {
-        SessionHandle::from(self.sessions.1).into()
+    fn optional_session_2(&self) -> Result<ESYS_TR> {
+        self.session_handle(self.sessions.1)
     }
 
     /// Private method for retrieving the ESYS session handle for
     /// the optional session 3.
-    fn optional_session_3(&self) -> ESYS_TR {
-        SessionHandle::from(self.sessions.2).into()
+    fn optional_session_3(&self) -> Result<ESYS_TR> {
+        self.session_handle(self.sessions.2)
     }
 
     /// Private method that returns the required
@@ -422,11 +1404,11 @@ impl Context {
     fn required_session_1(&self) -> Result<ESYS_TR> {
         self.sessions
             .0
-            .map(|v| SessionHandle::from(v).into())
             .ok_or_else(|| {
                 error!("Missing session handle for authorization (authSession1 = None)");
                 Error::local_error(ErrorKind::MissingAuthSession)
             })
+            .and_then(|v| self.session_handle(Some(v)))
     }
 
     /// Private method that returns the required
@@ -435,11 +1417,31 @@ impl Context {
     fn required_session_2(&self) -> Result<ESYS_TR> {
         self.sessions
             .1
-            .map(|v| SessionHandle::from(v).into())
             .ok_or_else(|| {
                 error!("Missing session handle for authorization (authSession2 = None)");
                 Error::local_error(ErrorKind::MissingAuthSession)
             })
+            .and_then(|v| self.session_handle(Some(v)))
+    }
+
+    /// Private method that returns the ESYS session handle
+    /// of the session if it has not already been flushed
+    /// else returns an error.
+    ///
+    /// # Details
+    /// This is only used for the sessions in the three
+    /// authorization slots, the session handles that are
+    /// passed as command parameters are not checked.
+    fn session_handle(&self, session: Option<AuthSession>) -> Result<ESYS_TR> {
+        let session_handle = SessionHandle::from(session);
+        if self.handle_manager.is_flushed(session_handle.into()) {
+            error!(
+                "Session handle({}) has already been flushed",
+                ESYS_TR::from(session_handle)
+            );
+            return Err(Error::local_error(ErrorKind::SessionAlreadyFlushed));
+        }
+        Ok(session_handle.into())
     }
 
     /// Private function for handling that has been allocated with
@@ -453,10 +1455,32 @@ impl Context {
     }
 }
 
+/// Returns the big-endian bytes of the TPM property values as a string,
+/// with any trailing null characters removed.
+fn property_values_to_string(values: &[u32]) -> Result<String> {
+    let mut bytes = values
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect::<Vec<u8>>();
+    while bytes.last() == Some(&0) {
+        let _ = bytes.pop();
+    }
+    String::from_utf8(bytes).map_err(|e| {
+        error!("The TPM returned a string that is not valid UTF-8: {}", e);
+        Error::WrapperError(ErrorKind::WrongValueFromTpm)
+    })
+}
+
 impl Drop for Context {
     fn drop(&mut self) {
         debug!("Closing context.");
 
+        // A context that failed to reconnect has no handles to release.
+        if self.esys_context.is_none() {
+            debug!("Context closed.");
+            return;
+        }
+
         // Flush handles
         for handle in self.handle_manager.handles_to_flush() {
             debug!("Flushing handle {}", ESYS_TR::from(handle));
@@ -479,15 +1503,7 @@ impl Drop for Context {
         }
 
         // Close the context.
-        unsafe {
-            Esys_Finalize(
-                &mut self
-                    .esys_context
-                    .take()
-                    .map(Malloced::<ESYS_CONTEXT>::into_raw)
-                    .unwrap(), // should not fail based on how the context is initialised/used
-            )
-        };
+        self.finalize();
         debug!("Context closed.");
     }
 }
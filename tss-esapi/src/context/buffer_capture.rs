@@ -0,0 +1,96 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use log::error;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Size of the header of command and response buffers: the tag,
+/// the size and the command code or response code.
+const HEADER_SIZE: usize = 10;
+
+/// Hook receiving the marshalled command and response buffers.
+pub type CommandBuffersHook = Box<dyn FnMut(&[u8], &[u8]) + Send>;
+
+/// Captures the buffers exchanged with the TPM through the TCTI.
+///
+/// The parameters are redacted unless redaction is disabled.
+pub struct BufferCapture {
+    enabled: bool,
+    redact: bool,
+    hook: Option<CommandBuffersHook>,
+    command: Option<Vec<u8>>,
+}
+
+impl Default for BufferCapture {
+    fn default() -> Self {
+        BufferCapture {
+            enabled: false,
+            redact: true,
+            hook: None,
+            command: None,
+        }
+    }
+}
+
+impl BufferCapture {
+    /// Enables or disables the capture.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.command = None;
+    }
+
+    /// Enables or disables the redaction of the parameters.
+    pub fn set_redact(&mut self, redact: bool) {
+        self.redact = redact;
+    }
+
+    /// Sets the hook invoked with the captured buffers.
+    pub fn set_hook(&mut self, hook: CommandBuffersHook) {
+        self.hook = Some(hook);
+    }
+
+    /// Captures a command sent to the TPM.
+    pub fn capture_command(&mut self, command: &[u8]) {
+        if self.enabled {
+            self.command = Some(self.redacted(command));
+        }
+    }
+
+    /// Captures the response to the last command, and invokes
+    /// the hook with both buffers.
+    pub fn capture_response(&mut self, response: &[u8]) {
+        let command = match self.command.take() {
+            Some(command) if self.enabled => command,
+            _ => return,
+        };
+        let response = self.redacted(response);
+        if let Some(hook) = self.hook.as_mut() {
+            // Unwinding out of the TCTI callback would abort the process.
+            if catch_unwind(AssertUnwindSafe(|| hook(&command, &response))).is_err() {
+                error!("The command buffers hook panicked");
+            }
+        }
+    }
+
+    /// Copies the buffer, only keeping the header if redaction is enabled.
+    fn redacted(&self, buffer: &[u8]) -> Vec<u8> {
+        let mut buffer = buffer.to_vec();
+        if self.redact {
+            buffer
+                .iter_mut()
+                .skip(HEADER_SIZE)
+                .for_each(|byte| *byte = 0);
+        }
+        buffer
+    }
+}
+
+impl fmt::Debug for BufferCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferCapture")
+            .field("enabled", &self.enabled)
+            .field("redact", &self.redact)
+            .field("hook", &self.hook.as_ref().map(|_| "FnMut(&[u8], &[u8])"))
+            .finish()
+    }
+}
@@ -247,9 +247,9 @@ impl Context {
                 Esys_TR_FromTPMPublic(
                     self.mut_context(),
                     tpm_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut object,
                 )
             },
@@ -473,6 +473,10 @@ impl Context {
     /// # Returns
     /// A handle to the object that was created from the buffer.
     ///
+    /// The handle is tracked by the context and closed when the context
+    /// is dropped. The object itself is not flushed from the TPM, as it
+    /// may still be referenced by other handles.
+    ///
     /// # Errors
     /// * if the TPM cannot deserialize the buffer, a TSS error is returned.
     /// * if the buffer length cannot be converted to a `usize`, an `InvalidParam`
@@ -532,6 +536,8 @@ impl Context {
                 error!("Error while deserializing buffer: {}", ret);
             },
         )?;
+        self.handle_manager
+            .add_handle(handle.into(), HandleDropAction::Close)?;
         Ok(ObjectHandle::from(handle))
     }
 }
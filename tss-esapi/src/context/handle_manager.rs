@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{handles::ObjectHandle, tss2_esys::ESYS_TR, Error, Result, WrapperErrorKind};
 use log::error;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+/// Number of flushed handles remembered by the HandleManager.
+const MAX_FLUSHED_HANDLES: usize = 64;
 
 /// Enum representing the action to be taken
 /// when the handle is dropped.
@@ -19,6 +22,8 @@ pub enum HandleDropAction {
 #[derive(Debug)]
 pub struct HandleManager {
     open_handles: HashMap<ObjectHandle, HandleDropAction>,
+    flushed_handles: VecDeque<ObjectHandle>,
+    parent_handles: HashMap<ObjectHandle, ObjectHandle>,
 }
 
 impl HandleManager {
@@ -26,6 +31,8 @@ impl HandleManager {
     pub fn new() -> HandleManager {
         HandleManager {
             open_handles: HashMap::new(),
+            flushed_handles: VecDeque::new(),
+            parent_handles: HashMap::new(),
         }
     }
 
@@ -49,10 +56,32 @@ impl HandleManager {
         }
 
         log::trace!("add handle {:#010X}", handle.value());
+        // The ESYS handle values of flushed objects are reused.
+        self.flushed_handles
+            .retain(|&flushed_handle| flushed_handle != handle);
+        let _ = self.parent_handles.remove(&handle);
         let _ = self.open_handles.insert(handle, handle_drop_action);
         Ok(())
     }
 
+    /// Adds a handle of an object that was loaded under `parent`.
+    ///
+    /// # Details
+    /// The parent relationship is used to flush the object
+    /// before its parent when the Context is dropped.
+    pub fn add_child_handle(
+        &mut self,
+        handle: ObjectHandle,
+        parent: ObjectHandle,
+        handle_drop_action: HandleDropAction,
+    ) -> Result<()> {
+        self.add_handle(handle, handle_drop_action)?;
+        if self.open_handles.contains_key(&parent) {
+            let _ = self.parent_handles.insert(handle, parent);
+        }
+        Ok(())
+    }
+
     /// Sets the handle as flushed which removes it from the manager.
     ///
     /// # Errors
@@ -60,6 +89,13 @@ impl HandleManager {
     /// error but the handle will still be removed from the handler.
     pub fn set_as_flushed(&mut self, handle: ObjectHandle) -> Result<()> {
         log::trace!("set as flushed handle {:#010X}", handle.value());
+        self.flushed_handles
+            .retain(|&flushed_handle| flushed_handle != handle);
+        if self.flushed_handles.len() == MAX_FLUSHED_HANDLES {
+            let _ = self.flushed_handles.pop_front();
+        }
+        self.flushed_handles.push_back(handle);
+        self.remove_parent_relationships(handle);
         self.open_handles
             .remove(&handle)
             .ok_or_else(|| {
@@ -86,6 +122,7 @@ impl HandleManager {
     /// error but the handle will still be removed from the handler.
     pub fn set_as_closed(&mut self, handle: ObjectHandle) -> Result<()> {
         log::trace!("set as closed handle {:#010X}", handle.value());
+        self.remove_parent_relationships(handle);
         self.open_handles
             .remove(&handle)
             .ok_or_else(|| {
@@ -106,8 +143,13 @@ impl HandleManager {
     }
 
     /// Retrieves all handles that needs to be flushed.
+    ///
+    /// # Details
+    /// The handles of objects are ordered before the
+    /// handles of their parents.
     pub fn handles_to_flush(&self) -> Vec<ObjectHandle> {
-        self.open_handles
+        let mut handles = self
+            .open_handles
             .iter()
             .filter_map(|(open_handle, &handle_drop_action)| {
                 if handle_drop_action == HandleDropAction::Flush {
@@ -117,7 +159,9 @@ impl HandleManager {
                 }
             })
             .cloned()
-            .collect::<Vec<ObjectHandle>>()
+            .collect::<Vec<ObjectHandle>>();
+        handles.sort_by_key(|&handle| std::cmp::Reverse(self.ancestor_count(handle)));
+        handles
     }
 
     /// Retrieves all handles that needs to be closed.
@@ -135,8 +179,60 @@ impl HandleManager {
             .collect::<Vec<ObjectHandle>>()
     }
 
+    /// Indicates if the handle has been flushed and not
+    /// been added to the manager again since.
+    ///
+    /// # Details
+    /// Only the most recently flushed handles are remembered,
+    /// so that the record does not grow for ever.
+    pub fn is_flushed(&self, handle: ObjectHandle) -> bool {
+        self.flushed_handles.contains(&handle)
+    }
+
+    /// Indicates if the handle is open in the manager.
+    #[cfg(feature = "abstraction")]
+    pub fn is_open(&self, handle: ObjectHandle) -> bool {
+        self.open_handles.contains_key(&handle)
+    }
+
     /// Indicates if the manager has any open handles
     pub fn has_open_handles(&self) -> bool {
         !self.open_handles.is_empty()
     }
+
+    /// Returns the number of open handles.
+    pub fn open_handles_count(&self) -> usize {
+        self.open_handles.len()
+    }
+
+    /// Returns the number of open handles that needs to be flushed.
+    pub fn handles_to_flush_count(&self) -> usize {
+        self.open_handles
+            .values()
+            .filter(|&&handle_drop_action| handle_drop_action == HandleDropAction::Flush)
+            .count()
+    }
+
+    /// Returns the number of tracked ancestors of the handle.
+    fn ancestor_count(&self, handle: ObjectHandle) -> usize {
+        let mut count = 0;
+        let mut current = handle;
+        // The count is bounded in order to not loop forever on
+        // inconsistent relationships.
+        while let Some(&parent) = self.parent_handles.get(&current) {
+            if count == self.parent_handles.len() {
+                break;
+            }
+            count += 1;
+            current = parent;
+        }
+        count
+    }
+
+    /// Removes the relationships in which the handle is
+    /// either the child or the parent.
+    fn remove_parent_relationships(&mut self, handle: ObjectHandle) {
+        let _ = self.parent_handles.remove(&handle);
+        self.parent_handles.retain(|_, parent| *parent != handle);
+    }
 }
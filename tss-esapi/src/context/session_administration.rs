@@ -4,11 +4,13 @@ use crate::{
     attributes::{SessionAttributes, SessionAttributesMask},
     handles::SessionHandle,
     interface_types::session_handles::AuthSession,
-    tss2_esys::{Esys_TRSess_GetAttributes, Esys_TRSess_SetAttributes},
+    structures::Nonce,
+    tss2_esys::{Esys_TRSess_GetAttributes, Esys_TRSess_GetNonceTPM, Esys_TRSess_SetAttributes},
     Context, Result, ReturnCode,
 };
 use log::error;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::ptr::null_mut;
 
 impl Context {
     /// Set the given attributes on a given session.
@@ -51,5 +53,26 @@ impl Context {
         Ok(SessionAttributes(flags))
     }
 
-    // Missing function: Esys_TRSess_GetNonceTPM
+    /// Get the latest nonce generated by the TPM for the session.
+    ///
+    /// # Details
+    /// This is the `nonce_tpm` expected by [policy_signed][Context::policy_signed]
+    /// and [policy_secret][Context::policy_secret] when the authorization is bound
+    /// to a policy session.
+    pub fn tr_sess_get_nonce_tpm(&mut self, session: AuthSession) -> Result<Nonce> {
+        let mut nonce_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_TRSess_GetNonceTPM(
+                    self.mut_context(),
+                    SessionHandle::from(session).into(),
+                    &mut nonce_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when getting session nonce: {:#010X}", ret);
+            },
+        )?;
+        Nonce::try_from(Context::ffi_data_to_owned(nonce_ptr))
+    }
 }
@@ -0,0 +1,443 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use super::buffer_capture::BufferCapture;
+use crate::{
+    constants::tss::{
+        TPM2_RC_CANCELED, TPM2_ST_NO_SESSIONS, TSS2_BASE_RC_BAD_VALUE,
+        TSS2_BASE_RC_INSUFFICIENT_BUFFER, TSS2_BASE_RC_IO_ERROR, TSS2_BASE_RC_NOT_IMPLEMENTED,
+        TSS2_BASE_RC_TRY_AGAIN, TSS2_RC_LAYER_MASK, TSS2_RC_SUCCESS, TSS2_TCTI_RC_LAYER,
+    },
+    ffi::FfiSizeType,
+    tcti_ldr::{PollHandle, TctiContext},
+    tss2_esys::{
+        nfds_t, pollfd, size_t, TPM2_HANDLE, TSS2_RC, TSS2_TCTI_CONTEXT,
+        TSS2_TCTI_CONTEXT_COMMON_V1, TSS2_TCTI_CONTEXT_COMMON_V2, TSS2_TCTI_MAKE_STICKY_FCN,
+        TSS2_TCTI_POLL_HANDLE, TSS2_TCTI_RECEIVE_FCN, TSS2_TCTI_TIMEOUT_BLOCK,
+    },
+    Error, Result, WrapperErrorKind,
+};
+use log::{error, warn};
+use std::convert::TryFrom;
+use std::io::ErrorKind;
+use std::os::raw::{c_int, c_short};
+use std::slice;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+extern "C" {
+    fn poll(fds: *mut pollfd, nfds: nfds_t, timeout: c_int) -> c_int;
+}
+
+/// Poll event for data being available to read.
+const POLLIN: c_short = 0x0001;
+
+/// Magic value identifying a [TimeoutTcti].
+const TIMEOUT_TCTI_MAGIC: u64 = 0x7473_735f_746d_6f75;
+
+/// Size of the buffer used to discard a late response.
+const MAX_RESPONSE_SIZE: usize = 4096;
+
+/// Response handed to ESYS in place of the response to a command
+/// that timed out, or that was not sent because the deadline had passed.
+const CANCELED_RESPONSE: [u8; 10] = {
+    let tag = TPM2_ST_NO_SESSIONS.to_be_bytes();
+    let rc = TPM2_RC_CANCELED.to_be_bytes();
+    [tag[0], tag[1], 0, 0, 0, 10, rc[0], rc[1], rc[2], rc[3]]
+};
+
+/// TCTI forwarding all the calls to the TCTI of the context, that
+/// can give up waiting for a response from the TPM after a deadline.
+///
+/// # Details
+/// ESYS blocks until the TPM responds, so the deadline is enforced
+/// by polling the poll handles of the TCTI before every receive. If
+/// the TCTI has no poll handles, the receive timeout of the TCTI is
+/// used instead.
+///
+/// When the deadline passes, ESYS is handed a `TPM_RC_CANCELED` response
+/// so that it can still be used, and the late response of the TPM is
+/// discarded with [TimeoutTcti::drain_late_response].
+///
+/// The commands and responses going through the TCTI are also
+/// handed to the [BufferCapture] of the context.
+#[repr(C)]
+#[derive(Debug)]
+pub struct TimeoutTcti {
+    // Must be the first field, the TCTI functions are
+    // looked up through a pointer to the structure.
+    common: TSS2_TCTI_CONTEXT_COMMON_V2,
+    inner: TctiContext,
+    deadline: Option<Instant>,
+    poll_handles: Vec<PollHandle>,
+    receive_timeout_unsupported: bool,
+    awaiting_response: bool,
+    late_response: bool,
+    command_canceled: bool,
+    timed_out: bool,
+    buffer_capture: Arc<Mutex<BufferCapture>>,
+}
+
+impl TimeoutTcti {
+    /// Creates a new TimeoutTcti forwarding the calls to `inner`.
+    pub fn new(
+        mut inner: TctiContext,
+        buffer_capture: Arc<Mutex<BufferCapture>>,
+    ) -> Box<TimeoutTcti> {
+        let inner_common =
+            unsafe { &*(inner.tcti_context_ptr() as *const TSS2_TCTI_CONTEXT_COMMON_V1) };
+        // The make sticky function only exists in version 2 of the structure.
+        let (version, make_sticky_fn): (u32, TSS2_TCTI_MAKE_STICKY_FCN) =
+            if inner_common.version >= 2 {
+                (2, Some(make_sticky))
+            } else {
+                (1, None)
+            };
+        Box::new(TimeoutTcti {
+            common: TSS2_TCTI_CONTEXT_COMMON_V2 {
+                v1: TSS2_TCTI_CONTEXT_COMMON_V1 {
+                    magic: TIMEOUT_TCTI_MAGIC,
+                    version,
+                    transmit: Some(transmit),
+                    receive: Some(receive),
+                    // The inner TCTI is finalized when it is dropped.
+                    finalize: None,
+                    cancel: inner_common.cancel.map(|_| cancel as _),
+                    getPollHandles: inner_common.getPollHandles.map(|_| get_poll_handles as _),
+                    setLocality: inner_common.setLocality.map(|_| set_locality as _),
+                },
+                makeSticky: make_sticky_fn,
+            },
+            inner,
+            deadline: None,
+            poll_handles: Vec::new(),
+            receive_timeout_unsupported: false,
+            awaiting_response: false,
+            late_response: false,
+            command_canceled: false,
+            timed_out: false,
+            buffer_capture,
+        })
+    }
+
+    /// Get access to the C pointer given to ESYS.
+    pub fn tcti_context_ptr(&mut self) -> *mut TSS2_TCTI_CONTEXT {
+        (self as *mut TimeoutTcti).cast()
+    }
+
+    /// Sets the deadline after which commands are no longer
+    /// sent and responses are no longer waited for.
+    ///
+    /// # Errors
+    /// * if the poll handles of the TCTI cannot be retrieved, the
+    ///   error is returned.
+    pub fn set_deadline(&mut self, deadline: Instant) -> Result<()> {
+        self.poll_handles = self.supported_poll_handles()?;
+        self.deadline = Some(deadline);
+        self.timed_out = false;
+        Ok(())
+    }
+
+    /// Removes the deadline, and returns whether it was missed.
+    pub fn clear_deadline(&mut self) -> bool {
+        self.deadline = None;
+        self.poll_handles.clear();
+        std::mem::take(&mut self.timed_out)
+    }
+
+    /// Waits until `deadline` for the response to a command that
+    /// timed out, and discards it.
+    ///
+    /// # Returns
+    /// Whether the TPM is ready to receive commands again.
+    pub fn drain_late_response(&mut self, deadline: Instant) -> bool {
+        if !self.late_response {
+            return true;
+        }
+        self.poll_handles = match self.supported_poll_handles() {
+            Ok(poll_handles) => poll_handles,
+            Err(_) => return false,
+        };
+        let mut buffer = vec![0u8; MAX_RESPONSE_SIZE];
+        let mut size = MAX_RESPONSE_SIZE as size_t;
+        let ret = unsafe { self.receive_before_deadline(&mut size, buffer.as_mut_ptr(), deadline) };
+        self.poll_handles.clear();
+        if ret == Some(TSS2_RC_SUCCESS) {
+            self.late_response = false;
+        }
+        !self.late_response
+    }
+
+    /// Returns the poll handles of the inner TCTI, or no handles
+    /// if the inner TCTI does not support them.
+    fn supported_poll_handles(&self) -> Result<Vec<PollHandle>> {
+        match self.inner.poll_handles() {
+            Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam)) => Ok(Vec::new()),
+            poll_handles => poll_handles,
+        }
+    }
+
+    /// Returns the common structure of the inner TCTI.
+    fn inner_common(&mut self) -> &TSS2_TCTI_CONTEXT_COMMON_V2 {
+        unsafe { &*(self.inner.tcti_context_ptr() as *const TSS2_TCTI_CONTEXT_COMMON_V2) }
+    }
+
+    /// Receives a response from the inner TCTI, unless `deadline` passes first.
+    ///
+    /// # Returns
+    /// The return code of the inner TCTI, or `None` if the deadline passed.
+    ///
+    /// # Safety
+    /// The pointers must be valid as for the receive function of a TCTI.
+    unsafe fn receive_before_deadline(
+        &mut self,
+        size: *mut size_t,
+        response: *mut u8,
+        deadline: Instant,
+    ) -> Option<TSS2_RC> {
+        let inner_receive: TSS2_TCTI_RECEIVE_FCN = self.inner_common().v1.receive;
+        let inner_receive = match inner_receive {
+            Some(inner_receive) => inner_receive,
+            None => return Some(tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED)),
+        };
+        let inner_context = self.inner.tcti_context_ptr();
+
+        if !self.poll_handles.is_empty() {
+            return match self.wait_for_response(deadline) {
+                Ok(true) => Some(inner_receive(
+                    inner_context,
+                    size,
+                    response,
+                    TSS2_TCTI_TIMEOUT_BLOCK,
+                )),
+                Ok(false) => None,
+                Err(e) => {
+                    error!("Failed to poll for a response from the TPM: {}", e);
+                    Some(tcti_error(TSS2_BASE_RC_IO_ERROR))
+                }
+            };
+        }
+
+        // Without poll handles the receive timeout of the TCTI is used.
+        let capacity = *size;
+        while !self.receive_timeout_unsupported {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.as_nanos() == 0 {
+                return None;
+            }
+            // Rounded up so that the deadline is not waited for repeatedly.
+            let timeout = i32::try_from(remaining.as_millis() + 1).unwrap_or(i32::MAX);
+            *size = capacity;
+            let ret = inner_receive(inner_context, size, response, timeout);
+            match ret & !TSS2_RC_LAYER_MASK {
+                TSS2_BASE_RC_TRY_AGAIN => {}
+                TSS2_BASE_RC_BAD_VALUE | TSS2_BASE_RC_NOT_IMPLEMENTED => {
+                    warn!("The TCTI supports neither poll handles nor receive timeouts, the deadline is only enforced before sending commands");
+                    self.receive_timeout_unsupported = true;
+                }
+                _ => return Some(ret),
+            }
+        }
+        *size = capacity;
+        Some(inner_receive(
+            inner_context,
+            size,
+            response,
+            TSS2_TCTI_TIMEOUT_BLOCK,
+        ))
+    }
+
+    /// Waits until a response is available or the deadline
+    /// has passed, returning whether a response is available.
+    fn wait_for_response(&mut self, deadline: Instant) -> std::io::Result<bool> {
+        // The handles might also be polled for writing by the TCTI, which
+        // is always possible while waiting for a response.
+        let mut fds: Vec<pollfd> = self
+            .poll_handles
+            .iter()
+            .map(|handle| pollfd {
+                fd: handle.fd(),
+                events: POLLIN,
+                revents: 0,
+            })
+            .collect();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.as_nanos() == 0 {
+                return Ok(false);
+            }
+            // Rounded up so that the deadline is not polled for repeatedly.
+            let timeout = c_int::try_from(remaining.as_millis() + 1).unwrap_or(c_int::MAX);
+            let ready = unsafe { poll(fds.as_mut_ptr(), fds.len() as nfds_t, timeout) };
+            if ready > 0 {
+                return Ok(true);
+            }
+            if ready < 0 {
+                let error = std::io::Error::last_os_error();
+                if error.kind() != ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the TimeoutTcti behind a TCTI context pointer given by ESYS.
+///
+/// # Safety
+/// The pointer must have been created by [TimeoutTcti::tcti_context_ptr].
+unsafe fn timeout_tcti<'a>(tcti_context: *mut TSS2_TCTI_CONTEXT) -> &'a mut TimeoutTcti {
+    &mut *tcti_context.cast::<TimeoutTcti>()
+}
+
+const fn tcti_error(base_error: u32) -> TSS2_RC {
+    TSS2_TCTI_RC_LAYER | base_error
+}
+
+/// Hands the canceled response to ESYS.
+///
+/// # Safety
+/// The pointers must be valid as for the receive function of a TCTI.
+unsafe fn receive_canceled_response(size: *mut size_t, response: *mut u8) -> TSS2_RC {
+    let canceled_size = CANCELED_RESPONSE.len() as size_t;
+    if !response.is_null() {
+        if *size < canceled_size {
+            return tcti_error(TSS2_BASE_RC_INSUFFICIENT_BUFFER);
+        }
+        slice::from_raw_parts_mut(response, CANCELED_RESPONSE.len())
+            .copy_from_slice(&CANCELED_RESPONSE);
+    }
+    *size = canceled_size;
+    TSS2_RC_SUCCESS
+}
+
+unsafe extern "C" fn transmit(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    size: size_t,
+    command: *const u8,
+) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    if tcti.late_response
+        || tcti
+            .deadline
+            .map_or(false, |deadline| Instant::now() >= deadline)
+    {
+        // The command is not sent, ESYS receives a canceled response instead.
+        error!("The timeout elapsed before the command was sent to the TPM");
+        tcti.timed_out = true;
+        tcti.command_canceled = true;
+        return TSS2_RC_SUCCESS;
+    }
+    let inner_transmit = match tcti.inner_common().v1.transmit {
+        Some(inner_transmit) => inner_transmit,
+        None => return tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+    };
+    let ret = inner_transmit(tcti.inner.tcti_context_ptr(), size, command);
+    if ret == TSS2_RC_SUCCESS {
+        tcti.awaiting_response = true;
+        if !command.is_null() {
+            if let Ok(size) = usize::try_from(FfiSizeType::from(size)) {
+                tcti.buffer_capture
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .capture_command(slice::from_raw_parts(command, size));
+            }
+        }
+    }
+    ret
+}
+
+unsafe extern "C" fn receive(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    size: *mut size_t,
+    response: *mut u8,
+    timeout: i32,
+) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    if tcti.command_canceled {
+        let ret = receive_canceled_response(size, response);
+        if ret == TSS2_RC_SUCCESS && !response.is_null() {
+            tcti.command_canceled = false;
+        }
+        return ret;
+    }
+    let ret = match tcti.deadline {
+        // Only the first read of a response has to wait for the TPM, the
+        // rest of the response might already be buffered by the TCTI.
+        Some(deadline) if tcti.awaiting_response && timeout == TSS2_TCTI_TIMEOUT_BLOCK => {
+            match tcti.receive_before_deadline(size, response, deadline) {
+                Some(ret) => ret,
+                None => {
+                    error!("The TPM did not respond before the timeout elapsed");
+                    tcti.timed_out = true;
+                    tcti.awaiting_response = false;
+                    tcti.late_response = true;
+                    tcti.command_canceled = true;
+                    // Hands the canceled response to ESYS.
+                    return receive(tcti_context, size, response, timeout);
+                }
+            }
+        }
+        _ => {
+            let inner_receive = match tcti.inner_common().v1.receive {
+                Some(inner_receive) => inner_receive,
+                None => return tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+            };
+            inner_receive(tcti.inner.tcti_context_ptr(), size, response, timeout)
+        }
+    };
+    if ret == TSS2_RC_SUCCESS {
+        tcti.awaiting_response = false;
+        // Without a buffer only the size of the response is queried.
+        if !response.is_null() && !size.is_null() {
+            if let Ok(size) = usize::try_from(FfiSizeType::from(*size)) {
+                tcti.buffer_capture
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .capture_response(slice::from_raw_parts(response, size));
+            }
+        }
+    }
+    ret
+}
+
+unsafe extern "C" fn cancel(tcti_context: *mut TSS2_TCTI_CONTEXT) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    match tcti.inner_common().v1.cancel {
+        Some(inner_cancel) => inner_cancel(tcti.inner.tcti_context_ptr()),
+        None => tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+    }
+}
+
+unsafe extern "C" fn get_poll_handles(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    handles: *mut TSS2_TCTI_POLL_HANDLE,
+    num_handles: *mut size_t,
+) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    match tcti.inner_common().v1.getPollHandles {
+        Some(inner_get_poll_handles) => {
+            inner_get_poll_handles(tcti.inner.tcti_context_ptr(), handles, num_handles)
+        }
+        None => tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+    }
+}
+
+unsafe extern "C" fn set_locality(tcti_context: *mut TSS2_TCTI_CONTEXT, locality: u8) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    match tcti.inner_common().v1.setLocality {
+        Some(inner_set_locality) => inner_set_locality(tcti.inner.tcti_context_ptr(), locality),
+        None => tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+    }
+}
+
+unsafe extern "C" fn make_sticky(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    handle: *mut TPM2_HANDLE,
+    sticky: u8,
+) -> TSS2_RC {
+    let tcti = timeout_tcti(tcti_context);
+    match tcti.inner_common().makeSticky {
+        Some(inner_make_sticky) => inner_make_sticky(tcti.inner.tcti_context_ptr(), handle, sticky),
+        None => tcti_error(TSS2_BASE_RC_NOT_IMPLEMENTED),
+    }
+}
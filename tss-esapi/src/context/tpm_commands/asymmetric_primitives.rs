@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     handles::KeyHandle,
+    interface_types::ecc::EccCurve,
     structures::Data,
-    structures::{EccPoint, PublicKeyRsa, RsaDecryptionScheme},
-    tss2_esys::{Esys_ECDH_KeyGen, Esys_ECDH_ZGen, Esys_RSA_Decrypt, Esys_RSA_Encrypt},
+    structures::{AlgorithmDetailEcc, EccPoint, PublicKeyRsa, RsaDecryptionScheme},
+    tss2_esys::{
+        Esys_ECC_Parameters, Esys_ECDH_KeyGen, Esys_ECDH_ZGen, Esys_RSA_Decrypt, Esys_RSA_Encrypt,
+    },
     Context, Result, ReturnCode,
 };
 use log::error;
@@ -26,9 +29,9 @@ impl Context {
                 Esys_RSA_Encrypt(
                     self.mut_context(),
                     key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &message.into(),
                     &in_scheme.into(),
                     &label.into(),
@@ -57,8 +60,8 @@ impl Context {
                     self.mut_context(),
                     key_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &cipher_text.into(),
                     &in_scheme.into(),
                     &label.into(),
@@ -187,9 +190,9 @@ impl Context {
                 Esys_ECDH_KeyGen(
                     self.mut_context(),
                     key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut z_point_ptr,
                     &mut pub_point_ptr,
                 )
@@ -325,8 +328,8 @@ impl Context {
                     self.mut_context(),
                     key_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &in_point.into(),
                     &mut out_point_ptr,
                 )
@@ -339,6 +342,48 @@ impl Context {
         EccPoint::try_from(out_point.point)
     }
 
-    // Missing function: ECC_Parameters
+    /// Get the parameters of an ECC curve.
+    ///
+    /// # Details
+    /// The parameters can be used to validate that a point is on the
+    /// curve, e.g. before it is passed to [ecdh_z_gen][Context::ecdh_z_gen].
+    ///
+    /// # Arguments
+    /// * `curve` - The curve for which the parameters are returned.
+    ///
+    /// # Returns
+    /// The [AlgorithmDetailEcc] of the curve.
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, tcti_ldr::TctiNameConf, interface_types::ecc::EccCurve};
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// let parameters = context
+    ///     .ecc_parameters(EccCurve::NistP256)
+    ///     .expect("Failed to get the parameters of the curve");
+    /// assert_eq!(parameters.key_size(), 256);
+    /// ```
+    pub fn ecc_parameters(&mut self, curve: EccCurve) -> Result<AlgorithmDetailEcc> {
+        let mut parameters_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_ECC_Parameters(
+                    self.mut_context(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    curve.into(),
+                    &mut parameters_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when getting ECC parameters: {:#010X}", ret);
+            },
+        )?;
+        AlgorithmDetailEcc::try_from(Context::ffi_data_to_owned(parameters_ptr))
+    }
+
     // Missing function: ZGen_2Phase
 }
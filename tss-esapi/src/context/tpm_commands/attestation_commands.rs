@@ -45,7 +45,6 @@ impl Context {
     /// # use tss_esapi::{Context, TctiNameConf};
     /// # use std::convert::TryFrom;
     /// # use tss_esapi::{
-    /// #     abstraction::cipher::Cipher,
     /// #     handles::KeyHandle,
     /// #     interface_types::{
     /// #         algorithm::{HashingAlgorithm, RsaSchemeAlgorithm, SignatureSchemeAlgorithm},
@@ -53,7 +52,7 @@ impl Context {
     /// #         reserved_handles::Hierarchy,
     /// #     },
     /// #     structures::{
-    /// #         RsaExponent, RsaScheme, SymmetricDefinition,
+    /// #         RsaExponent, RsaScheme, SymmetricDefinition, SymmetricDefinitionObject,
     /// #     },
     /// #     utils::{create_unrestricted_signing_rsa_public, create_restricted_decryption_rsa_public},
     /// # };
@@ -81,9 +80,7 @@ impl Context {
     /// #     .unwrap()
     /// #     .key_handle;
     /// # let decryption_key_pub = create_restricted_decryption_rsa_public(
-    /// #         Cipher::aes_256_cfb()
-    /// #         .try_into()
-    /// #         .expect("Failed to create symmetric object"),
+    /// #     SymmetricDefinitionObject::AES_256_CFB,
     /// #     RsaKeyBits::Rsa2048,
     /// #     RsaExponent::default(),
     /// # )
@@ -136,7 +133,7 @@ impl Context {
                     signing_key_handle.into(),
                     self.required_session_1()?,
                     self.required_session_2()?,
-                    self.optional_session_3(),
+                    self.optional_session_3()?,
                     &qualifying_data.into(),
                     &signing_scheme.into(),
                     &mut certify_info_ptr,
@@ -188,7 +185,6 @@ impl Context {
     /// # use tss_esapi::{Context, TctiNameConf};
     /// # use std::convert::TryFrom;
     /// # use tss_esapi::{
-    /// #     abstraction::cipher::Cipher,
     /// #     handles::KeyHandle,
     /// #     interface_types::{
     /// #         algorithm::{HashingAlgorithm, EccSchemeAlgorithm, SignatureSchemeAlgorithm},
@@ -260,8 +256,8 @@ impl Context {
                     signing_key_handle.into(),
                     created_object.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &qualifying_data.into(),
                     &creation_hash.into(),
                     &signing_scheme.into(),
@@ -301,9 +297,9 @@ impl Context {
                 Esys_Quote(
                     self.mut_context(),
                     signing_key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &qualifying_data.into(),
                     &signing_scheme.into(),
                     &pcr_selection_list.into(),
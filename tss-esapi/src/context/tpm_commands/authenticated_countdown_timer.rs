@@ -1,7 +1,128 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    constants::{tss::TPM2_RH_ACT_0, CapabilityType},
+    handles::{AuthHandle, PermanentTpmHandle},
+    structures::{ActData, CapabilityData},
+    tss2_esys::{Esys_ACT_SetTimeout, ESYS_TR_RH_ACT_FIRST, ESYS_TR_RH_ACT_LAST},
+    Context, Error, Result, ReturnCode, WrapperErrorKind,
+};
+use log::error;
 
 impl Context {
-    // Missing function: ACT_SetTimeout
+    /// Sets the number of seconds before an authenticated countdown timer (ACT) signals.
+    ///
+    /// # Details
+    /// The availability of ACTs is TPM dependent, a TPM may implement
+    /// none, some or all of ACT_0 to ACT_F. The state of the implemented
+    /// ACTs, including the remaining time, can be read using
+    /// [act_status](Context::act_status).
+    ///
+    /// # Arguments
+    /// * `act_handle` - The handle of the ACT, see [AuthHandle::act].
+    /// * `start_timeout` - The number of seconds before the ACT signals. A
+    ///   value of zero stops the countdown.
+    ///
+    /// # Errors
+    /// * if `act_handle` does not refer to an ACT an `InvalidParam` wrapper
+    ///   error is returned.
+    /// * if the ACT is not implemented by the TPM an `UnsupportedParam`
+    ///   wrapper error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust, no_run
+    /// # use tss_esapi::{Context, TctiNameConf, interface_types::session_handles::AuthSession};
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// use tss_esapi::handles::AuthHandle;
+    ///
+    /// context.execute_with_session(Some(AuthSession::Password), |ctx| {
+    ///     ctx.act_set_timeout(AuthHandle::act(0).expect("Invalid ACT number"), 60)
+    ///         .expect("Failed to set ACT timeout");
+    /// });
+    /// ```
+    pub fn act_set_timeout(&mut self, act_handle: AuthHandle, start_timeout: u32) -> Result<()> {
+        let _ = self.act_status(act_handle)?;
+
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_ACT_SetTimeout(
+                    self.mut_context(),
+                    act_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    start_timeout,
+                )
+            },
+            |ret| {
+                error!("Error when setting ACT timeout: {:#010X}", ret);
+            },
+        )
+    }
+
+    /// Reads the state of an authenticated countdown timer (ACT).
+    ///
+    /// # Details
+    /// The state is read using [get_capability](Context::get_capability)
+    /// with [CapabilityType::Act]. The returned [ActData] contains the number
+    /// of seconds remaining before the ACT signals, and its attributes
+    /// indicate whether the ACT has signaled.
+    ///
+    /// # Arguments
+    /// * `act_handle` - The handle of the ACT, see [AuthHandle::act].
+    ///
+    /// # Errors
+    /// * if `act_handle` does not refer to an ACT an `InvalidParam` wrapper
+    ///   error is returned.
+    /// * if the ACT is not implemented by the TPM an `UnsupportedParam`
+    ///   wrapper error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust, no_run
+    /// # use tss_esapi::{Context, TctiNameConf};
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// use tss_esapi::handles::AuthHandle;
+    ///
+    /// let act_data = context
+    ///     .act_status(AuthHandle::act(0).expect("Invalid ACT number"))
+    ///     .expect("Failed to read ACT status");
+    /// let remaining_time = act_data.timeout();
+    /// let signaled = act_data.attributes().signaled();
+    /// ```
+    pub fn act_status(&mut self, act_handle: AuthHandle) -> Result<ActData> {
+        let act_tpm_handle = Self::act_tpm_handle(act_handle)?;
+        let act_data = self
+            .execute_without_session(|ctx| {
+                ctx.get_capability(CapabilityType::Act, act_tpm_handle.into(), 1)
+            })
+            .map(|(capability_data, _)| match capability_data {
+                CapabilityData::ActData(act_data_list) => {
+                    act_data_list.find(act_tpm_handle).copied()
+                }
+                _ => None,
+            })?;
+        act_data.ok_or_else(|| {
+            error!("The ACT {:?} is not implemented by the TPM", act_tpm_handle);
+            Error::local_error(WrapperErrorKind::UnsupportedParam)
+        })
+    }
+
+    /// Private function that converts the ESYS handle of an ACT
+    /// into the corresponding TPM handle.
+    fn act_tpm_handle(act_handle: AuthHandle) -> Result<PermanentTpmHandle> {
+        let esys_handle = u32::from(act_handle);
+        if !(ESYS_TR_RH_ACT_FIRST..=ESYS_TR_RH_ACT_LAST).contains(&esys_handle) {
+            error!("The handle {:#010X} is not an ACT handle", esys_handle);
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        PermanentTpmHandle::new(TPM2_RH_ACT_0 + (esys_handle - ESYS_TR_RH_ACT_FIRST))
+    }
 }
@@ -1,11 +1,16 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    constants::CapabilityType,
+    constants::{CapabilityType, HandleRange},
+    ffi::FfiSizeType,
+    handles::TpmHandle,
     interface_types::YesNo,
-    structures::{CapabilityData, PublicParameters},
-    tss2_esys::{Esys_GetCapability, Esys_TestParms},
-    Context, Result, ReturnCode,
+    structures::{CapabilityData, HandleList, PublicParameters},
+    tss2_esys::{
+        Esys_GetCapability, Esys_TestParms, Tss2_MU_TPMS_CAPABILITY_DATA_Marshal, TPM2_CAP,
+        TPM2_HANDLE, TPMS_CAPABILITY_DATA,
+    },
+    Context, Error, Result, ReturnCode, WrapperErrorKind,
 };
 use log::{error, warn};
 use std::convert::TryFrom;
@@ -46,32 +51,130 @@ impl Context {
         property: u32,
         property_count: u32,
     ) -> Result<(CapabilityData, bool)> {
-        let mut capability_data_ptr = null_mut();
-        let mut more_data = YesNo::No.into();
+        let (capability_data, more_data) =
+            self.get_capability_ffi(capability.into(), property, property_count)?;
+        Ok((CapabilityData::try_from(capability_data)?, more_data))
+    }
+
+    /// Get current capability information about the TPM as raw
+    /// marshalled `TPMS_CAPABILITY_DATA`.
+    ///
+    /// # Details
+    /// This is an escape hatch for capabilities that are not (yet)
+    /// modelled by [CapabilityData]. The capability value is passed
+    /// to the TPM unchecked and the capability data unmarshalled by
+    /// ESYS is marshalled again, without being converted into a
+    /// [CapabilityData]. The capability therefore still needs to be
+    /// supported by the tpm2-tss library.
+    ///
+    /// # Returns
+    /// The marshalled capability data and the moreData flag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// #
+    /// use tss_esapi::constants::tss::{TPM2_CAP_TPM_PROPERTIES, TPM2_PT_FIXED};
+    ///
+    /// let (_raw_capability_data, _more) = context
+    ///     .get_capability_raw(TPM2_CAP_TPM_PROPERTIES, TPM2_PT_FIXED, 10)
+    ///     .expect("Failed to call get_capability_raw");
+    /// ```
+    pub fn get_capability_raw(
+        &mut self,
+        capability: TPM2_CAP,
+        property: u32,
+        property_count: u32,
+    ) -> Result<(Vec<u8>, bool)> {
+        let (capability_data, more_data) =
+            self.get_capability_ffi(capability, property, property_count)?;
 
+        let mut buffer = vec![0; std::mem::size_of::<TPMS_CAPABILITY_DATA>()];
+        let ffi_buffer_size = FfiSizeType::try_from(buffer.len())?;
+        let mut ffi_offset = FfiSizeType::try_from(0usize)?;
         ReturnCode::ensure_success(
             unsafe {
-                Esys_GetCapability(
-                    self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
-                    capability.into(),
-                    property,
-                    property_count,
-                    &mut more_data,
-                    &mut capability_data_ptr,
+                Tss2_MU_TPMS_CAPABILITY_DATA_Marshal(
+                    &capability_data,
+                    buffer.as_mut_ptr(),
+                    ffi_buffer_size.into(),
+                    ffi_offset.as_mut_ptr(),
                 )
             },
             |ret| {
-                error!("Error when getting capabilities: {:#010X}", ret);
+                error!("Failed to marshall capability data: {:#010X}", ret);
             },
         )?;
+        buffer.truncate(usize::try_from(ffi_offset)?);
 
-        Ok((
-            CapabilityData::try_from(Context::ffi_data_to_owned(capability_data_ptr))?,
-            YesNo::try_from(more_data)?.into(),
-        ))
+        Ok((buffer, more_data))
+    }
+
+    /// Get all the handles that the TPM reports within a handle range.
+    ///
+    /// # Details
+    /// The handles capability is queried starting at the first handle
+    /// of the range, and queried again as long as the TPM indicates that
+    /// more data is available. Handles outside the range are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, TctiNameConf};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// #
+    /// use tss_esapi::constants::HandleRange;
+    ///
+    /// let _persistent_handles = context
+    ///     .handles_in_range(HandleRange::Persistent)
+    ///     .expect("Failed to call handles_in_range");
+    /// ```
+    pub fn handles_in_range(&mut self, range: HandleRange) -> Result<Vec<TpmHandle>> {
+        let mut handles = Vec::new();
+        let mut property = range.first();
+        loop {
+            let (capability_data, more_data) = self.get_capability(
+                CapabilityType::Handles,
+                property,
+                HandleList::MAX_SIZE as u32,
+            )?;
+            let handle_list = match capability_data {
+                CapabilityData::Handles(handle_list) => handle_list,
+                _ => {
+                    error!("Unexpected capability data returned when listing handles");
+                    return Err(Error::local_error(WrapperErrorKind::WrongValueFromTpm));
+                }
+            };
+
+            let mut last_handle = None;
+            for handle in handle_list.into_inner() {
+                let tss_handle = TPM2_HANDLE::from(handle);
+                last_handle = Some(tss_handle);
+                if range.contains(tss_handle) {
+                    handles.push(handle);
+                }
+            }
+
+            match last_handle {
+                Some(tss_handle)
+                    if more_data && range.contains(tss_handle) && tss_handle < range.last() =>
+                {
+                    property = tss_handle + 1;
+                }
+                _ => break,
+            }
+        }
+        Ok(handles)
     }
 
     /// Test if the given parameters are supported by the TPM.
@@ -84,9 +187,9 @@ impl Context {
             unsafe {
                 Esys_TestParms(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &public_parmeters.into(),
                 )
             },
@@ -98,4 +201,39 @@ impl Context {
             },
         )
     }
+
+    /// Sends TPM2_GetCapability and returns the unparsed capability data.
+    pub(crate) fn get_capability_ffi(
+        &mut self,
+        capability: TPM2_CAP,
+        property: u32,
+        property_count: u32,
+    ) -> Result<(TPMS_CAPABILITY_DATA, bool)> {
+        let mut capability_data_ptr = null_mut();
+        let mut more_data = YesNo::No.into();
+
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_GetCapability(
+                    self.mut_context(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    capability,
+                    property,
+                    property_count,
+                    &mut more_data,
+                    &mut capability_data_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when getting capabilities: {:#010X}", ret);
+            },
+        )?;
+
+        Ok((
+            Context::ffi_data_to_owned(capability_data_ptr),
+            YesNo::try_from(more_data)?.into(),
+        ))
+    }
 }
@@ -163,6 +163,11 @@ impl Context {
     /// value will be ObjectHandle::None and the input `object_handle` will not
     /// be valid after this call is made.
     ///
+    /// # Errors
+    /// * if the object is in the null hierarchy, it cannot be made persistent
+    ///   and the TPM returns an error for which [is_attributes][crate::Error::is_attributes]
+    ///   is true.
+    ///
     /// # Example
     ///
     /// Make transient object persistent:
@@ -417,8 +422,8 @@ impl Context {
                     AuthHandle::from(auth).into(),
                     object_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     PersistentTpmHandle::from(persistent).into(),
                     &mut new_object_handle,
                 )
@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::Context;
 use crate::{
+    ffi::data_zeroize::FfiDataZeroize,
     handles::ObjectHandle,
     structures::{Data, EncryptedSecret, Private, Public, SymmetricDefinitionObject},
-    tss2_esys::{Esys_Duplicate, Esys_Import},
+    tss2_esys::{Esys_Duplicate, Esys_Import, TPM2B_ENCRYPTED_SECRET, TPM2B_PRIVATE, TPM2B_PUBLIC},
     Result, ReturnCode,
 };
 use log::error;
 
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 use std::ptr::null_mut;
 
 impl Context {
@@ -55,7 +56,6 @@ impl Context {
     /// #     RsaExponent,
     /// # };
     /// use tss_esapi::structures::SymmetricDefinitionObject;
-    /// # use tss_esapi::abstraction::cipher::Cipher;
     /// # use tss_esapi::{Context, TctiNameConf};
     /// #
     /// # let mut context = // ...
@@ -154,7 +154,7 @@ impl Context {
     /// #     .with_object_attributes(parent_object_attributes)
     /// #     .with_rsa_parameters(
     /// #         PublicRsaParametersBuilder::new_restricted_decryption_key(
-    /// #             Cipher::aes_256_cfb().try_into().unwrap(),
+    /// #             SymmetricDefinitionObject::AES_256_CFB,
     /// #             RsaKeyBits::Rsa2048,
     /// #             RsaExponent::default(),
     /// #         )
@@ -312,8 +312,8 @@ impl Context {
                     object_to_duplicate.into(),
                     new_parent_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &encryption_key_in.unwrap_or_default().into(),
                     &symmetric_alg.into(),
                     &mut encryption_key_out_ptr,
@@ -377,7 +377,6 @@ impl Context {
     /// #     RsaExponent,
     /// # };
     /// use tss_esapi::structures::SymmetricDefinitionObject;
-    /// # use tss_esapi::abstraction::cipher::Cipher;
     /// # use tss_esapi::{Context, TctiNameConf};
     /// #
     /// # let mut context = // ...
@@ -476,7 +475,7 @@ impl Context {
     /// #     .with_object_attributes(parent_object_attributes)
     /// #     .with_rsa_parameters(
     /// #         PublicRsaParametersBuilder::new_restricted_decryption_key(
-    /// #             Cipher::aes_256_cfb().try_into().unwrap(),
+    /// #             SymmetricDefinitionObject::AES_256_CFB,
     /// #             RsaKeyBits::Rsa2048,
     /// #             RsaExponent::default(),
     /// #         )
@@ -663,26 +662,32 @@ impl Context {
         symmetric_alg: SymmetricDefinitionObject,
     ) -> Result<Private> {
         let mut out_private_ptr = null_mut();
-        ReturnCode::ensure_success(
-            unsafe {
-                Esys_Import(
-                    self.mut_context(),
-                    parent_handle.into(),
-                    self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
-                    &encryption_key.unwrap_or_default().into(),
-                    &public.try_into()?,
-                    &duplicate.into(),
-                    &encrypted_secret.into(),
-                    &symmetric_alg.into(),
-                    &mut out_private_ptr,
-                )
-            },
-            |ret| {
-                error!("Error when performing import: {:#010X}", ret);
-            },
-        )?;
+        let session_1 = self.required_session_1()?;
+        let session_2 = self.optional_session_2()?;
+        let session_3 = self.optional_session_3()?;
+        let ffi_public = TPM2B_PUBLIC::try_from(public)?;
+        let mut ffi_duplicate = TPM2B_PRIVATE::from(duplicate);
+        let mut ffi_encrypted_secret = TPM2B_ENCRYPTED_SECRET::from(encrypted_secret);
+        let return_code = unsafe {
+            Esys_Import(
+                self.mut_context(),
+                parent_handle.into(),
+                session_1,
+                session_2,
+                session_3,
+                &encryption_key.unwrap_or_default().into(),
+                &ffi_public,
+                &ffi_duplicate,
+                &ffi_encrypted_secret,
+                &symmetric_alg.into(),
+                &mut out_private_ptr,
+            )
+        };
+        ffi_duplicate.ffi_data_zeroize();
+        ffi_encrypted_secret.ffi_data_zeroize();
+        ReturnCode::ensure_success(return_code, |ret| {
+            error!("Error when performing import: {:#010X}", ret);
+        })?;
         Private::try_from(Context::ffi_data_to_owned(out_private_ptr))
     }
 }
@@ -4,17 +4,22 @@ use crate::{
     attributes::LocalityAttributes,
     constants::CommandCode,
     handles::{AuthHandle, NvIndexHandle, ObjectHandle, SessionHandle},
-    interface_types::{reserved_handles::NvAuth, session_handles::PolicySession, YesNo},
+    interface_types::{
+        algorithm::HashingAlgorithm,
+        reserved_handles::{Hierarchy, NvAuth},
+        session_handles::PolicySession,
+        YesNo,
+    },
     structures::{
-        AuthTicket, Digest, DigestList, Name, Nonce, PcrSelectionList, Signature, Timeout,
-        VerifiedTicket,
+        AuthTicket, Digest, DigestList, MaxBuffer, Name, Nonce, PcrSelectionList, Signature,
+        Timeout, VerifiedTicket,
     },
     tss2_esys::{
         Esys_PolicyAuthValue, Esys_PolicyAuthorize, Esys_PolicyAuthorizeNV, Esys_PolicyCommandCode,
         Esys_PolicyCpHash, Esys_PolicyDuplicationSelect, Esys_PolicyGetDigest, Esys_PolicyLocality,
         Esys_PolicyNameHash, Esys_PolicyNvWritten, Esys_PolicyOR, Esys_PolicyPCR,
         Esys_PolicyPassword, Esys_PolicyPhysicalPresence, Esys_PolicySecret, Esys_PolicySigned,
-        Esys_PolicyTemplate,
+        Esys_PolicyTemplate, Esys_PolicyTicket,
     },
     Context, Error, Result, ReturnCode, WrapperErrorKind as ErrorKind,
 };
@@ -45,15 +50,12 @@ impl Context {
                     auth_object.into(),
                     SessionHandle::from(policy_session).into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &nonce_tpm.into(),
                     &cp_hash_a.into(),
                     &policy_ref.into(),
-                    i32::try_from(expiration.map_or(0, |v| v.as_secs())).map_err(|e| {
-                        error!("Unable to convert duration to i32: {}", e);
-                        Error::local_error(ErrorKind::InvalidParam)
-                    })?,
+                    Self::expiration_seconds(expiration.unwrap_or_default())?,
                     &signature.try_into()?,
                     &mut out_timeout_ptr,
                     &mut out_policy_ticket_ptr,
@@ -79,40 +81,106 @@ impl Context {
         policy_ref: Nonce,
         expiration: Option<Duration>,
     ) -> Result<(Timeout, AuthTicket)> {
-        let mut out_timeout_ptr = null_mut();
-        let mut out_policy_ticket_ptr = null_mut();
+        let expiration = Self::expiration_seconds(expiration.unwrap_or_default())?;
+        self.policy_secret_internal(
+            policy_session,
+            auth_handle,
+            nonce_tpm,
+            cp_hash_a,
+            policy_ref,
+            expiration,
+        )
+    }
+
+    /// Cause the policy to require a secret in authValue, and get
+    /// a ticket that can be used to repeat the authorization.
+    ///
+    /// # Details
+    /// This is the same as [policy_secret][Context::policy_secret], except that
+    /// the TPM is asked to return a ticket. The ticket and timeout can be given to
+    /// [policy_ticket][Context::policy_ticket] to satisfy the same assertion in other
+    /// policy sessions, without providing the secret again, until `expiration` has passed.
+    ///
+    /// If `nonce_tpm` is the nonce of `policy_session` (see
+    /// [tr_sess_get_nonce_tpm][Context::tr_sess_get_nonce_tpm]) the expiration is counted
+    /// from the start of the session, otherwise it is counted from the time of the call and
+    /// the ticket expires on TPM reset.
+    ///
+    /// No ticket is produced for trial sessions.
+    ///
+    /// # Errors
+    /// * if `expiration` is shorter than one second, an `InvalidParam` wrapper error is returned.
+    pub fn policy_secret_with_ticket(
+        &mut self,
+        policy_session: PolicySession,
+        auth_handle: AuthHandle,
+        nonce_tpm: Nonce,
+        cp_hash_a: Digest,
+        policy_ref: Nonce,
+        expiration: Duration,
+    ) -> Result<(Timeout, AuthTicket)> {
+        let expiration = Self::expiration_seconds(expiration)?;
+        if expiration == 0 {
+            error!("An expiration of at least one second is required to get a ticket");
+            return Err(Error::local_error(ErrorKind::InvalidParam));
+        }
+        // A negative expiration asks the TPM for a ticket.
+        let expiration = -expiration;
+        self.policy_secret_internal(
+            policy_session,
+            auth_handle,
+            nonce_tpm,
+            cp_hash_a,
+            policy_ref,
+            expiration,
+        )
+    }
+
+    /// Cause the policy to include an authorization that was
+    /// previously given in a ticket.
+    ///
+    /// # Arguments
+    /// * `policy_session` - The [policy session][PolicySession] being extended.
+    /// * `timeout` - The [Timeout] returned together with `ticket`.
+    /// * `cp_hash_a` - The command parameter hash used when the ticket was created.
+    /// * `policy_ref` - The policy reference used when the ticket was created.
+    /// * `auth_name` - The [name][Name] of the object that provided the authorization.
+    /// * `ticket` - The [AuthTicket] returned by [policy_signed][Context::policy_signed]
+    ///   or [policy_secret_with_ticket][Context::policy_secret_with_ticket].
+    ///
+    /// # Errors
+    /// * if the ticket has expired, or does not match the other parameters, a TSS
+    ///   error is returned.
+    pub fn policy_ticket(
+        &mut self,
+        policy_session: PolicySession,
+        timeout: Timeout,
+        cp_hash_a: Digest,
+        policy_ref: Nonce,
+        auth_name: Name,
+        ticket: AuthTicket,
+    ) -> Result<()> {
         ReturnCode::ensure_success(
             unsafe {
-                Esys_PolicySecret(
+                Esys_PolicyTicket(
                     self.mut_context(),
-                    auth_handle.into(),
                     SessionHandle::from(policy_session).into(),
-                    self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
-                    &nonce_tpm.into(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &timeout.into(),
                     &cp_hash_a.into(),
                     &policy_ref.into(),
-                    i32::try_from(expiration.map_or(0, |v| v.as_secs())).map_err(|e| {
-                        error!("Unable to convert duration to i32: {}", e);
-                        Error::local_error(ErrorKind::InvalidParam)
-                    })?,
-                    &mut out_timeout_ptr,
-                    &mut out_policy_ticket_ptr,
+                    auth_name.as_ref(),
+                    &ticket.try_into()?,
                 )
             },
             |ret| {
-                error!("Error when sending policy secret: {:#010X}", ret);
+                error!("Error when sending policy ticket: {:#010X}", ret);
             },
-        )?;
-        Ok((
-            Timeout::try_from(Context::ffi_data_to_owned(out_timeout_ptr))?,
-            AuthTicket::try_from(Context::ffi_data_to_owned(out_policy_ticket_ptr))?,
-        ))
+        )
     }
 
-    // Missing function: PolicyTicket
-
     /// Cause conditional gating of a policy based on an OR'd condition.
     ///
     /// The TPM will ensure that the current policy digest equals at least
@@ -143,9 +211,9 @@ impl Context {
                 Esys_PolicyOR(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &digest_list.try_into()?,
                 )
             },
@@ -164,22 +232,29 @@ impl Context {
     /// This is then compared to pcr_policy_digest if they match then
     /// the policyDigest of the policy session is extended.
     ///
+    /// If `pcr_policy_digest` is `None` an empty digest is sent and the
+    /// TPM uses the digest of the current PCR values. Supplying a digest
+    /// binds the policy to those exact values, which makes it possible
+    /// to compute policies for PCR values that are not yet present
+    /// using a trial session.
+    ///
     /// # Errors
     /// * if the pcr policy digest provided is too long, a `WrongParamSize` wrapper error will be returned
     pub fn policy_pcr(
         &mut self,
         policy_session: PolicySession,
-        pcr_policy_digest: Digest,
+        pcr_policy_digest: Option<Digest>,
         pcr_selection_list: PcrSelectionList,
     ) -> Result<()> {
+        let pcr_policy_digest = pcr_policy_digest.unwrap_or_default();
         ReturnCode::ensure_success(
             unsafe {
                 Esys_PolicyPCR(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &pcr_policy_digest.into(),
                     &pcr_selection_list.into(),
                 )
@@ -190,6 +265,45 @@ impl Context {
         )
     }
 
+    /// Cause conditional gating of a policy based on PCR, after checking
+    /// the current PCR values.
+    ///
+    /// # Details
+    /// If `pcr_policy_digest` is provided, the PCRs in `pcr_selection_list`
+    /// are read and their digest is computed, using the hash algorithm of
+    /// the policy session. The policy is only extended if the digest
+    /// matches `pcr_policy_digest`, this also applies to trial sessions
+    /// for which the TPM does not check the PCR values.
+    ///
+    /// If `pcr_policy_digest` is not provided, the TPM uses the
+    /// current values of the PCRs.
+    ///
+    /// # Errors
+    /// * if the current PCR values do not match `pcr_policy_digest`, a `PcrMismatch`
+    ///   wrapper error is returned.
+    /// * if the selected PCR values do not fit in a [MaxBuffer], a `WrongParamSize`
+    ///   wrapper error is returned.
+    pub fn policy_pcr_checked(
+        &mut self,
+        policy_session: PolicySession,
+        pcr_policy_digest: Option<Digest>,
+        pcr_selection_list: PcrSelectionList,
+    ) -> Result<()> {
+        if let Some(pcr_policy_digest) = &pcr_policy_digest {
+            let PolicySession::PolicySession {
+                hashing_algorithm, ..
+            } = policy_session;
+            let current_pcr_digest = self.execute_without_session(|ctx| {
+                ctx.current_pcr_digest(hashing_algorithm, pcr_selection_list.clone())
+            })?;
+            if &current_pcr_digest != pcr_policy_digest {
+                error!("The PCR values do not match the PCR policy digest");
+                return Err(Error::local_error(ErrorKind::PcrMismatch));
+            }
+        }
+        self.policy_pcr(policy_session, pcr_policy_digest, pcr_selection_list)
+    }
+
     /// Cause conditional gating of a policy based on locality.
     ///
     /// The TPM will ensure that the current policy can only complete in the specified
@@ -204,9 +318,9 @@ impl Context {
                 Esys_PolicyLocality(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     locality.into(),
                 )
             },
@@ -233,9 +347,9 @@ impl Context {
                 Esys_PolicyCommandCode(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     code.into(),
                 )
             },
@@ -255,9 +369,9 @@ impl Context {
                 Esys_PolicyPhysicalPresence(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -283,9 +397,9 @@ impl Context {
                 Esys_PolicyCpHash(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &cp_hash_a.into(),
                 )
             },
@@ -312,9 +426,9 @@ impl Context {
                 Esys_PolicyNameHash(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &name_hash.into(),
                 )
             },
@@ -356,7 +470,6 @@ impl Context {
     /// #     RsaExponent, Name,
     /// # };
     /// # use tss_esapi::structures::SymmetricDefinitionObject;
-    /// # use tss_esapi::abstraction::cipher::Cipher;
     /// # use tss_esapi::{Context, TctiNameConf};
     /// #
     /// # let mut context = // ...
@@ -416,9 +529,9 @@ impl Context {
                 Esys_PolicyDuplicationSelect(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &object_name.into(),
                     &new_parent_name.into(),
                     YesNo::from(include_object).into(),
@@ -453,9 +566,9 @@ impl Context {
                 Esys_PolicyAuthorize(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &approved_policy.into(),
                     &policy_ref.into(),
                     key_sign.as_ref(),
@@ -478,9 +591,9 @@ impl Context {
                 Esys_PolicyAuthValue(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -499,9 +612,9 @@ impl Context {
                 Esys_PolicyPassword(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -519,9 +632,9 @@ impl Context {
                 Esys_PolicyGetDigest(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut policy_digest_ptr,
                 )
             },
@@ -549,9 +662,9 @@ impl Context {
                 Esys_PolicyNvWritten(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     written_set.into(),
                 )
             },
@@ -579,9 +692,9 @@ impl Context {
                 Esys_PolicyTemplate(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &template_hash.into(),
                 )
             },
@@ -603,6 +716,12 @@ impl Context {
     /// * `nv_index_handle` - The [NvIndexHandle] associated with NV memory
     ///                       where the policy is stored.
     ///
+    /// # Details
+    /// The NV index must contain the authorized policy digest marshalled
+    /// as a `TPMT_HA`, i.e. the big-endian hash algorithm identifier
+    /// followed by the digest. For a non-trial session the TPM checks that
+    /// the current policy digest of the session matches it.
+    ///
     /// # Example
     /// ```rust
     /// # use std::convert::TryFrom;
@@ -716,9 +835,9 @@ impl Context {
                     AuthHandle::from(auth_handle).into(),
                     nv_index_handle.into(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -726,4 +845,77 @@ impl Context {
             },
         )
     }
+
+    /// Computes the digest of the current values of the selected PCRs,
+    /// in the same way as the TPM does for a PCR policy.
+    fn current_pcr_digest(
+        &mut self,
+        hashing_algorithm: HashingAlgorithm,
+        mut pcr_selection_list: PcrSelectionList,
+    ) -> Result<Digest> {
+        let mut pcr_values = Vec::new();
+        while !pcr_selection_list.is_empty() {
+            let (_, pcrs_read, pcr_digests) = self.pcr_read(pcr_selection_list.clone())?;
+            pcr_digests
+                .value()
+                .iter()
+                .for_each(|pcr_digest| pcr_values.extend_from_slice(pcr_digest));
+            pcr_selection_list.subtract(&pcrs_read)?;
+        }
+        self.hash(
+            MaxBuffer::try_from(pcr_values)?,
+            hashing_algorithm,
+            Hierarchy::Null,
+        )
+        .map(|(digest, _)| digest)
+    }
+
+    /// Private function for calling PolicySecret with the
+    /// expiration, in seconds, that is sent to the TPM.
+    fn policy_secret_internal(
+        &mut self,
+        policy_session: PolicySession,
+        auth_handle: AuthHandle,
+        nonce_tpm: Nonce,
+        cp_hash_a: Digest,
+        policy_ref: Nonce,
+        expiration: i32,
+    ) -> Result<(Timeout, AuthTicket)> {
+        let mut out_timeout_ptr = null_mut();
+        let mut out_policy_ticket_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_PolicySecret(
+                    self.mut_context(),
+                    auth_handle.into(),
+                    SessionHandle::from(policy_session).into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &nonce_tpm.into(),
+                    &cp_hash_a.into(),
+                    &policy_ref.into(),
+                    expiration,
+                    &mut out_timeout_ptr,
+                    &mut out_policy_ticket_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when sending policy secret: {:#010X}", ret);
+            },
+        )?;
+        Ok((
+            Timeout::try_from(Context::ffi_data_to_owned(out_timeout_ptr))?,
+            AuthTicket::try_from(Context::ffi_data_to_owned(out_policy_ticket_ptr))?,
+        ))
+    }
+
+    /// Private function for converting an expiration to
+    /// the number of seconds sent to the TPM.
+    fn expiration_seconds(expiration: Duration) -> Result<i32> {
+        i32::try_from(expiration.as_secs()).map_err(|e| {
+            error!("Unable to convert duration to i32: {}", e);
+            Error::local_error(ErrorKind::InvalidParam)
+        })
+    }
 }
@@ -1,8 +1,164 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    handles::KeyHandle,
+    structures::{CommitCounter, EccParameter, EccPoint, SensitiveData},
+    tss2_esys::{Esys_Commit, TPM2B_ECC_PARAMETER, TPM2B_ECC_POINT, TPM2B_SENSITIVE_DATA},
+    Context, Result, ReturnCode,
+};
+use log::error;
+use std::convert::TryFrom;
+use std::ptr::{null, null_mut};
 
 impl Context {
-    // Missing function: Commit
+    /// Performs the first part of an ECC anonymous signing operation.
+    ///
+    /// # Arguments
+    /// * `sign_handle` - A [KeyHandle] of an ECC key using the ECDAA scheme.
+    /// * `p1` - An optional [EccPoint] on the curve of the key.
+    /// * `s2` - An optional octet array used to derive the x coordinate of a base point.
+    /// * `y2` - An optional y coordinate of the point associated with `s2`.
+    ///
+    /// # Details
+    /// The TPM generates an ephemeral value that is used by the signing
+    /// operation identified by the returned [CommitCounter].
+    ///
+    /// The counter strictly increases between the commits of a TPM,
+    /// but it wraps around after [CommitCounter::MAX] and the commit
+    /// values are discarded on a TPM Reset. A counter that is not larger
+    /// than the one of a previous commit therefore indicates that the
+    /// previous commit can no longer be used, see [Context::max_commit_id].
+    ///
+    /// # Returns
+    /// The points `K`, `L` and `E` computed by the TPM and the [CommitCounter].
+    /// `K` and `L` are empty unless `s2` and `y2` are provided.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{
+    /// #    Context, TctiNameConf,
+    /// #    attributes::{SessionAttributesBuilder, ObjectAttributesBuilder},
+    /// #    constants::SessionType,
+    /// #    interface_types::{
+    /// #        algorithm::{HashingAlgorithm, PublicAlgorithm},
+    /// #        ecc::EccCurve,
+    /// #        reserved_handles::Hierarchy,
+    /// #    },
+    /// #    structures::{
+    /// #        EcDaaScheme, EccPoint, EccScheme, KeyDerivationFunctionScheme, PublicBuilder,
+    /// #        PublicEccParametersBuilder, SymmetricDefinition,
+    /// #    },
+    /// # };
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// #
+    /// # let session = context
+    /// #     .start_auth_session(
+    /// #         None,
+    /// #         None,
+    /// #         None,
+    /// #         SessionType::Hmac,
+    /// #         SymmetricDefinition::AES_256_CFB,
+    /// #         HashingAlgorithm::Sha256,
+    /// #     )
+    /// #     .expect("Failed to create session")
+    /// #     .expect("Received invalid handle");
+    /// # let (session_attributes, session_attributes_mask) = SessionAttributesBuilder::new()
+    /// #     .with_decrypt(true)
+    /// #     .with_encrypt(true)
+    /// #     .build();
+    /// # context.tr_sess_set_attributes(session, session_attributes, session_attributes_mask)
+    /// #     .expect("Failed to set attributes on session");
+    /// # context.set_sessions((Some(session), None, None));
+    /// #
+    /// // Create a key using the ECDAA scheme
+    /// let ecc_parms = PublicEccParametersBuilder::new()
+    ///     .with_ecc_scheme(EccScheme::EcDaa(EcDaaScheme::new(HashingAlgorithm::Sha256, 0)))
+    ///     .with_curve(EccCurve::BnP256)
+    ///     .with_is_signing_key(true)
+    ///     .with_is_decryption_key(false)
+    ///     .with_restricted(false)
+    ///     .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let object_attributes = ObjectAttributesBuilder::new()
+    ///     .with_fixed_tpm(true)
+    ///     .with_fixed_parent(true)
+    ///     .with_sensitive_data_origin(true)
+    ///     .with_user_with_auth(true)
+    ///     .with_decrypt(false)
+    ///     .with_sign_encrypt(true)
+    ///     .with_restricted(false)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let public = PublicBuilder::new()
+    ///     .with_public_algorithm(PublicAlgorithm::Ecc)
+    ///     .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+    ///     .with_object_attributes(object_attributes)
+    ///     .with_ecc_parameters(ecc_parms)
+    ///     .with_ecc_unique_identifier(EccPoint::default())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let key_handle = context
+    ///     .create_primary(Hierarchy::Owner, public, None, None, None, None)
+    ///     .unwrap()
+    ///     .key_handle;
+    ///
+    /// let (_k, _l, e, counter) = context.commit(key_handle, None, None, None).unwrap();
+    /// ```
+    pub fn commit(
+        &mut self,
+        sign_handle: KeyHandle,
+        p1: Option<EccPoint>,
+        s2: Option<SensitiveData>,
+        y2: Option<EccParameter>,
+    ) -> Result<(EccPoint, EccPoint, EccPoint, CommitCounter)> {
+        let potential_p1 = p1.map(TPM2B_ECC_POINT::from);
+        let potential_s2 = s2.map(TPM2B_SENSITIVE_DATA::from);
+        let potential_y2 = y2.map(TPM2B_ECC_PARAMETER::from);
+        let mut k_ptr = null_mut();
+        let mut l_ptr = null_mut();
+        let mut e_ptr = null_mut();
+        let mut counter = 0;
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_Commit(
+                    self.mut_context(),
+                    sign_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    potential_p1.as_ref().map_or_else(null, |v| v),
+                    potential_s2.as_ref().map_or_else(null, |v| v),
+                    potential_y2.as_ref().map_or_else(null, |v| v),
+                    &mut k_ptr,
+                    &mut l_ptr,
+                    &mut e_ptr,
+                    &mut counter,
+                )
+            },
+            |ret| {
+                error!("Error when performing commit: {:#010X}", ret);
+            },
+        )?;
+
+        let k = Context::ffi_data_to_owned(k_ptr);
+        let l = Context::ffi_data_to_owned(l_ptr);
+        let e = Context::ffi_data_to_owned(e_ptr);
+        Ok((
+            EccPoint::try_from(k.point)?,
+            EccPoint::try_from(l.point)?,
+            EccPoint::try_from(e.point)?,
+            CommitCounter::from(counter),
+        ))
+    }
+
     // Missing function: EC_Ephemeral
 }
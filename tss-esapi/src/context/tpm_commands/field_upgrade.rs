@@ -1,9 +1,115 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    constants::tss::TPM2_ALG_NULL,
+    handles::{AuthHandle, KeyHandle},
+    interface_types::reserved_handles::Platform,
+    structures::{Digest, HashAgile, MaxBuffer, Signature},
+    tss2_esys::{Esys_FieldUpgradeData, Esys_FieldUpgradeStart},
+    Context, Result, ReturnCode,
+};
+use log::error;
+use std::convert::{TryFrom, TryInto};
+use std::ptr::null_mut;
 
 impl Context {
-    // Missing function: FieldUpgradeStart
-    // Missing function: FieldUpgradeData
+    /// Starts a field upgrade of the TPM firmware.
+    ///
+    /// # Details
+    /// The field upgrade commands are optional and vendor specific, so
+    /// whether or not they are available, and the format of the upgrade
+    /// data, depends on the TPM. A TPM that does not implement them will
+    /// respond with a `TPM2_RC_COMMAND_CODE` error.
+    ///
+    /// Please beware that this method requires an authorization
+    /// session handle to be present.
+    ///
+    /// # Arguments
+    /// * `authorization` - The platform hierarchy authorization.
+    /// * `key_handle` - Handle of the public key used to verify the manifest signature.
+    /// * `fu_digest` - The digest of the first block of the field upgrade data.
+    /// * `manifest_signature` - The signature over `fu_digest` made with the key
+    ///                          referenced by `key_handle`.
+    pub fn field_upgrade_start(
+        &mut self,
+        authorization: Platform,
+        key_handle: KeyHandle,
+        fu_digest: Digest,
+        manifest_signature: Signature,
+    ) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_FieldUpgradeStart(
+                    self.mut_context(),
+                    AuthHandle::from(authorization).into(),
+                    key_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &fu_digest.into(),
+                    &manifest_signature.try_into()?,
+                )
+            },
+            |ret| {
+                error!("Error when starting field upgrade: {:#010X}", ret);
+            },
+        )
+    }
+
+    /// Sends a block of field upgrade data to the TPM.
+    ///
+    /// # Details
+    /// The field upgrade commands are optional and vendor specific, so
+    /// whether or not they are available, and the format of the upgrade
+    /// data, depends on the TPM. A TPM that does not implement them will
+    /// respond with a `TPM2_RC_COMMAND_CODE` error.
+    ///
+    /// # Arguments
+    /// * `fu_data` - A block of field upgrade data.
+    ///
+    /// # Returns
+    /// A tuple containing the tagged digest of the next block of data,
+    /// or `None` if the field upgrade is complete, and the tagged digest
+    /// of the first block of data.
+    ///
+    /// The digests are returned as [HashAgile] rather than [Digest], because
+    /// the TPM tags them with the hashing algorithm used for the next block.
+    /// Once the last block has been sent the next digest is tagged with
+    /// `TPM2_ALG_NULL`, which is returned as `None`.
+    pub fn field_upgrade_data(
+        &mut self,
+        fu_data: MaxBuffer,
+    ) -> Result<(Option<HashAgile>, HashAgile)> {
+        let mut next_digest_ptr = null_mut();
+        let mut first_digest_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_FieldUpgradeData(
+                    self.mut_context(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &fu_data.into(),
+                    &mut next_digest_ptr,
+                    &mut first_digest_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when sending field upgrade data: {:#010X}", ret);
+            },
+        )?;
+
+        let next_digest = Context::ffi_data_to_owned(next_digest_ptr);
+        let first_digest = Context::ffi_data_to_owned(first_digest_ptr);
+        Ok((
+            if next_digest.hashAlg == TPM2_ALG_NULL {
+                None
+            } else {
+                Some(HashAgile::try_from(next_digest)?)
+            },
+            HashAgile::try_from(first_digest)?,
+        ))
+    }
+
     // Missing function: FirmwareRead
 }
@@ -1,12 +1,144 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    context::handle_manager::HandleDropAction,
+    handles::{ObjectHandle, TpmHandle},
+    interface_types::{algorithm::HashingAlgorithm, reserved_handles::Hierarchy},
+    structures::{Auth, Digest, HashcheckTicket, MaxBuffer},
+    tss2_esys::{Esys_HashSequenceStart, Esys_SequenceComplete, Esys_SequenceUpdate},
+    Context, Result, ReturnCode,
+};
+use log::error;
+use std::convert::TryFrom;
+use std::ptr::null_mut;
 
 impl Context {
     // Missing function: HMAC_Start
     // Missing function: MAC_Start
-    // Missing function: HashSequenceStart
-    // Missing function: SequenceUpdate
-    // Missing function: SequenceComplete
+
+    /// Starts a hash sequence.
+    ///
+    /// # Details
+    /// The sequence allows hashing data that does not fit in a
+    /// single [MaxBuffer]. The data is added with [Context::sequence_update]
+    /// and the digest is returned by [Context::sequence_complete].
+    ///
+    /// # Arguments
+    /// * `hashing_algorithm` - The hashing algorithm of the sequence.
+    /// * `auth` - The authorization value of the sequence object.
+    ///
+    /// # Returns
+    /// The handle of the sequence object.
+    pub fn hash_sequence_start(
+        &mut self,
+        hashing_algorithm: HashingAlgorithm,
+        auth: Option<Auth>,
+    ) -> Result<ObjectHandle> {
+        let mut sequence_handle = ObjectHandle::None.into();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_HashSequenceStart(
+                    self.mut_context(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &auth.unwrap_or_default().into(),
+                    hashing_algorithm.into(),
+                    &mut sequence_handle,
+                )
+            },
+            |ret| {
+                error!("Error when starting hash sequence: {:#010X}", ret);
+            },
+        )?;
+        let sequence_handle = ObjectHandle::from(sequence_handle);
+        self.handle_manager
+            .add_handle(sequence_handle, HandleDropAction::Flush)?;
+        Ok(sequence_handle)
+    }
+
+    /// Adds data to a sequence.
+    ///
+    /// # Details
+    /// The sequence object is authorized using the first session.
+    ///
+    /// # Arguments
+    /// * `sequence_handle` - The handle of the sequence object.
+    /// * `data` - The data to add to the sequence.
+    pub fn sequence_update(
+        &mut self,
+        sequence_handle: ObjectHandle,
+        data: MaxBuffer,
+    ) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_SequenceUpdate(
+                    self.mut_context(),
+                    sequence_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &data.into(),
+                )
+            },
+            |ret| {
+                error!("Error when updating sequence: {:#010X}", ret);
+            },
+        )
+    }
+
+    /// Adds the last data to a hash sequence and returns the digest.
+    ///
+    /// # Details
+    /// The sequence object is authorized using the first session, and
+    /// it is flushed by the TPM when the sequence completes.
+    ///
+    /// As with [Context::hash], a usable ticket is only produced when
+    /// `hierarchy` is not the null hierarchy.
+    ///
+    /// # Arguments
+    /// * `sequence_handle` - The handle of the sequence object.
+    /// * `data` - The last data to add to the sequence.
+    /// * `hierarchy` - The hierarchy of the ticket.
+    ///
+    /// # Returns
+    /// The digest of the sequence and its [HashcheckTicket].
+    pub fn sequence_complete(
+        &mut self,
+        sequence_handle: ObjectHandle,
+        data: MaxBuffer,
+        hierarchy: Hierarchy,
+    ) -> Result<(Digest, HashcheckTicket)> {
+        let mut out_hash_ptr = null_mut();
+        let mut validation_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_SequenceComplete(
+                    self.mut_context(),
+                    sequence_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &data.into(),
+                    if cfg!(hierarchy_is_esys_tr) {
+                        ObjectHandle::from(hierarchy).into()
+                    } else {
+                        TpmHandle::from(hierarchy).into()
+                    },
+                    &mut out_hash_ptr,
+                    &mut validation_ptr,
+                )
+            },
+            |ret| {
+                error!("Error when completing sequence: {:#010X}", ret);
+            },
+        )?;
+        self.handle_manager.set_as_flushed(sequence_handle)?;
+        Ok((
+            Digest::try_from(Context::ffi_data_to_owned(out_hash_ptr))?,
+            HashcheckTicket::try_from(Context::ffi_data_to_owned(validation_ptr))?,
+        ))
+    }
+
     // Missing function: EventSequenceComplete
 }
@@ -21,6 +21,12 @@ impl Context {
     /// The authentication value, initial data, outside info and creation PCRs are passed as slices
     /// which are then converted by the method into TSS native structures.
     ///
+    /// # Details
+    /// A primary key created under [Hierarchy::Null] is ephemeral: the seed of the
+    /// null hierarchy changes on every TPM Reset, so the key cannot be recreated
+    /// after a reboot, and it cannot be made persistent with
+    /// [evict_control][Context::evict_control].
+    ///
     /// # Errors
     /// * if either of the slices is larger than the maximum size of the native objects, a
     /// `WrongParamSize` wrapper error is returned
@@ -52,9 +58,9 @@ impl Context {
                 Esys_CreatePrimary(
                     self.mut_context(),
                     ObjectHandle::from(primary_handle).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &sensitive_create.try_into()?,
                     &public.try_into()?,
                     &outside_info.unwrap_or_default().into(),
@@ -93,6 +99,9 @@ impl Context {
     // Missing function: ChangeEPS
 
     /// Clear all TPM context associated with a specific Owner
+    ///
+    /// # Details
+    /// The capabilities cached by the context are discarded.
     pub fn clear(&mut self, auth_handle: AuthHandle) -> Result<()> {
         ReturnCode::ensure_success(
             unsafe {
@@ -100,14 +109,16 @@ impl Context {
                     self.mut_context(),
                     auth_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
                 error!("Error in clearing TPM hierarchy: {:#010X}", ret);
             },
-        )
+        )?;
+        self.refresh_capabilities();
+        Ok(())
     }
 
     /// Disable or enable the TPM2_CLEAR command
@@ -118,8 +129,8 @@ impl Context {
                     self.mut_context(),
                     auth_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     YesNo::from(disable).into(),
                 )
             },
@@ -130,6 +141,12 @@ impl Context {
     }
 
     /// Change authorization for a hierarchy root
+    ///
+    /// # Details
+    /// The hierarchy is authorized with its current auth value. When the
+    /// command succeeds, the auth value of the hierarchy handle in the ESYS
+    /// context is updated to `new_auth`, so subsequent commands authorized
+    /// by the hierarchy use the new auth value.
     pub fn hierarchy_change_auth(&mut self, auth_handle: AuthHandle, new_auth: Auth) -> Result<()> {
         ReturnCode::ensure_success(
             unsafe {
@@ -137,8 +154,8 @@ impl Context {
                     self.mut_context(),
                     auth_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &new_auth.into(),
                 )
             },
@@ -1,9 +1,14 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    handles::PcrHandle,
-    structures::{DigestList, DigestValues, PcrSelectionList},
-    tss2_esys::{Esys_PCR_Extend, Esys_PCR_Read, Esys_PCR_Reset},
+    constants::tss::TPM2_PCR_FIRST,
+    handles::{AuthHandle, PcrHandle},
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Auth, Digest, DigestList, DigestValues, PcrSelectionList},
+    tss2_esys::{
+        Esys_PCR_Extend, Esys_PCR_Read, Esys_PCR_Reset, Esys_PCR_SetAuthPolicy,
+        Esys_PCR_SetAuthValue, ESYS_TR, ESYS_TR_PCR0,
+    },
     Context, Result, ReturnCode,
 };
 use log::error;
@@ -20,6 +25,12 @@ impl Context {
     /// # Details
     /// This method is used to cause an update to the indicated PCR. The digests param
     /// contains the digests for specific algorithms that are to be used.
+    /// Several banks can be extended at once by providing one digest per
+    /// hashing algorithm.
+    ///
+    /// # Errors
+    /// * if the length of a digest does not match the digest size of its
+    ///   hashing algorithm a `WrongParamSize` wrapper error is returned.
     ///
     /// # Example
     ///
@@ -93,9 +104,9 @@ impl Context {
                 Esys_PCR_Extend(
                     self.mut_context(),
                     pcr_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &digests.try_into()?,
                 )
             },
@@ -159,9 +170,9 @@ impl Context {
             unsafe {
                 Esys_PCR_Read(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &pcr_selection_list.into(),
                     &mut pcr_update_counter,
                     &mut pcr_selection_out_ptr,
@@ -181,8 +192,124 @@ impl Context {
     }
 
     // Missing function: PCR_Allocate
-    // Missing function: PCR_SetAuthPolicy
-    // Missing function: PCR_SetAuthValue
+
+    /// Sets the authorization policy of a group of PCRs.
+    ///
+    /// # Arguments
+    /// * `auth` - An [AuthHandle] that must be [AuthHandle::Platform].
+    /// * `policy` - The policy [Digest], or an empty digest to remove the policy.
+    /// * `hash_alg` - The [HashingAlgorithm] of the policy, or [HashingAlgorithm::Null]
+    ///                if the policy is empty.
+    /// * `pcr_num` - A [PcrHandle] to a PCR in the group.
+    ///
+    /// # Details
+    /// The policy applies to all the PCRs in the same policy group as `pcr_num`.
+    /// Only PCRs in a policy group can have an authorization policy, which PCRs
+    /// are in such a group is defined by the platform specification.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{
+    /// #     Context, TctiNameConf,
+    /// #     handles::{AuthHandle, PcrHandle},
+    /// #     interface_types::{algorithm::HashingAlgorithm, session_handles::AuthSession},
+    /// #     structures::Digest,
+    /// # };
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// // Remove the policy of the group of PCR 20.
+    /// context.execute_with_session(Some(AuthSession::Password), |ctx| {
+    ///     ctx.pcr_set_auth_policy(
+    ///         AuthHandle::Platform,
+    ///         Digest::default(),
+    ///         HashingAlgorithm::Null,
+    ///         PcrHandle::Pcr20,
+    ///     )
+    ///     .expect("Call to pcr_set_auth_policy failed");
+    /// });
+    /// ```
+    pub fn pcr_set_auth_policy(
+        &mut self,
+        auth: AuthHandle,
+        policy: Digest,
+        hash_alg: HashingAlgorithm,
+        pcr_num: PcrHandle,
+    ) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_PCR_SetAuthPolicy(
+                    self.mut_context(),
+                    auth.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &policy.into(),
+                    hash_alg.into(),
+                    // The PCR is given by its TPM handle and not by its ESYS_TR.
+                    TPM2_PCR_FIRST + (ESYS_TR::from(pcr_num) - ESYS_TR_PCR0),
+                )
+            },
+            |ret| {
+                error!("Error when setting the PCR auth policy: {:#010X}", ret);
+            },
+        )
+    }
+
+    /// Sets the authorization value of a group of PCRs.
+    ///
+    /// # Arguments
+    /// * `pcr_handle` - A [PcrHandle] to a PCR in the group.
+    /// * `auth` - The new [Auth] of the group.
+    ///
+    /// # Details
+    /// The authorization value applies to all the PCRs in the same authorization
+    /// group as `pcr_handle`. Only PCRs in an authorization group can have an
+    /// authorization value, which PCRs are in such a group is defined by the
+    /// platform specification.
+    ///
+    /// The command is authorized with the current authorization value of the PCR,
+    /// the authorization value of the handle is not updated by this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{
+    /// #     Context, TctiNameConf,
+    /// #     handles::PcrHandle,
+    /// #     interface_types::session_handles::AuthSession,
+    /// #     structures::Auth,
+    /// # };
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// context.execute_with_session(Some(AuthSession::Password), |ctx| {
+    ///     ctx.pcr_set_auth_value(PcrHandle::Pcr20, Auth::default())
+    ///         .expect("Call to pcr_set_auth_value failed");
+    /// });
+    /// ```
+    pub fn pcr_set_auth_value(&mut self, pcr_handle: PcrHandle, auth: Auth) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_PCR_SetAuthValue(
+                    self.mut_context(),
+                    pcr_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &auth.into(),
+                )
+            },
+            |ret| {
+                error!("Error when setting the PCR auth value: {:#010X}", ret);
+            },
+        )
+    }
 
     /// Resets the value in a PCR.
     ///
@@ -243,9 +370,9 @@ impl Context {
                 Esys_PCR_Reset(
                     self.mut_context(),
                     pcr_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -1,8 +1,69 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    constants::return_code::TpmFormatZeroError, handles::AuthHandle,
+    tss2_esys::Esys_SetAlgorithmSet, Context, Error, Result, ReturnCode, WrapperErrorKind,
+};
+use log::error;
 
 impl Context {
     // Missing function: PP_Commands
-    // Missing function: SetAlgorithmSet
+
+    /// Sets the algorithm set of the TPM.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - An [AuthHandle] that must be [AuthHandle::Platform].
+    /// * `algorithm_set` - A vendor dependent value identifying the algorithm set.
+    ///
+    /// # Details
+    /// The meaning of `algorithm_set` is defined by the vendor of the TPM
+    /// and the change only takes effect after the next `_TPM_Init`
+    /// followed by `TPM2_Startup(TPM_SU_CLEAR)`.
+    /// This command requires platform authorization.
+    ///
+    /// # Errors
+    /// * if the TPM does not implement `TPM2_SetAlgorithmSet` an
+    ///   `UnsupportedCommand` wrapper error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust, no_run
+    /// # use tss_esapi::{Context, TctiNameConf, interface_types::session_handles::AuthSession};
+    /// use tss_esapi::handles::AuthHandle;
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    ///
+    /// context.execute_with_session(Some(AuthSession::Password), |ctx| {
+    ///     ctx.set_algorithm_set(AuthHandle::Platform, 1)
+    ///         .expect("Failed to set the algorithm set");
+    /// });
+    /// ```
+    pub fn set_algorithm_set(&mut self, auth_handle: AuthHandle, algorithm_set: u32) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_SetAlgorithmSet(
+                    self.mut_context(),
+                    auth_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    algorithm_set,
+                )
+            },
+            |ret| {
+                error!("Error when setting the algorithm set: {:#010X}", ret);
+            },
+        )
+        .map_err(|e| {
+            if e.tpm_format_zero_error() == Some(TpmFormatZeroError::CommandCode) {
+                error!("The TPM does not implement TPM2_SetAlgorithmSet");
+                Error::local_error(WrapperErrorKind::UnsupportedCommand)
+            } else {
+                e
+            }
+        })
+    }
 }
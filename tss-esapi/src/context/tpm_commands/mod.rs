@@ -3,6 +3,7 @@
 mod asymmetric_primitives;
 mod attached_components;
 mod attestation_commands;
+#[cfg(has_act)]
 mod authenticated_countdown_timer;
 mod capability_commands;
 mod clocks_and_timers;
@@ -1,15 +1,21 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
+    constants::{
+        tss::{TPM2_RC_NV_DEFINED, TPM2_RC_NV_SPACE},
+        PropertyTag,
+    },
     context::handle_manager::HandleDropAction,
+    ffi::data_zeroize::FfiDataZeroize,
     handles::{AuthHandle, NvIndexHandle, ObjectHandle},
     interface_types::reserved_handles::{NvAuth, Provision},
     structures::{Auth, MaxNvBuffer, Name, NvPublic},
     tss2_esys::{
-        Esys_NV_DefineSpace, Esys_NV_Increment, Esys_NV_Read, Esys_NV_ReadPublic,
-        Esys_NV_UndefineSpace, Esys_NV_UndefineSpaceSpecial, Esys_NV_Write,
+        Esys_NV_ChangeAuth, Esys_NV_DefineSpace, Esys_NV_GlobalWriteLock, Esys_NV_Increment,
+        Esys_NV_Read, Esys_NV_ReadLock, Esys_NV_ReadPublic, Esys_NV_UndefineSpace,
+        Esys_NV_UndefineSpaceSpecial, Esys_NV_Write, Esys_NV_WriteLock, TPM2B_MAX_NV_BUFFER,
     },
-    Context, Result, ReturnCode,
+    Context, Error, Result, ReturnCode, WrapperErrorKind,
 };
 use log::error;
 use std::convert::{TryFrom, TryInto};
@@ -34,6 +40,14 @@ impl Context {
     /// A [NvIndexHandle] associated with the NV memory that
     /// was defined.
     ///
+    /// # Errors
+    /// * if the data area of `public_info` is larger than the maximum size of
+    ///   an NV index supported by the TPM, an `InvalidParam` wrapper error is returned.
+    /// * if the NV index is already defined, a `NvIndexAlreadyDefined`
+    ///   wrapper error is returned.
+    /// * if there is not enough NV memory left in the TPM to define the
+    ///   index, a `NvSpaceExhausted` wrapper error is returned.
+    ///
     /// # Example
     /// ```rust
     /// # use tss_esapi::{
@@ -103,6 +117,17 @@ impl Context {
         auth: Option<Auth>,
         public_info: NvPublic,
     ) -> Result<NvIndexHandle> {
+        if let Some(nv_index_max) = self.get_tpm_property(PropertyTag::NvIndexMax)? {
+            if public_info.data_size() > nv_index_max as usize {
+                error!(
+                    "The data area size {} is larger than the maximum size of an NV index ({})",
+                    public_info.data_size(),
+                    nv_index_max
+                );
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        }
+        let public_info_data_size = public_info.data_size();
         let mut nv_handle = ObjectHandle::None.into();
         ReturnCode::ensure_success(
             unsafe {
@@ -110,8 +135,8 @@ impl Context {
                     self.mut_context(),
                     AuthHandle::from(nv_auth).into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &auth.unwrap_or_default().into(),
                     &public_info.try_into()?,
                     &mut nv_handle,
@@ -120,7 +145,20 @@ impl Context {
             |ret| {
                 error!("Error when defining NV space: {:#010X}", ret);
             },
-        )?;
+        )
+        .map_err(|e| {
+            if e.is_rc(TPM2_RC_NV_DEFINED) {
+                Error::local_error(WrapperErrorKind::NvIndexAlreadyDefined)
+            } else if e.is_rc(TPM2_RC_NV_SPACE) {
+                error!(
+                    "Not enough NV memory left to define an index of {} bytes",
+                    public_info_data_size
+                );
+                Error::local_error(WrapperErrorKind::NvSpaceExhausted)
+            } else {
+                e
+            }
+        })?;
 
         self.handle_manager
             .add_handle(nv_handle.into(), HandleDropAction::Close)?;
@@ -214,8 +252,8 @@ impl Context {
                     AuthHandle::from(nv_auth).into(),
                     nv_index_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -358,8 +396,8 @@ impl Context {
                     nv_index_handle.into(),
                     AuthHandle::from(nv_auth).into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -463,9 +501,9 @@ impl Context {
                 Esys_NV_ReadPublic(
                     self.mut_context(),
                     nv_index_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut nv_public_ptr,
                     &mut nv_name_ptr,
                 )
@@ -577,23 +615,26 @@ impl Context {
         data: MaxNvBuffer,
         offset: u16,
     ) -> Result<()> {
-        ReturnCode::ensure_success(
-            unsafe {
-                Esys_NV_Write(
-                    self.mut_context(),
-                    AuthHandle::from(auth_handle).into(),
-                    nv_index_handle.into(),
-                    self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
-                    &data.into(),
-                    offset,
-                )
-            },
-            |ret| {
-                error!("Error when writing NV: {:#010X}", ret);
-            },
-        )
+        let session_1 = self.required_session_1()?;
+        let session_2 = self.optional_session_2()?;
+        let session_3 = self.optional_session_3()?;
+        let mut ffi_data = TPM2B_MAX_NV_BUFFER::from(data);
+        let return_code = unsafe {
+            Esys_NV_Write(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                session_1,
+                session_2,
+                session_3,
+                &ffi_data,
+                offset,
+            )
+        };
+        ffi_data.ffi_data_zeroize();
+        ReturnCode::ensure_success(return_code, |ret| {
+            error!("Error when writing NV: {:#010X}", ret);
+        })
     }
 
     /// Increment monotonic counter index
@@ -690,8 +731,8 @@ impl Context {
                     AuthHandle::from(auth_handle).into(),
                     nv_index_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| error!("Error when incrementing NV: {:#010X}", ret),
@@ -700,8 +741,70 @@ impl Context {
 
     // Missing function: NV_Extend
     // Missing function: NV_SetBits
-    // Missing function: NV_WriteLock
-    // Missing function: NV_GlobalWriteLock
+
+    /// Prevents further writes to an NV index.
+    ///
+    /// # Details
+    /// This method can only be used on NV indices that have either
+    /// the `write define` or the `write stclear` attribute set. For
+    /// indices with `write stclear` set, the lock is removed the next
+    /// time the TPM is started with `Startup(CLEAR)`. Any subsequent
+    /// write to the index will fail with a `TPM2_RC_NV_LOCKED` response
+    /// code.
+    ///
+    /// Please beware that this method requires an authorization
+    /// session handle to be present.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - Handle indicating the source of authorization value.
+    /// * `nv_index_handle` - The [NvIndexHandle] associated with NV memory
+    ///                       that is to be write locked.
+    pub fn nv_write_lock(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_NV_WriteLock(
+                    self.mut_context(),
+                    AuthHandle::from(auth_handle).into(),
+                    nv_index_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                )
+            },
+            |ret| error!("Error when write locking NV: {:#010X}", ret),
+        )
+    }
+
+    /// Prevents further writes to all NV indices that have the
+    /// `global lock` attribute set.
+    ///
+    /// # Details
+    /// The lock is removed the next time the TPM is started with
+    /// `Startup(CLEAR)`.
+    ///
+    /// Please beware that this method requires an authorization
+    /// session handle to be present.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - The [Provision] used for authorization.
+    pub fn nv_global_write_lock(&mut self, auth_handle: Provision) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_NV_GlobalWriteLock(
+                    self.mut_context(),
+                    AuthHandle::from(auth_handle).into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                )
+            },
+            |ret| error!("Error when globally write locking NV: {:#010X}", ret),
+        )
+    }
 
     /// Reads data from the nv index.
     ///
@@ -709,6 +812,10 @@ impl Context {
     /// This method is used to read a value from an area in
     /// NV memory of the TPM.
     ///
+    /// NV indices often hold secrets, so the memory allocated by the
+    /// TSS for the response is zeroized before it is freed and the
+    /// returned [MaxNvBuffer] is zeroized when it is dropped.
+    ///
     /// Please beware that this method requires an authorization
     /// session handle to be present.
     ///
@@ -814,8 +921,8 @@ impl Context {
                     AuthHandle::from(auth_handle).into(),
                     nv_index_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     size,
                     offset,
                     &mut data_ptr,
@@ -825,10 +932,105 @@ impl Context {
                 error!("Error when reading NV: {:#010X}", ret);
             },
         )?;
-        MaxNvBuffer::try_from(Context::ffi_data_to_owned(data_ptr))
+        let mut ffi_data = crate::ffi::to_owned_with_zeroized_source(data_ptr);
+        let data = MaxNvBuffer::try_from(ffi_data);
+        ffi_data.ffi_data_zeroize();
+        data
+    }
+
+    /// Prevents further reads of an NV index.
+    ///
+    /// # Details
+    /// This method can only be used on NV indices that have the
+    /// `read stclear` attribute set. The lock is removed the next
+    /// time the TPM is started with `Startup(CLEAR)`. Until then any
+    /// read of the index will fail with a `TPM2_RC_NV_LOCKED` response
+    /// code.
+    ///
+    /// Please beware that this method requires an authorization
+    /// session handle to be present.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - Handle indicating the source of authorization value.
+    /// * `nv_index_handle` - The [NvIndexHandle] associated with NV memory
+    ///                       that is to be read locked.
+    pub fn nv_read_lock(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_NV_ReadLock(
+                    self.mut_context(),
+                    AuthHandle::from(auth_handle).into(),
+                    nv_index_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                )
+            },
+            |ret| error!("Error when read locking NV: {:#010X}", ret),
+        )
+    }
+
+    /// Changes the authorization value of an NV index.
+    ///
+    /// # Details
+    /// The TPM only allows the authorization value of an NV index
+    /// to be changed using the admin role, which means that the command
+    /// has to be authorized using a policy session that satisfies the
+    /// `authPolicy` of the NV index (e.g. one that has been restricted to
+    /// `TPM2_NV_ChangeAuth` using `policy_command_code`).
+    ///
+    /// The size of `new_auth` is validated against the digest size of
+    /// the name algorithm of the NV index before the command is sent.
+    /// When the command succeeds the authorization value associated with
+    /// the `nv_index_handle` in the ESYS context is updated to `new_auth`.
+    ///
+    /// Please beware that this method requires an authorization
+    /// session handle to be present.
+    ///
+    /// # Arguments
+    /// * `nv_index_handle` - The [NvIndexHandle] associated with NV memory
+    ///                       whose authorization value is to be changed.
+    /// * `new_auth` - The new authorization value.
+    ///
+    /// # Errors
+    /// * if `new_auth` is larger than the digest size of the name algorithm
+    ///   of the NV index, a `WrongParamSize` wrapper error is returned.
+    pub fn nv_change_auth(&mut self, nv_index_handle: NvIndexHandle, new_auth: Auth) -> Result<()> {
+        let (nv_public, _) =
+            self.execute_without_session(|ctx| ctx.nv_read_public(nv_index_handle))?;
+        let max_auth_size = nv_public
+            .name_algorithm()
+            .digest_size()
+            .ok_or_else(|| Error::local_error(WrapperErrorKind::InconsistentParams))?;
+        if new_auth.len() > max_auth_size {
+            error!(
+                "The new auth value is larger than the digest size of the name algorithm ({} > {})",
+                new_auth.len(),
+                max_auth_size
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_NV_ChangeAuth(
+                    self.mut_context(),
+                    nv_index_handle.into(),
+                    self.required_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &new_auth.clone().into(),
+                )
+            },
+            |ret| error!("Error when changing NV auth: {:#010X}", ret),
+        )?;
+
+        self.tr_set_auth(nv_index_handle.into(), new_auth)
     }
 
-    // Missing function: NV_ReadLock
-    // Missing function: NV_ChangeAuth
     // Missing function: NV_Certify
 }
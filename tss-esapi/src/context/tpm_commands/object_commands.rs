@@ -5,6 +5,7 @@ mod create_command_output;
 
 use crate::{
     context::handle_manager::HandleDropAction,
+    ffi::data_zeroize::FfiDataZeroize,
     handles::{KeyHandle, ObjectHandle, TpmHandle},
     interface_types::reserved_handles::Hierarchy,
     structures::{
@@ -13,7 +14,7 @@ use crate::{
     },
     tss2_esys::{
         Esys_ActivateCredential, Esys_Create, Esys_Load, Esys_LoadExternal, Esys_MakeCredential,
-        Esys_ObjectChangeAuth, Esys_ReadPublic, Esys_Unseal,
+        Esys_ObjectChangeAuth, Esys_ReadPublic, Esys_Unseal, TPM2B_PRIVATE, TPM2B_PUBLIC,
     },
     Context, Result, ReturnCode,
 };
@@ -69,9 +70,9 @@ impl Context {
                 Esys_Create(
                     self.mut_context(),
                     input_parameters.ffi_in_parent_handle(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     input_parameters.ffi_in_sensitive(),
                     input_parameters.ffi_in_public(),
                     input_parameters.ffi_outside_info(),
@@ -99,26 +100,33 @@ impl Context {
         public: Public,
     ) -> Result<KeyHandle> {
         let mut object_handle = ObjectHandle::None.into();
-        ReturnCode::ensure_success(
-            unsafe {
-                Esys_Load(
-                    self.mut_context(),
-                    parent_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
-                    &private.into(),
-                    &public.try_into()?,
-                    &mut object_handle,
-                )
-            },
-            |ret| {
-                error!("Error in loading: {:#010X}", ret);
-            },
-        )?;
+        let session_1 = self.optional_session_1()?;
+        let session_2 = self.optional_session_2()?;
+        let session_3 = self.optional_session_3()?;
+        let ffi_public = TPM2B_PUBLIC::try_from(public)?;
+        let mut ffi_private = TPM2B_PRIVATE::from(private);
+        let return_code = unsafe {
+            Esys_Load(
+                self.mut_context(),
+                parent_handle.into(),
+                session_1,
+                session_2,
+                session_3,
+                &ffi_private,
+                &ffi_public,
+                &mut object_handle,
+            )
+        };
+        ffi_private.ffi_data_zeroize();
+        ReturnCode::ensure_success(return_code, |ret| {
+            error!("Error in loading: {:#010X}", ret);
+        })?;
         let key_handle = KeyHandle::from(object_handle);
-        self.handle_manager
-            .add_handle(key_handle.into(), HandleDropAction::Flush)?;
+        self.handle_manager.add_child_handle(
+            key_handle.into(),
+            parent_handle.into(),
+            HandleDropAction::Flush,
+        )?;
         Ok(key_handle)
     }
 
@@ -134,9 +142,9 @@ impl Context {
             unsafe {
                 Esys_LoadExternal(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &private.try_into()?,
                     &public.try_into()?,
                     if cfg!(hierarchy_is_esys_tr) {
@@ -169,9 +177,9 @@ impl Context {
             unsafe {
                 Esys_LoadExternal(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     null(),
                     &public.try_into()?,
                     if cfg!(hierarchy_is_esys_tr) {
@@ -194,6 +202,12 @@ impl Context {
     }
 
     /// Read the public part of a key currently in the TPM and return it.
+    ///
+    /// # Details
+    /// Returns the public area, the name and the qualified name of the key.
+    /// The qualified name is computed by the TPM from the qualified name of
+    /// the parent and the name of the key, and so identifies the key together
+    /// with its whole hierarchy.
     pub fn read_public(&mut self, key_handle: KeyHandle) -> Result<(Public, Name, Name)> {
         let mut out_public_ptr = null_mut();
         let mut name_ptr = null_mut();
@@ -203,9 +217,9 @@ impl Context {
                 Esys_ReadPublic(
                     self.mut_context(),
                     key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut out_public_ptr,
                     &mut name_ptr,
                     &mut qualified_name_ptr,
@@ -239,7 +253,7 @@ impl Context {
                     key_handle.into(),
                     self.required_session_1()?,
                     self.required_session_2()?,
-                    self.optional_session_3(),
+                    self.optional_session_3()?,
                     &credential_blob.into(),
                     &secret.into(),
                     &mut cert_info_ptr,
@@ -269,9 +283,9 @@ impl Context {
                 Esys_MakeCredential(
                     self.mut_context(),
                     key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &credential.into(),
                     object_name.as_ref(),
                     &mut credential_blob_ptr,
@@ -297,9 +311,9 @@ impl Context {
                 Esys_Unseal(
                     self.mut_context(),
                     item_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut out_data_ptr,
                 )
             },
@@ -311,6 +325,19 @@ impl Context {
     }
 
     /// Change authorization for a TPM-resident object.
+    ///
+    /// # Details
+    /// The TPM does not modify the loaded object. Instead a new private
+    /// area, protected by `parent_handle` and holding `new_auth`, is returned.
+    /// This has to be [loaded][Context::load] together with the public area of
+    /// the object before the new authorization value can be used, and the old
+    /// private area should be discarded.
+    ///
+    /// # Arguments
+    /// * `object_handle` - The handle of the loaded object. The call must be
+    ///   authorized with the current auth value of the object, using the ADMIN role.
+    /// * `parent_handle` - The handle of the parent of the object.
+    /// * `new_auth` - The new authorization value of the object.
     pub fn object_change_auth(
         &mut self,
         object_handle: ObjectHandle,
@@ -325,8 +352,8 @@ impl Context {
                     object_handle.into(),
                     parent_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &new_auth.into(),
                     &mut out_private_ptr,
                 )
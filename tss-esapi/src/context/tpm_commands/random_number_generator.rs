@@ -12,6 +12,13 @@ use std::ptr::null_mut;
 impl Context {
     /// Get a number of random bytes from the TPM and return them.
     ///
+    /// # Details
+    /// The TPM reseeds its random number generator on reset, but does
+    /// not report whether this has happened. Callers wanting to mix in
+    /// their own entropy before drawing bytes can use
+    /// [stir_random][Context::stir_random] first, see
+    /// `abstraction::rng::reseed_and_get_random`.
+    ///
     /// # Errors
     /// * if converting `num_bytes` to `u16` fails, a `WrongParamSize` will be returned
     pub fn get_random(&mut self, num_bytes: usize) -> Result<Digest> {
@@ -20,9 +27,9 @@ impl Context {
             unsafe {
                 Esys_GetRandom(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     num_bytes
                         .try_into()
                         .map_err(|_| Error::local_error(ErrorKind::WrongParamSize))?,
@@ -37,14 +44,18 @@ impl Context {
     }
 
     /// Add additional information into the TPM RNG state
+    ///
+    /// # Details
+    /// TPMs are only required to accept up to 128 bytes of `in_data`
+    /// in a single call.
     pub fn stir_random(&mut self, in_data: SensitiveData) -> Result<()> {
         ReturnCode::ensure_success(
             unsafe {
                 Esys_StirRandom(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &in_data.into(),
                 )
             },
@@ -69,9 +69,9 @@ impl Context {
                         .unwrap_or(ObjectHandle::None)
                         .into(),
                     bind.unwrap_or(ObjectHandle::None).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     potential_tpm2b_nonce.as_ref().map_or_else(null, |v| v),
                     session_type.into(),
                     &symmetric.try_into()?,
@@ -94,15 +94,25 @@ impl Context {
     }
 
     /// Restart the TPM Policy
+    ///
+    /// # Arguments
+    /// * `policy_session` - The [PolicySession] to restart.
+    ///
+    /// # Details
+    /// The policy digest of the session is set back to all zeros
+    /// and the other policy state of the session is cleared, without
+    /// the session having to be flushed and started again. This
+    /// allows a policy to be evaluated again, e.g. when a
+    /// policy assertion failed or a PCR has changed.
     pub fn policy_restart(&mut self, policy_session: PolicySession) -> Result<()> {
         ReturnCode::ensure_success(
             unsafe {
                 Esys_PolicyRestart(
                     self.mut_context(),
                     SessionHandle::from(policy_session).into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                 )
             },
             |ret| {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     handles::KeyHandle,
-    structures::{Digest, HashcheckTicket, Signature, SignatureScheme, VerifiedTicket},
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Digest, HashAgile, HashcheckTicket, Signature, SignatureScheme, VerifiedTicket},
     tss2_esys::{Esys_Sign, Esys_VerifySignature},
     Context, Result, ReturnCode,
 };
@@ -24,9 +25,9 @@ impl Context {
                 Esys_VerifySignature(
                     self.mut_context(),
                     key_handle.into(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &digest.into(),
                     &signature.try_into()?,
                     &mut validation_ptr,
@@ -39,11 +40,45 @@ impl Context {
         VerifiedTicket::try_from(Context::ffi_data_to_owned(validation_ptr))
     }
 
+    /// Verify if an HMAC was generated by computing it over the given data with a keyed hash key in the TPM.
+    ///
+    /// # Details
+    /// The HMAC is verified with `TPM2_VerifySignature`, which computes the HMAC over
+    /// its digest parameter, so the data must fit in a [Digest]. An HMAC computed by
+    /// [Context::hmac] over the same data and with the same key can be verified.
+    ///
+    /// # Parameters
+    /// `key_handle` - Handle to the keyed hash key used for computing the HMAC.
+    /// `data`       - The data over which the HMAC was computed.
+    /// `mac`        - The HMAC that is going to be verified.
+    /// `alg_hash`   - The hashing algorithm used for computing the HMAC.
+    ///
+    /// # Errors
+    /// * if the HMAC does not match, the TPM returns an error.
+    pub fn mac_verify(
+        &mut self,
+        key_handle: KeyHandle,
+        data: Digest,
+        mac: Digest,
+        alg_hash: HashingAlgorithm,
+    ) -> Result<VerifiedTicket> {
+        self.verify_signature(
+            key_handle,
+            data,
+            Signature::Hmac(HashAgile::new(alg_hash, mac)),
+        )
+    }
+
     /// Sign a digest with a key present in the TPM and return the signature.
     ///
     /// # Details
     /// For signatures using a restricted key, a hashcheck must be provided. For unrestricted keys, this may be None.
     ///
+    /// The hashcheck ticket of a digest is returned by the TPM when the digest
+    /// is computed using [Context::hash]. It proves
+    /// that the digested data did not start with the `TPM_GENERATED_VALUE`, so a
+    /// restricted key will reject signing the digest without it.
+    ///
     /// # Parameters
     /// `key_handle` - Handle to the key be used for signing.
     /// `digest`     - The digest that is going to be signed.
@@ -106,8 +141,8 @@ impl Context {
                     self.mut_context(),
                     key_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &digest.into(),
                     &scheme.into(),
                     &validation_ticket,
@@ -24,9 +24,9 @@ impl Context {
             unsafe {
                 Esys_Shutdown(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     shutdown_type.into(),
                 )
             },
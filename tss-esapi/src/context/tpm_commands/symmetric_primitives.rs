@@ -197,8 +197,8 @@ impl Context {
                     self.mut_context(),
                     key_handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &in_data.into(),
                     decrypt.into(),
                     mode.into(),
@@ -227,6 +227,9 @@ impl Context {
     /// the result. The HashCheckTicket indicates if the hash can be used in
     /// a signing operation that uses restricted signing key.
     ///
+    /// A usable ticket is only produced when `hierarchy` is not the null
+    /// hierarchy. Using [Hierarchy::Null] returns a null ticket.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -271,9 +274,9 @@ impl Context {
             unsafe {
                 Esys_Hash(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &data.into(),
                     hashing_algorithm.into(),
                     if cfg!(hierarchy_is_esys_tr) {
@@ -358,8 +361,8 @@ impl Context {
                     self.mut_context(),
                     handle.into(),
                     self.required_session_1()?,
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &buffer.into(),
                     alg_hash.into(),
                     &mut out_hmac_ptr,
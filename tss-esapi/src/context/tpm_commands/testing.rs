@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     interface_types::YesNo,
-    structures::MaxBuffer,
-    tss2_esys::{Esys_GetTestResult, Esys_SelfTest},
+    structures::{AlgorithmList, MaxBuffer},
+    tss2_esys::{Esys_GetTestResult, Esys_IncrementalSelfTest, Esys_SelfTest, TPML_ALG},
     Context, Result, ReturnCode,
 };
 use log::error;
@@ -17,9 +17,9 @@ impl Context {
             unsafe {
                 Esys_SelfTest(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     YesNo::from(full_test).into(),
                 )
             },
@@ -29,7 +29,55 @@ impl Context {
         )
     }
 
-    // Missing function: incremental_self_test
+    /// Execute the TPM self test of the selected algorithms
+    ///
+    /// # Arguments
+    /// * `to_test` - An [AlgorithmList] with the algorithms that should be tested.
+    ///
+    /// # Details
+    /// Algorithms that have already been tested are not tested again.
+    /// The TPM may return before the tests are complete, [Context::get_test_result]
+    /// can be used to check the outcome.
+    ///
+    /// # Returns
+    /// An [AlgorithmList] with the algorithms that still need to be tested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{Context, TctiNameConf};
+    /// use tss_esapi::{constants::AlgorithmIdentifier, structures::AlgorithmList};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    ///
+    /// let mut to_test = AlgorithmList::new();
+    /// to_test.add(AlgorithmIdentifier::Sha256).expect("Failed to add algorithm");
+    /// let _to_do_list = context
+    ///     .incremental_self_test(to_test)
+    ///     .expect("Failed to run incremental self test");
+    /// ```
+    pub fn incremental_self_test(&mut self, to_test: AlgorithmList) -> Result<AlgorithmList> {
+        let mut to_do_list_ptr = null_mut();
+        ReturnCode::ensure_success(
+            unsafe {
+                Esys_IncrementalSelfTest(
+                    self.mut_context(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
+                    &TPML_ALG::from(to_test),
+                    &mut to_do_list_ptr,
+                )
+            },
+            |ret| {
+                error!("Error in incremental self-test: {:#010X}", ret);
+            },
+        )?;
+        AlgorithmList::try_from(Context::ffi_data_to_owned(to_do_list_ptr))
+    }
 
     /// Get the TPM self test result
     ///
@@ -63,9 +111,9 @@ impl Context {
             unsafe {
                 Esys_GetTestResult(
                     self.mut_context(),
-                    self.optional_session_1(),
-                    self.optional_session_2(),
-                    self.optional_session_3(),
+                    self.optional_session_1()?,
+                    self.optional_session_2()?,
+                    self.optional_session_3()?,
                     &mut out_data_ptr,
                     &mut test_result,
                 )
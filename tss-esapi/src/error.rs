@@ -10,6 +10,15 @@ pub use return_code::{
 };
 pub use wrapper::WrapperErrorKind;
 
+use crate::{
+    constants::{
+        return_code::{TpmFormatOneError, TpmFormatZeroError},
+        BaseError,
+    },
+    tss2_esys::TSS2_RC,
+};
+use std::convert::TryFrom;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Main error type used by the crate to return issues with a method call. The value can either be
@@ -31,6 +40,85 @@ impl Error {
     pub(crate) const fn tss_error(return_code: ReturnCode) -> Self {
         Error::TssError(return_code)
     }
+
+    /// Checks if the error corresponds to the TSS return code `rc`.
+    ///
+    /// # Details
+    /// Format one TPM response codes indicate which handle, parameter
+    /// or session caused the error. If `rc` does not specify one,
+    /// e.g. `TPM2_RC_BAD_AUTH`, then this is ignored when comparing
+    /// the response codes.
+    pub fn is_rc(&self, rc: TSS2_RC) -> bool {
+        let (actual, expected) = match (self, ReturnCode::try_from(rc)) {
+            (Error::TssError(actual), Ok(expected)) => (*actual, expected),
+            (Error::WrapperError(WrapperErrorKind::Transport(actual)), Ok(expected)) => {
+                (ReturnCode::Tcti(*actual), expected)
+            }
+            _ => return false,
+        };
+        match (actual, expected) {
+            (
+                ReturnCode::Tpm(TpmResponseCode::FormatOne(actual)),
+                ReturnCode::Tpm(TpmResponseCode::FormatOne(expected)),
+            ) if expected.argument_number() == ArgumentNumber::Handle(0) => {
+                actual.error_number() == expected.error_number()
+            }
+            (actual, expected) => actual == expected,
+        }
+    }
+
+    /// Checks if the error was caused by the TCTI failing to transport
+    /// a command to the TPM or its response back.
+    ///
+    /// # Details
+    /// Such errors are not reported by the TPM, and usually mean that
+    /// the connection to the TPM has been lost. The context can then be
+    /// [reconnected][crate::Context::reconnect].
+    pub fn is_transport_error(&self) -> bool {
+        matches!(self, Error::WrapperError(WrapperErrorKind::Transport(_)))
+    }
+
+    /// Checks if the error is a `TPM2_RC_BAD_AUTH` response from the TPM.
+    pub fn is_bad_auth(&self) -> bool {
+        self.tpm_format_one_error() == Some(TpmFormatOneError::BadAuth)
+    }
+
+    /// Checks if the error is a `TPM2_RC_POLICY_FAIL` response from the TPM.
+    pub fn is_policy_fail(&self) -> bool {
+        self.tpm_format_one_error() == Some(TpmFormatOneError::PolicyFail)
+    }
+
+    /// Checks if the error is a `TPM2_RC_ATTRIBUTES` response from the TPM.
+    pub fn is_attributes(&self) -> bool {
+        self.tpm_format_one_error() == Some(TpmFormatOneError::Attributes)
+    }
+
+    /// Checks if the error is a `TPM2_RC_NV_LOCKED` response from the TPM.
+    pub fn is_nv_locked(&self) -> bool {
+        self.tpm_format_zero_error() == Some(TpmFormatZeroError::NvLocked)
+    }
+
+    /// Returns the error number if the error is a
+    /// format one response code from the TPM.
+    fn tpm_format_one_error(&self) -> Option<TpmFormatOneError> {
+        match self {
+            Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatOne(rc))) => {
+                Some(rc.error_number())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the error number if the error is a format
+    /// zero error response code from the TPM.
+    pub(crate) fn tpm_format_zero_error(&self) -> Option<TpmFormatZeroError> {
+        match self {
+            Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+                TpmFormatZeroResponseCode::Error(rc),
+            ))) => Some(rc.error_number()),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -42,6 +130,37 @@ impl std::fmt::Display for Error {
     }
 }
 
+impl From<Error> for std::io::Error {
+    /// Converts the error into an I/O error.
+    ///
+    /// # Details
+    /// Transport errors and other TCTI errors are given the I/O error
+    /// kind matching the TCTI return code, other errors are of the
+    /// `Other` kind.
+    fn from(error: Error) -> Self {
+        let kind = match error {
+            Error::WrapperError(WrapperErrorKind::Transport(tcti_return_code))
+            | Error::TssError(ReturnCode::Tcti(tcti_return_code)) => {
+                match tcti_return_code.base_error() {
+                    BaseError::NoConnection => std::io::ErrorKind::NotConnected,
+                    BaseError::TryAgain => std::io::ErrorKind::WouldBlock,
+                    BaseError::NotPermitted => std::io::ErrorKind::PermissionDenied,
+                    BaseError::MalformedResponse => std::io::ErrorKind::InvalidData,
+                    BaseError::BadValue | BaseError::BadReference => {
+                        std::io::ErrorKind::InvalidInput
+                    }
+                    BaseError::NotImplemented | BaseError::NotSupported => {
+                        std::io::ErrorKind::Unsupported
+                    }
+                    _ => std::io::ErrorKind::Other,
+                }
+            }
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -10,9 +10,9 @@ mod tcti;
 mod tpm;
 
 use crate::{
-    constants::{return_code::ReturnCodeLayer, tss::TSS2_RC_SUCCESS},
+    constants::{return_code::ReturnCodeLayer, tss::TSS2_RC_SUCCESS, BaseError},
     tss2_esys::TSS2_RC,
-    Error, Result,
+    Error, Result, WrapperErrorKind,
 };
 pub use base::BaseReturnCode;
 use bitfield::bitfield;
@@ -49,7 +49,9 @@ impl ReturnCode {
     /// * `f` - Function that will be executed before an error is returned.
     ///
     /// # Errors
-    /// Generates the error indicated by the return code.
+    /// Generates the error indicated by the return code. TCTI return codes
+    /// indicating that the connection to the TPM failed are reported as
+    /// `Transport` wrapper errors, see [ReturnCode::is_transport_failure].
     pub(crate) fn ensure_success<F>(tss2_rc: TSS2_RC, f: F) -> Result<()>
     where
         F: FnOnce(TSS2_RC),
@@ -58,9 +60,35 @@ impl ReturnCode {
             Ok(())
         } else {
             f(tss2_rc);
-            Err(Error::tss_error(ReturnCode::try_from(tss2_rc)?))
+            match ReturnCode::try_from(tss2_rc)? {
+                ReturnCode::Tcti(tcti_return_code)
+                    if ReturnCode::is_transport_failure(tcti_return_code) =>
+                {
+                    Err(Error::local_error(WrapperErrorKind::Transport(
+                        tcti_return_code,
+                    )))
+                }
+                return_code => Err(Error::tss_error(return_code)),
+            }
         }
     }
+
+    /// Checks if the TCTI return code indicates that the TCTI failed
+    /// to transport a command to the TPM or its response back.
+    ///
+    /// # Details
+    /// Only I/O errors, connection errors, requests to try again and
+    /// malformed responses are transport failures. The other TCTI return
+    /// codes, e.g. bad values or sequences, are caused by the caller.
+    fn is_transport_failure(tcti_return_code: TctiReturnCode) -> bool {
+        matches!(
+            tcti_return_code.base_error(),
+            BaseError::IoError
+                | BaseError::NoConnection
+                | BaseError::TryAgain
+                | BaseError::MalformedResponse
+        )
+    }
 }
 
 impl TryFrom<TSS2_RC> for ReturnCode {
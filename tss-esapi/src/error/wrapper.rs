@@ -1,5 +1,7 @@
 // Copyright 2022 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+use crate::error::TctiReturnCode;
+
 /// List of error types that might occur in the wrapper.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -23,6 +25,30 @@ pub enum WrapperErrorKind {
     /// Returned when a handle is required to be in a specific state
     /// (i.g. Open, Flushed, Closed) but it is not.
     InvalidHandleState,
+    /// Returned when an NV index is read before it has been written.
+    NvIndexNotWritten,
+    /// Returned when defining an NV index that is already defined.
+    NvIndexAlreadyDefined,
+    /// Returned when there is not enough NV memory left to define an NV index.
+    NvSpaceExhausted,
+    /// Returned when the values of the PCRs do not match the expected digest.
+    PcrMismatch,
+    /// Returned when a session set with `Context::set_sessions` is
+    /// used after it has been flushed. Session handles passed as
+    /// command parameters, e.g. the policy session of the policy
+    /// commands, are not checked.
+    SessionAlreadyFlushed,
+    /// Returned when the TPM did not respond before the timeout
+    /// of the command elapsed.
+    Timeout,
+    /// Returned when the TPM does not implement the command.
+    UnsupportedCommand,
+    /// Returned when the TCTI failed to transport a command or its
+    /// response, e.g. because the connection to the TPM was lost.
+    /// Holds the return code of the TCTI, which is either an I/O error,
+    /// a connection error, a request to try again or a malformed
+    /// response. The other TCTI return codes are reported as TSS errors.
+    Transport(TctiReturnCode),
     /// An unexpected internal error occurred.
     InternalError,
 }
@@ -50,6 +76,28 @@ impl std::fmt::Display for WrapperErrorKind {
             WrapperErrorKind::WrongValueFromTpm => write!(f, "The TPM returned an invalid value."),
             WrapperErrorKind::MissingAuthSession => write!(f, "Missing authorization session."),
             WrapperErrorKind::InvalidHandleState => write!(f, "Invalid handle state."),
+            WrapperErrorKind::NvIndexNotWritten => {
+                write!(f, "The NV index has not been written.")
+            }
+            WrapperErrorKind::NvIndexAlreadyDefined => {
+                write!(f, "The NV index is already defined.")
+            }
+            WrapperErrorKind::NvSpaceExhausted => {
+                write!(f, "There is not enough NV memory left.")
+            }
+            WrapperErrorKind::PcrMismatch => {
+                write!(f, "The PCR values do not match the expected digest.")
+            }
+            WrapperErrorKind::SessionAlreadyFlushed => {
+                write!(f, "The session has already been flushed.")
+            }
+            WrapperErrorKind::Timeout => write!(f, "The TPM did not respond in time."),
+            WrapperErrorKind::UnsupportedCommand => {
+                write!(f, "The command is not supported by the TPM.")
+            }
+            WrapperErrorKind::Transport(tcti_return_code) => {
+                write!(f, "The TCTI failed to reach the TPM: {}", tcti_return_code)
+            }
             WrapperErrorKind::InternalError => {
                 write!(f, "An unexpected error occurred within the crate.")
             }
@@ -273,6 +273,13 @@ pub mod auth {
     use crate::tss2_esys::{
         ESYS_TR_RH_ENDORSEMENT, ESYS_TR_RH_LOCKOUT, ESYS_TR_RH_OWNER, ESYS_TR_RH_PLATFORM,
     };
+    #[cfg(has_act)]
+    use crate::{
+        tss2_esys::{ESYS_TR_RH_ACT_FIRST, ESYS_TR_RH_ACT_LAST},
+        Error, Result, WrapperErrorKind,
+    };
+    #[cfg(has_act)]
+    use log::error;
     impl_basic_handle!(
         /// Auth handle
         ///
@@ -288,7 +295,24 @@ pub mod auth {
     add_constant_handle!(AuthHandle, Endorsement, ESYS_TR_RH_ENDORSEMENT);
     add_constant_handle!(AuthHandle, Platform, ESYS_TR_RH_PLATFORM);
     // TODO: Figure out how to add AUTH_00 to AUTH_FF range
-    // TODO: Figure out how to add ACT_0 to ACT_F range
+
+    #[cfg(has_act)]
+    impl AuthHandle {
+        /// Creates an AuthHandle for the authenticated countdown
+        /// timer (ACT) with the given number (0 to 15).
+        ///
+        /// # Errors
+        /// * if `act_number` is larger than 15 an `InvalidParam` wrapper
+        ///   error is returned.
+        pub fn act(act_number: u8) -> Result<AuthHandle> {
+            let value = ESYS_TR_RH_ACT_FIRST + u32::from(act_number);
+            if value > ESYS_TR_RH_ACT_LAST {
+                error!("Invalid ACT number {} (> 15)", act_number);
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+            Ok(AuthHandle { value })
+        }
+    }
 }
 
 /// NV Index handle module
@@ -10,11 +10,17 @@ use crate::{
     Error, Result, WrapperErrorKind,
 };
 use std::convert::TryFrom;
+
+#[cfg(feature = "openssl")]
+use crate::structures::Digest;
+#[cfg(feature = "openssl")]
+use openssl::hash::MessageDigest;
+
 /// Enum containing the supported hash algorithms
 ///
 /// # Details
 /// This corresponds to TPMI_ALG_HASH interface type.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum HashingAlgorithm {
     Sha1,
     Sha256,
@@ -27,6 +33,48 @@ pub enum HashingAlgorithm {
     Null,
 }
 
+impl HashingAlgorithm {
+    /// Returns the size, in bytes, of the digests produced by
+    /// the hashing algorithm.
+    ///
+    /// # Details
+    /// `None` is returned for [HashingAlgorithm::Null].
+    pub const fn digest_size(&self) -> Option<usize> {
+        match self {
+            HashingAlgorithm::Sha1 => Some(20),
+            HashingAlgorithm::Sha256 | HashingAlgorithm::Sm3_256 | HashingAlgorithm::Sha3_256 => {
+                Some(32)
+            }
+            HashingAlgorithm::Sha384 | HashingAlgorithm::Sha3_384 => Some(48),
+            HashingAlgorithm::Sha512 | HashingAlgorithm::Sha3_512 => Some(64),
+            HashingAlgorithm::Null => None,
+        }
+    }
+
+    /// Computes the digest of `data` in software, using OpenSSL.
+    ///
+    /// # Errors
+    /// * if the hashing algorithm is not one of the SHA-1 or SHA-2
+    ///   algorithms, `UnsupportedParam` will be returned.
+    /// * if OpenSSL fails to compute the digest, `InvalidParam` will
+    ///   be returned.
+    #[cfg(feature = "openssl")]
+    pub fn digest(&self, data: &[u8]) -> Result<Digest> {
+        let message_digest = match self {
+            HashingAlgorithm::Sha1 => MessageDigest::sha1(),
+            HashingAlgorithm::Sha256 => MessageDigest::sha256(),
+            HashingAlgorithm::Sha384 => MessageDigest::sha384(),
+            HashingAlgorithm::Sha512 => MessageDigest::sha512(),
+            _ => return Err(Error::local_error(WrapperErrorKind::UnsupportedParam)),
+        };
+        let digest = openssl::hash::hash(message_digest, data).map_err(|e| {
+            log::error!("Failed to compute the {:?} digest: {}", self, e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        Digest::from_bytes(&digest)
+    }
+}
+
 impl From<HashingAlgorithm> for AlgorithmIdentifier {
     fn from(hashing_algorithm: HashingAlgorithm) -> Self {
         match hashing_algorithm {
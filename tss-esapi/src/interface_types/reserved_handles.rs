@@ -5,6 +5,7 @@ use crate::{
         AttachedComponentTpmHandle, AuthHandle, NvIndexHandle, NvIndexTpmHandle, ObjectHandle,
         PermanentTpmHandle, TpmHandle,
     },
+    tss2_esys::ESYS_TR,
     Error, Result, WrapperErrorKind,
 };
 use std::convert::TryFrom;
@@ -32,6 +33,18 @@ impl From<Hierarchy> for ObjectHandle {
     }
 }
 
+impl From<Hierarchy> for AuthHandle {
+    fn from(hierarchy: Hierarchy) -> AuthHandle {
+        ObjectHandle::from(hierarchy).into()
+    }
+}
+
+impl From<Hierarchy> for ESYS_TR {
+    fn from(hierarchy: Hierarchy) -> ESYS_TR {
+        ObjectHandle::from(hierarchy).into()
+    }
+}
+
 impl From<Hierarchy> for TpmHandle {
     fn from(hierarchy: Hierarchy) -> TpmHandle {
         match hierarchy {
@@ -57,6 +70,14 @@ impl TryFrom<ObjectHandle> for Hierarchy {
     }
 }
 
+impl TryFrom<AuthHandle> for Hierarchy {
+    type Error = Error;
+
+    fn try_from(auth_handle: AuthHandle) -> Result<Hierarchy> {
+        Hierarchy::try_from(ObjectHandle::from(auth_handle))
+    }
+}
+
 impl TryFrom<TpmHandle> for Hierarchy {
     type Error = Error;
 
@@ -1,8 +1,11 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    constants::SessionType, handles::SessionHandle, interface_types::algorithm::HashingAlgorithm,
-    Error, Result, WrapperErrorKind,
+    constants::SessionType,
+    handles::{KeyHandle, ObjectHandle, SessionHandle},
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Nonce, SymmetricDefinition},
+    Context, Error, Result, WrapperErrorKind,
 };
 use std::convert::TryFrom;
 
@@ -125,6 +128,11 @@ impl AuthSession {
             None
         }
     }
+
+    /// Get a builder for starting an authorization session
+    pub const fn builder() -> AuthSessionBuilder {
+        AuthSessionBuilder::new()
+    }
 }
 
 impl From<AuthSession> for SessionHandle {
@@ -153,3 +161,100 @@ impl From<Option<AuthSession>> for SessionHandle {
         }
     }
 }
+
+/// A builder for starting an authorization session.
+///
+/// # Details
+/// The default values describe an unbound and unsalted HMAC session
+/// using SHA256 and AES-128-CFB for parameter encryption.
+///
+/// # Example
+/// ```rust
+/// # use tss_esapi::{Context, TctiNameConf};
+/// use tss_esapi::interface_types::session_handles::AuthSession;
+/// # let mut context =
+/// #     Context::new(
+/// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+/// #     ).expect("Failed to create Context");
+/// let session = AuthSession::builder()
+///     .start(&mut context)
+///     .expect("Failed to create session")
+///     .expect("Received invalid handle");
+/// ```
+#[derive(Debug, Clone)]
+pub struct AuthSessionBuilder {
+    tpm_key: Option<KeyHandle>,
+    bind: Option<ObjectHandle>,
+    nonce: Option<Nonce>,
+    session_type: SessionType,
+    symmetric: SymmetricDefinition,
+    auth_hash: HashingAlgorithm,
+}
+
+impl AuthSessionBuilder {
+    /// Creates a new builder with the default values.
+    pub const fn new() -> Self {
+        AuthSessionBuilder {
+            tpm_key: None,
+            bind: None,
+            nonce: None,
+            session_type: SessionType::Hmac,
+            symmetric: SymmetricDefinition::AES_128_CFB,
+            auth_hash: HashingAlgorithm::Sha256,
+        }
+    }
+
+    /// Adds a key that is used to encrypt the salt of the session.
+    pub const fn with_tpm_key(mut self, tpm_key: KeyHandle) -> Self {
+        self.tpm_key = Some(tpm_key);
+        self
+    }
+
+    /// Adds an entity whose authorization value is bound to the session.
+    pub const fn with_bind(mut self, bind: ObjectHandle) -> Self {
+        self.bind = Some(bind);
+        self
+    }
+
+    /// Adds the initial nonce of the caller.
+    pub fn with_nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Adds the type of the session.
+    pub const fn with_session_type(mut self, session_type: SessionType) -> Self {
+        self.session_type = session_type;
+        self
+    }
+
+    /// Adds the symmetric definition used for parameter encryption.
+    pub const fn with_symmetric(mut self, symmetric: SymmetricDefinition) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Adds the hashing algorithm used by the session.
+    pub const fn with_hashing(mut self, auth_hash: HashingAlgorithm) -> Self {
+        self.auth_hash = auth_hash;
+        self
+    }
+
+    /// Starts the session using [Context::start_auth_session].
+    pub fn start(self, context: &mut Context) -> Result<Option<AuthSession>> {
+        context.start_auth_session(
+            self.tpm_key,
+            self.bind,
+            self.nonce,
+            self.session_type,
+            self.symmetric,
+            self.auth_hash,
+        )
+    }
+}
+
+impl Default for AuthSessionBuilder {
+    fn default() -> Self {
+        AuthSessionBuilder::new()
+    }
+}
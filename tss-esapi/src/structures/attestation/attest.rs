@@ -5,9 +5,9 @@ use crate::{
     constants::tss::TPM2_GENERATED_VALUE,
     interface_types::structure_tags::AttestationType,
     structures::{AttestInfo, ClockInfo, Data, Name},
-    traits::impl_mu_standard,
-    tss2_esys::TPMS_ATTEST,
-    Error, Result, WrapperErrorKind,
+    traits::{impl_marshall_trait, UnMarshall},
+    tss2_esys::{Tss2_MU_TPMS_ATTEST_Unmarshal, TPM2_GENERATED, TPMS_ATTEST},
+    Error, Result, ReturnCode, WrapperErrorKind,
 };
 use log::error;
 use std::convert::{TryFrom, TryInto};
@@ -77,6 +77,7 @@ impl TryFrom<TPMS_ATTEST> for Attest {
 
     fn try_from(tpms_attest: TPMS_ATTEST) -> Result<Self> {
         if tpms_attest.magic != TPM2_GENERATED_VALUE {
+            error!("Attest did not contain the expected magic value");
             return Err(Error::local_error(WrapperErrorKind::InvalidParam));
         }
 
@@ -118,4 +119,41 @@ impl TryFrom<TPMS_ATTEST> for Attest {
     }
 }
 
-impl_mu_standard!(Attest, TPMS_ATTEST);
+impl_marshall_trait!(Attest, TPMS_ATTEST, TPMS_ATTEST, into(), &);
+
+impl UnMarshall for Attest {
+    /// Creates an Attest from marshalled data.
+    ///
+    /// # Errors
+    /// * if the magic value is not `TPM2_GENERATED_VALUE` or the type
+    ///   is not a known attestation type, an `InvalidParam` wrapper
+    ///   error is returned.
+    fn unmarshall_offset(marshalled_data: &[u8], offset: &mut usize) -> Result<Self> {
+        // The header is validated before unmarshalling the whole structure
+        // because the attested data is selected by the type.
+        let mut header_offset = *offset;
+        let magic = TPM2_GENERATED::unmarshall_offset(marshalled_data, &mut header_offset)?;
+        if magic != TPM2_GENERATED_VALUE {
+            error!("Marshalled attest did not contain the expected magic value");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        let _ = AttestationType::unmarshall_offset(marshalled_data, &mut header_offset)?;
+
+        let mut tpms_attest = TPMS_ATTEST::default();
+        let ffi_buffer_size = crate::ffi::FfiSizeType::try_from(marshalled_data.len())?;
+        let mut ffi_offset = crate::ffi::FfiSizeType::try_from(*offset)?;
+        ReturnCode::ensure_success(
+            unsafe {
+                Tss2_MU_TPMS_ATTEST_Unmarshal(
+                    marshalled_data.as_ptr(),
+                    ffi_buffer_size.into(),
+                    ffi_offset.as_mut_ptr(),
+                    &mut tpms_attest,
+                )
+            },
+            |ret| error!("Failed to unmarshal Attest: {}", ret),
+        )?;
+        *offset = usize::try_from(ffi_offset)?;
+        Attest::try_from(tpms_attest)
+    }
+}
@@ -4,6 +4,15 @@
 #[allow(unused_macros)]
 macro_rules! named_field_buffer_type {
     ($native_type:ident,$MAX:expr,$tss_type:ident,$buffer_field_name:ident) => {
+        named_field_buffer_type!(
+            $native_type,
+            $MAX,
+            $tss_type,
+            $buffer_field_name,
+            Zeroizing<Vec<u8>>
+        );
+    };
+    ($native_type:ident,$MAX:expr,$tss_type:ident,$buffer_field_name:ident,$storage:ty) => {
         use crate::tss2_esys::$tss_type;
         use crate::{Error, Result, WrapperErrorKind};
         use log::error;
@@ -12,7 +21,7 @@ macro_rules! named_field_buffer_type {
         use zeroize::{Zeroize, Zeroizing};
 
         #[derive(Debug, Clone, PartialEq, Eq, Zeroize)]
-        pub struct $native_type(Zeroizing<Vec<u8>>);
+        pub struct $native_type($storage);
 
         impl Default for $native_type {
             fn default() -> Self {
@@ -96,6 +105,23 @@ macro_rules! buffer_type {
     };
 }
 
+/// Buffer type whose content is redacted when formatted with `Debug`.
+#[allow(unused_macros)]
+macro_rules! sensitive_buffer_type {
+    ($native_type:ident,$MAX:expr,$tss_type:ident) => {
+        named_field_buffer_type!(
+            $native_type,
+            $MAX,
+            $tss_type,
+            buffer,
+            crate::structures::buffers::SensitiveParameter<Zeroizing<Vec<u8>>>
+        );
+    };
+}
+
+mod sensitive_parameter;
+pub(crate) use sensitive_parameter::SensitiveParameter;
+
 pub mod attest;
 pub mod private;
 pub mod public;
@@ -103,7 +129,7 @@ pub mod sensitive;
 pub mod sensitive_create;
 
 pub mod auth {
-    buffer_type!(Auth, 64, TPM2B_AUTH);
+    sensitive_buffer_type!(Auth, 64, TPM2B_AUTH);
 }
 
 pub mod data {
@@ -216,7 +242,11 @@ pub mod ecc_parameter {
 }
 
 pub mod encrypted_secret {
+    use zeroize::ZeroizeOnDrop;
+
     named_field_buffer_type!(EncryptedSecret, 256, TPM2B_ENCRYPTED_SECRET, secret);
+
+    impl ZeroizeOnDrop for EncryptedSecret {}
 }
 
 pub mod id_object {
@@ -234,15 +264,54 @@ pub mod initial_value {
 pub mod max_buffer {
     use crate::tss2_esys::TPM2_MAX_DIGEST_BUFFER;
     buffer_type!(MaxBuffer, TPM2_MAX_DIGEST_BUFFER as usize, TPM2B_MAX_BUFFER);
+
+    impl MaxBuffer {
+        /// Splits `data` into buffers of at most [`MaxBuffer::MAX_SIZE`] bytes.
+        ///
+        /// # Details
+        /// All buffers except the last one are filled completely. Empty
+        /// data yields no buffers.
+        pub fn chunks(data: &[u8]) -> impl Iterator<Item = MaxBuffer> + '_ {
+            data.chunks(Self::MAX_SIZE)
+                .map(|chunk| MaxBuffer(chunk.to_vec().into()))
+        }
+    }
 }
 
 pub mod max_nv_buffer {
     use crate::tss2_esys::TPM2_MAX_NV_BUFFER_SIZE;
+    use zeroize::ZeroizeOnDrop;
     buffer_type!(
         MaxNvBuffer,
         TPM2_MAX_NV_BUFFER_SIZE as usize,
         TPM2B_MAX_NV_BUFFER
     );
+
+    impl ZeroizeOnDrop for MaxNvBuffer {}
+
+    impl MaxNvBuffer {
+        /// Splits `data` into buffers of at most `buffer_max` bytes.
+        ///
+        /// # Details
+        /// `buffer_max` is usually the `NvBufferMax` property of the TPM. It
+        /// is capped to [`MaxNvBuffer::MAX_SIZE`]. All buffers except the
+        /// last one are filled completely. Empty data yields no buffers.
+        ///
+        /// # Errors
+        /// * if `buffer_max` is zero a `WrongParamSize` wrapper error is returned.
+        pub fn chunks(
+            data: &[u8],
+            buffer_max: usize,
+        ) -> Result<impl Iterator<Item = MaxNvBuffer> + '_> {
+            if buffer_max == 0 {
+                error!("Invalid NV buffer size (0)");
+                return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+            }
+            Ok(data
+                .chunks(std::cmp::min(buffer_max, Self::MAX_SIZE))
+                .map(|chunk| MaxNvBuffer(chunk.to_vec().into())))
+        }
+    }
 }
 
 pub mod nonce {
@@ -278,7 +347,10 @@ pub mod private_vendor_specific {
 }
 
 pub mod public_key_rsa {
-    use crate::{interface_types::key_bits::RsaKeyBits, tss2_esys::TPM2_MAX_RSA_KEY_BYTES};
+    use crate::{
+        interface_types::key_bits::RsaKeyBits,
+        tss2_esys::{TPM2_MAX_RSA_KEY_BYTES, TPMI_RSA_KEY_BITS},
+    };
     buffer_type!(
         PublicKeyRsa,
         TPM2_MAX_RSA_KEY_BYTES as usize,
@@ -294,6 +366,37 @@ pub mod public_key_rsa {
                 RsaKeyBits::Rsa4096 => PublicKeyRsa(vec![0u8; 512].into()),
             }
         }
+
+        /// Creates a new PublicKeyRsa from a big endian encoded modulus.
+        ///
+        /// # Details
+        /// The modulus is left padded with zeros to the byte length of
+        /// `rsa_key_bits`. Redundant leading zeros, such as the sign byte
+        /// of an ASN.1 encoded integer, are removed. This means that a
+        /// modulus which has had its leading zero bytes stripped, as is
+        /// common for big number libraries, is restored to its full length.
+        ///
+        /// # Errors
+        /// * if the modulus, without leading zeros, is larger than the
+        ///   byte length of `rsa_key_bits`, a `WrongParamSize` wrapper
+        ///   error is returned.
+        pub fn from_be_bytes_padded(bytes: &[u8], rsa_key_bits: RsaKeyBits) -> Result<Self> {
+            let key_bytes = TPMI_RSA_KEY_BITS::from(rsa_key_bits) as usize / 8;
+            let leading_zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+            let significant_bytes =
+                &bytes[leading_zeros.min(bytes.len().saturating_sub(key_bytes))..];
+            if significant_bytes.len() > key_bytes {
+                error!(
+                    "The modulus is larger than the key size ({} > {})",
+                    significant_bytes.len(),
+                    key_bytes
+                );
+                return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+            }
+            let mut padded = vec![0u8; key_bytes - significant_bytes.len()];
+            padded.extend_from_slice(significant_bytes);
+            Ok(PublicKeyRsa(padded.into()))
+        }
     }
 
     impl TryFrom<PublicKeyRsa> for [u8; 128] {
@@ -362,14 +465,14 @@ pub mod sensitive_data {
     cfg_if::cfg_if! {
         if #[cfg(has_tpmu_sensitive_create)] {
             use crate::tss2_esys::TPMU_SENSITIVE_CREATE;
-            buffer_type!(
+            sensitive_buffer_type!(
                 SensitiveData,
                 ::std::mem::size_of::<TPMU_SENSITIVE_CREATE>(),
                 TPM2B_SENSITIVE_DATA
             );
         } else {
             use crate::tss2_esys::UINT16;
-            buffer_type!(
+            sensitive_buffer_type!(
                 SensitiveData,
                 std::mem::size_of::<TPM2B_SENSITIVE_DATA>() - std::mem::size_of::<UINT16>(),
                 TPM2B_SENSITIVE_DATA
@@ -388,7 +491,11 @@ pub mod symmetric_key {
 }
 
 pub mod timeout {
+    use crate::traits::impl_mu_standard;
+
     buffer_type!(Timeout, 8, TPM2B_TIMEOUT);
+
+    impl_mu_standard!(Timeout, TPM2B_TIMEOUT);
 }
 
 pub mod tpm_context_data {
@@ -1,14 +1,47 @@
 // Copyright 2023 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::traits::impl_mu_standard;
-use crate::traits::{Marshall, UnMarshall};
+use crate::{
+    ffi::{data_zeroize::FfiDataZeroize, FfiSizeType},
+    traits::{impl_unmarshall_trait, Marshall, UnMarshall},
+    tss2_esys::Tss2_MU_TPM2B_PRIVATE_Marshal,
+    ReturnCode,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tss_esapi_sys::_PRIVATE;
+use zeroize::ZeroizeOnDrop;
 
 buffer_type!(Private, ::std::mem::size_of::<_PRIVATE>(), TPM2B_PRIVATE);
 
-impl_mu_standard!(Private, TPM2B_PRIVATE);
+impl ZeroizeOnDrop for Private {}
+
+impl Marshall for Private {
+    const BUFFER_SIZE: usize = std::mem::size_of::<TPM2B_PRIVATE>();
+
+    /// Marshalls the [Private] data, zeroizing the intermediate
+    /// TPM2B_PRIVATE structure once it has been written.
+    fn marshall_offset(&self, marshalled_data: &mut [u8], offset: &mut usize) -> Result<()> {
+        let mut ffi_object = TPM2B_PRIVATE::from(self.clone());
+        let ffi_buffer_size = FfiSizeType::try_from(marshalled_data.len())?;
+        let mut ffi_offset = FfiSizeType::try_from(*offset)?;
+        let return_code = unsafe {
+            Tss2_MU_TPM2B_PRIVATE_Marshal(
+                &ffi_object,
+                marshalled_data.as_mut_ptr(),
+                ffi_buffer_size.into(),
+                ffi_offset.as_mut_ptr(),
+            )
+        };
+        ffi_object.ffi_data_zeroize();
+        ReturnCode::ensure_success(return_code, |ret| {
+            error!("Failed to marshall Private: {}", ret);
+        })?;
+        *offset = usize::try_from(ffi_offset)?;
+        Ok(())
+    }
+}
+
+impl_unmarshall_trait!(Private, TPM2B_PRIVATE, TPM2B_PRIVATE, Private::try_from);
 
 impl Serialize for Private {
     /// Serialise the [Private] data into it's bytes representation of the TCG
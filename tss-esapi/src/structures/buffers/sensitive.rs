@@ -9,6 +9,7 @@ use crate::{
 use log::error;
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
     ops::Deref,
 };
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -18,10 +19,19 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 /// # Details
 /// Corresponds to `TPM2B_SENSITIVE`. The contents of
 /// the buffer can be unmarshalled into a [Sensitive]
-/// structure.
-#[derive(Debug, Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+/// structure. The [Debug] implementation does not print
+/// the contents of the buffer.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
 pub struct SensitiveBuffer(Vec<u8>);
 
+impl fmt::Debug for SensitiveBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SensitiveBuffer")
+            .field(&format_args!("[redacted]"))
+            .finish()
+    }
+}
+
 impl SensitiveBuffer {
     pub const MAX_SIZE: usize = std::mem::size_of::<TPMT_SENSITIVE>();
 
@@ -1,7 +1,7 @@
 // Copyright 2022 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    structures::SensitiveCreate,
+    structures::{buffers::SensitiveParameter, SensitiveCreate},
     traits::{impl_mu_complex, Marshall, UnMarshall},
     tss2_esys::{TPM2B_SENSITIVE_CREATE, TPMS_SENSITIVE_CREATE},
     Error, Result, WrapperErrorKind,
@@ -20,7 +20,7 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 /// form. It can be unmarshalled into [SensitiveCreate] or [TPM2B_SENSITIVE_CREATE].
 /// structure.
 #[derive(Debug, Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
-pub struct SensitiveCreateBuffer(Vec<u8>);
+pub struct SensitiveCreateBuffer(SensitiveParameter<Vec<u8>>);
 
 impl SensitiveCreateBuffer {
     pub const MAX_SIZE: usize = std::mem::size_of::<TPMS_SENSITIVE_CREATE>();
@@ -61,7 +61,7 @@ impl TryFrom<Vec<u8>> for SensitiveCreateBuffer {
 
     fn try_from(bytes: Vec<u8>) -> Result<Self> {
         Self::ensure_valid_buffer_size(bytes.len(), "Vec<u8>")?;
-        Ok(SensitiveCreateBuffer(bytes))
+        Ok(SensitiveCreateBuffer(SensitiveParameter::new(bytes)))
     }
 }
 
@@ -70,7 +70,9 @@ impl TryFrom<&[u8]> for SensitiveCreateBuffer {
 
     fn try_from(bytes: &[u8]) -> Result<Self> {
         Self::ensure_valid_buffer_size(bytes.len(), "&[u8]")?;
-        Ok(SensitiveCreateBuffer(bytes.to_vec()))
+        Ok(SensitiveCreateBuffer(SensitiveParameter::new(
+            bytes.to_vec(),
+        )))
     }
 }
 
@@ -81,7 +83,7 @@ impl TryFrom<TPM2B_SENSITIVE_CREATE> for SensitiveCreateBuffer {
         Self::ensure_valid_buffer_size(tss.size as usize, "buffer")?;
         SensitiveCreate::try_from(tss.sensitive)
             .and_then(|sensitive_create| sensitive_create.marshall())
-            .map(SensitiveCreateBuffer)
+            .map(|bytes| SensitiveCreateBuffer(SensitiveParameter::new(bytes)))
     }
 }
 
@@ -108,7 +110,9 @@ impl TryFrom<SensitiveCreate> for SensitiveCreateBuffer {
     type Error = Error;
 
     fn try_from(sensitve_create: SensitiveCreate) -> Result<SensitiveCreateBuffer> {
-        Ok(SensitiveCreateBuffer(sensitve_create.marshall()?))
+        Ok(SensitiveCreateBuffer(SensitiveParameter::new(
+            sensitve_create.marshall()?,
+        )))
     }
 }
 
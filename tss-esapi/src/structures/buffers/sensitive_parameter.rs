@@ -0,0 +1,46 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::{fmt, ops::Deref};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Wrapper around a value that must never end up in logs.
+///
+/// # Details
+/// The [Debug] implementation prints `[redacted]` instead of the
+/// wrapped value, so that auth values and sensitive data are not
+/// leaked when the structures holding them are formatted.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct SensitiveParameter<T>(T);
+
+impl<T> SensitiveParameter<T> {
+    /// Wraps the sensitive `value`.
+    pub(crate) const fn new(value: T) -> Self {
+        SensitiveParameter(value)
+    }
+}
+
+impl<T> Deref for SensitiveParameter<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for SensitiveParameter<Zeroizing<Vec<u8>>> {
+    fn from(bytes: Vec<u8>) -> Self {
+        SensitiveParameter(bytes.into())
+    }
+}
+
+impl<T> fmt::Debug for SensitiveParameter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl<T: Zeroize> Zeroize for SensitiveParameter<T> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
@@ -1,11 +1,14 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+#[cfg(has_act)]
+use crate::structures::ActDataList;
 use crate::{
     constants::CapabilityType,
     structures::{
         AlgorithmPropertyList, CommandCodeAttributesList, CommandCodeList, EccCurveList,
         HandleList, PcrSelectionList, TaggedPcrPropertyList, TaggedTpmPropertyList,
     },
+    traits::impl_mu_standard,
     tss2_esys::{TPM2_CAP, TPM2_MAX_CAP_BUFFER, TPMS_CAPABILITY_DATA, TPMU_CAPABILITIES},
     Error, Result, WrapperErrorKind,
 };
@@ -19,7 +22,7 @@ use std::mem::size_of;
 /// # Details
 /// This corresponds to `TPMS_CAPABILITY_DATA`
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CapabilityData {
     Algorithms(AlgorithmPropertyList),
     Handles(HandleList),
@@ -30,9 +33,10 @@ pub enum CapabilityData {
     TpmProperties(TaggedTpmPropertyList),
     PcrProperties(TaggedPcrPropertyList),
     EccCurves(EccCurveList),
-    // These are in the TPM TPMU_CAPABILITIES, but are not defined by esapi-2.4.1
+    #[cfg(has_act)]
+    ActData(ActDataList),
+    // This is in the TPM TPMU_CAPABILITIES, but is not defined by esapi-2.4.1
     // AuthPolicies(),
-    // ActData(),
 }
 
 pub const fn max_cap_size<T>() -> usize {
@@ -96,6 +100,13 @@ impl From<CapabilityData> for TPMS_CAPABILITY_DATA {
                     eccCurves: data.into(),
                 },
             },
+            #[cfg(has_act)]
+            CapabilityData::ActData(data) => TPMS_CAPABILITY_DATA {
+                capability: CapabilityType::Act.into(),
+                data: TPMU_CAPABILITIES {
+                    actData: data.into(),
+                },
+            },
         }
     }
 }
@@ -138,10 +149,17 @@ impl TryFrom<TPMS_CAPABILITY_DATA> for CapabilityData {
                 error!("AuthPolicies capability type is currently not supported");
                 Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam))
             }
+            #[cfg(has_act)]
+            CapabilityType::Act => Ok(CapabilityData::ActData(
+                unsafe { tpms_capability_data.data.actData }.try_into()?,
+            )),
+            #[cfg(not(has_act))]
             CapabilityType::Act => {
-                error!("Act capability type is currently not supported");
+                error!("Act capability type is not supported by this version of the TSS");
                 Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam))
             }
         }
     }
 }
+
+impl_mu_standard!(CapabilityData, TPMS_CAPABILITY_DATA);
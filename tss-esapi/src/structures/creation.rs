@@ -6,11 +6,16 @@ use crate::{
     constants::AlgorithmIdentifier,
     interface_types::algorithm::HashingAlgorithm,
     structures::{Data, Digest, Name, PcrSelectionList},
+    traits::impl_mu_standard,
     tss2_esys::{TPM2B_CREATION_DATA, TPMS_CREATION_DATA},
     Error, Result,
 };
 use std::convert::{TryFrom, TryInto};
 
+/// Structure holding the data associated with the creation of an object
+///
+/// # Details
+/// This corresponds to the TPMS_CREATION_DATA
 #[derive(Debug, Clone)]
 pub struct CreationData {
     pcr_select: PcrSelectionList,
@@ -22,6 +27,43 @@ pub struct CreationData {
     outside_info: Data,
 }
 
+impl CreationData {
+    /// Returns the PCRs included in the creation data
+    pub const fn pcr_select(&self) -> &PcrSelectionList {
+        &self.pcr_select
+    }
+
+    /// Returns the digest of the selected PCRs
+    pub const fn pcr_digest(&self) -> &Digest {
+        &self.pcr_digest
+    }
+
+    /// Returns the locality at which the object was created
+    pub const fn locality(&self) -> LocalityAttributes {
+        self.locality
+    }
+
+    /// Returns the name algorithm of the parent
+    pub const fn parent_name_alg(&self) -> Option<HashingAlgorithm> {
+        self.parent_name_alg
+    }
+
+    /// Returns the name of the parent
+    pub const fn parent_name(&self) -> &Name {
+        &self.parent_name
+    }
+
+    /// Returns the qualified name of the parent
+    pub const fn parent_qualified_name(&self) -> &Name {
+        &self.parent_qualified_name
+    }
+
+    /// Returns the outside info provided when creating the object
+    pub const fn outside_info(&self) -> &Data {
+        &self.outside_info
+    }
+}
+
 impl TryFrom<TPMS_CREATION_DATA> for CreationData {
     type Error = Error;
     fn try_from(tss_creation_data: TPMS_CREATION_DATA) -> Result<Self> {
@@ -63,3 +105,5 @@ impl From<CreationData> for TPMS_CREATION_DATA {
         }
     }
 }
+
+impl_mu_standard!(CreationData, TPMS_CREATION_DATA);
@@ -0,0 +1,115 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    interface_types::ecc::EccCurve,
+    structures::{EccParameter, EccPoint, EccScheme, KeyDerivationFunctionScheme},
+    tss2_esys::TPMS_ALGORITHM_DETAIL_ECC,
+    Error, Result,
+};
+use std::convert::{TryFrom, TryInto};
+
+/// Structure holding the parameters of an ECC curve.
+///
+/// # Details
+/// This corresponds to TPMS_ALGORITHM_DETAIL_ECC.
+///
+/// The curve is defined by the equation y^2 = x^3 + ax + b
+/// over the prime field of order p.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgorithmDetailEcc {
+    curve: EccCurve,
+    key_size: u16,
+    kdf: KeyDerivationFunctionScheme,
+    sign: EccScheme,
+    p: EccParameter,
+    a: EccParameter,
+    b: EccParameter,
+    g_x: EccParameter,
+    g_y: EccParameter,
+    n: EccParameter,
+    h: EccParameter,
+}
+
+impl AlgorithmDetailEcc {
+    /// Returns the curve.
+    pub const fn curve(&self) -> EccCurve {
+        self.curve
+    }
+
+    /// Returns the size of the key in bits.
+    pub const fn key_size(&self) -> u16 {
+        self.key_size
+    }
+
+    /// Returns the key derivation function scheme
+    /// required by the curve, if any.
+    pub const fn kdf(&self) -> KeyDerivationFunctionScheme {
+        self.kdf
+    }
+
+    /// Returns the signing scheme required
+    /// by the curve, if any.
+    pub const fn sign(&self) -> EccScheme {
+        self.sign
+    }
+
+    /// Returns the prime of the field.
+    pub const fn p(&self) -> &EccParameter {
+        &self.p
+    }
+
+    /// Returns the coefficient of the linear term.
+    pub const fn a(&self) -> &EccParameter {
+        &self.a
+    }
+
+    /// Returns the constant term.
+    pub const fn b(&self) -> &EccParameter {
+        &self.b
+    }
+
+    /// Returns the x coordinate of the base point.
+    pub const fn g_x(&self) -> &EccParameter {
+        &self.g_x
+    }
+
+    /// Returns the y coordinate of the base point.
+    pub const fn g_y(&self) -> &EccParameter {
+        &self.g_y
+    }
+
+    /// Returns the base point.
+    pub fn generator(&self) -> EccPoint {
+        EccPoint::new(self.g_x.clone(), self.g_y.clone())
+    }
+
+    /// Returns the order of the base point.
+    pub const fn n(&self) -> &EccParameter {
+        &self.n
+    }
+
+    /// Returns the cofactor.
+    pub const fn h(&self) -> &EccParameter {
+        &self.h
+    }
+}
+
+impl TryFrom<TPMS_ALGORITHM_DETAIL_ECC> for AlgorithmDetailEcc {
+    type Error = Error;
+
+    fn try_from(tpms_algorithm_detail_ecc: TPMS_ALGORITHM_DETAIL_ECC) -> Result<Self> {
+        Ok(AlgorithmDetailEcc {
+            curve: EccCurve::try_from(tpms_algorithm_detail_ecc.curveID)?,
+            key_size: tpms_algorithm_detail_ecc.keySize,
+            kdf: tpms_algorithm_detail_ecc.kdf.try_into()?,
+            sign: tpms_algorithm_detail_ecc.sign.try_into()?,
+            p: tpms_algorithm_detail_ecc.p.try_into()?,
+            a: tpms_algorithm_detail_ecc.a.try_into()?,
+            b: tpms_algorithm_detail_ecc.b.try_into()?,
+            g_x: tpms_algorithm_detail_ecc.gX.try_into()?,
+            g_y: tpms_algorithm_detail_ecc.gY.try_into()?,
+            n: tpms_algorithm_detail_ecc.n.try_into()?,
+            h: tpms_algorithm_detail_ecc.h.try_into()?,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Counter returned by the TPM for an ECDAA commit.
+///
+/// # Details
+/// The value is the least significant 16 bits of the
+/// commit count of the TPM. It is used to identify the
+/// commit when a key with the ECDAA scheme is used to sign.
+///
+/// The counter is not persistent: it wraps around after
+/// [CommitCounter::MAX] and the commit values tracked by the TPM
+/// are discarded on a TPM Reset, so a counter that does not
+/// increase from the previous one indicates that commit values
+/// obtained earlier must no longer be used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitCounter(u16);
+
+impl CommitCounter {
+    /// The largest value of the counter.
+    pub const MAX: CommitCounter = CommitCounter(u16::MAX);
+
+    /// Creates a new CommitCounter.
+    pub const fn new(value: u16) -> Self {
+        CommitCounter(value)
+    }
+
+    /// Returns the value of the counter.
+    pub const fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for CommitCounter {
+    fn from(value: u16) -> Self {
+        CommitCounter(value)
+    }
+}
+
+impl From<CommitCounter> for u16 {
+    fn from(commit_counter: CommitCounter) -> Self {
+        commit_counter.0
+    }
+}
@@ -1,3 +1,5 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+pub mod algorithm_detail;
+pub mod commit_counter;
 pub mod point;
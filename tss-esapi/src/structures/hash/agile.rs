@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::interface_types::algorithm::HashingAlgorithm;
 use crate::structures::Digest;
+use crate::traits::impl_mu_complex;
 use crate::tss2_esys::{TPMT_HA, TPMU_HA};
 use crate::{Error, Result, WrapperErrorKind};
 use std::convert::{TryFrom, TryInto};
@@ -16,6 +17,16 @@ impl HashAgile {
     pub fn new(algorithm: HashingAlgorithm, digest: Digest) -> Self {
         HashAgile { algorithm, digest }
     }
+
+    /// Returns the hashing algorithm used to produce the digest.
+    pub const fn algorithm(&self) -> HashingAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the digest.
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
 }
 
 impl TryFrom<HashAgile> for TPMT_HA {
@@ -65,3 +76,5 @@ impl TryFrom<TPMT_HA> for HashAgile {
         })
     }
 }
+
+impl_mu_complex!(HashAgile, TPMT_HA);
@@ -0,0 +1,112 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::constants::AlgorithmIdentifier;
+use crate::tss2_esys::{TPM2_MAX_ALG_LIST_SIZE, TPML_ALG};
+use crate::{Error, Result, WrapperErrorKind};
+use log::{error, warn};
+use std::convert::TryFrom;
+use std::ops::Deref;
+
+/// A list of algorithms
+///
+/// # Details
+/// This corresponds to `TPML_ALG`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlgorithmList {
+    algorithms: Vec<AlgorithmIdentifier>,
+}
+
+impl AlgorithmList {
+    pub const MAX_SIZE: usize = TPM2_MAX_ALG_LIST_SIZE as usize;
+
+    pub fn new() -> Self {
+        AlgorithmList {
+            algorithms: Vec::new(),
+        }
+    }
+
+    /// Adds an algorithm to the list of algorithms.
+    pub fn add(&mut self, algorithm: AlgorithmIdentifier) -> Result<()> {
+        if self.algorithms.len() + 1 > AlgorithmList::MAX_SIZE {
+            error!(
+                "Adding algorithm to list will make the list exceeded its maximum count(> {})",
+                AlgorithmList::MAX_SIZE
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        self.algorithms.push(algorithm);
+        Ok(())
+    }
+
+    /// Returns the inner type.
+    pub fn into_inner(self) -> Vec<AlgorithmIdentifier> {
+        self.algorithms
+    }
+}
+
+impl TryFrom<TPML_ALG> for AlgorithmList {
+    type Error = Error;
+
+    fn try_from(algorithms: TPML_ALG) -> Result<Self> {
+        let algorithm_count = algorithms.count as usize;
+        if algorithm_count > Self::MAX_SIZE {
+            error!("Error: Invalid TPML_ALG count(> {})", Self::MAX_SIZE);
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        // TPMs may report vendor specific algorithms that are not known
+        // by the crate, these are skipped so that the known algorithms
+        // can still be used.
+        let algorithms = algorithms.algorithms[..algorithm_count]
+            .iter()
+            .filter_map(|&alg| {
+                AlgorithmIdentifier::try_from(alg)
+                    .map_err(|_| warn!("Skipping unknown algorithm identifier {:#06X}", alg))
+                    .ok()
+            })
+            .collect();
+        Ok(AlgorithmList { algorithms })
+    }
+}
+
+impl From<AlgorithmList> for TPML_ALG {
+    fn from(algorithms: AlgorithmList) -> Self {
+        let mut tss_algorithms: TPML_ALG = Default::default();
+        for algorithm in algorithms.algorithms {
+            tss_algorithms.algorithms[tss_algorithms.count as usize] = algorithm.into();
+            tss_algorithms.count += 1;
+        }
+        tss_algorithms
+    }
+}
+
+impl TryFrom<Vec<AlgorithmIdentifier>> for AlgorithmList {
+    type Error = Error;
+
+    fn try_from(algorithms: Vec<AlgorithmIdentifier>) -> Result<Self> {
+        if algorithms.len() > Self::MAX_SIZE {
+            error!("Error: Invalid TPML_ALG count(> {})", Self::MAX_SIZE);
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        Ok(AlgorithmList { algorithms })
+    }
+}
+
+impl From<AlgorithmList> for Vec<AlgorithmIdentifier> {
+    fn from(algorithm_list: AlgorithmList) -> Self {
+        algorithm_list.algorithms
+    }
+}
+
+impl AsRef<[AlgorithmIdentifier]> for AlgorithmList {
+    fn as_ref(&self) -> &[AlgorithmIdentifier] {
+        self.algorithms.as_slice()
+    }
+}
+
+impl Deref for AlgorithmList {
+    type Target = Vec<AlgorithmIdentifier>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.algorithms
+    }
+}
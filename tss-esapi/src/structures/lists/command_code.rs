@@ -10,7 +10,7 @@ use log::error;
 use std::{convert::TryFrom, ops::Deref};
 
 /// A list of command codes.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CommandCodeList {
     command_codes: Vec<CommandCode>,
 }
@@ -1,11 +1,19 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::structures::Digest;
+use crate::traits::impl_mu_complex;
 use crate::tss2_esys::TPML_DIGEST;
 use crate::{Error, Result, WrapperErrorKind};
 use log::error;
 use std::convert::TryFrom;
 
+/// A list of digests.
+///
+/// # Details
+/// This corresponds to TPML_DIGEST. The list can hold digests of
+/// different sizes, e.g. the values of PCRs in different banks. Lists
+/// of branches for [policy_or][crate::Context::policy_or] should be
+/// created with [DigestList::policy_or_branches].
 #[derive(Debug, Clone, Default)]
 pub struct DigestList {
     digests: Vec<Digest>,
@@ -13,6 +21,8 @@ pub struct DigestList {
 
 impl DigestList {
     pub const MAX_SIZE: usize = 8;
+    /// The minimum number of branches of a PolicyOR.
+    pub const POLICY_OR_MIN_SIZE: usize = 2;
 
     /// Creates a nnew empty DigestList
     pub const fn new() -> Self {
@@ -37,6 +47,9 @@ impl DigestList {
     }
 
     /// Adds a new digest to the digest list.
+    ///
+    /// # Errors
+    /// * if the list is full, a `WrongParamSize` wrapper error is returned.
     pub fn add(&mut self, dig: Digest) -> Result<()> {
         if self.digests.len() >= DigestList::MAX_SIZE {
             error!("Exceeded maximum count(> {})", DigestList::MAX_SIZE);
@@ -45,6 +58,51 @@ impl DigestList {
         self.digests.push(dig);
         Ok(())
     }
+
+    /// Creates a list of branches for [policy_or][crate::Context::policy_or].
+    ///
+    /// # Details
+    /// The branches are policy digests computed with the same hashing
+    /// algorithm, so they must all have the same size.
+    ///
+    /// # Errors
+    /// * if there are less than [DigestList::POLICY_OR_MIN_SIZE] or more than
+    ///   [DigestList::MAX_SIZE] branches, a `WrongParamSize` wrapper error is returned.
+    /// * if the branches do not all have the same size, an `InconsistentParams`
+    ///   wrapper error is returned.
+    pub fn policy_or_branches(branches: Vec<Digest>) -> Result<Self> {
+        if branches.len() < DigestList::POLICY_OR_MIN_SIZE {
+            error!(
+                "A PolicyOR needs at least {} branches",
+                DigestList::POLICY_OR_MIN_SIZE
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        if let Some(branch) = branches
+            .iter()
+            .find(|branch| branch.len() != branches[0].len())
+        {
+            error!(
+                "PolicyOR branch size {} is not consistent with the size {} of the first branch",
+                branch.len(),
+                branches[0].len()
+            );
+            return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+        }
+        DigestList::try_from(branches)
+    }
+}
+
+impl TryFrom<Vec<Digest>> for DigestList {
+    type Error = Error;
+
+    fn try_from(digests: Vec<Digest>) -> Result<Self> {
+        let mut digest_list = DigestList::new();
+        for digest in digests {
+            digest_list.add(digest)?;
+        }
+        Ok(digest_list)
+    }
 }
 
 impl TryFrom<TPML_DIGEST> for DigestList {
@@ -81,3 +139,5 @@ impl TryFrom<DigestList> for TPML_DIGEST {
         Ok(tss_digest_list)
     }
 }
+
+impl_mu_complex!(DigestList, TPML_DIGEST);
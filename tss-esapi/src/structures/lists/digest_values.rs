@@ -4,7 +4,8 @@ use crate::interface_types::algorithm::HashingAlgorithm;
 use crate::structures::Digest;
 use crate::structures::HashAgile;
 use crate::tss2_esys::TPML_DIGEST_VALUES;
-use crate::{Error, Result};
+use crate::{Error, Result, WrapperErrorKind};
+use log::error;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 
@@ -31,6 +32,17 @@ impl TryFrom<DigestValues> for TPML_DIGEST_VALUES {
         let mut digest_values = digest_values;
         let mut tss_digest_values: TPML_DIGEST_VALUES = Default::default();
         for (digest_hash, digest_val) in digest_values.digests.drain() {
+            if let Some(expected_size) = digest_hash.digest_size() {
+                if digest_val.len() != expected_size {
+                    error!(
+                        "The {:?} digest has {} bytes but {} bytes were expected",
+                        digest_hash,
+                        digest_val.len(),
+                        expected_size
+                    );
+                    return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+                }
+            }
             let ha = HashAgile::new(digest_hash, digest_val);
             tss_digest_values.digests[tss_digest_values.count as usize] = ha.try_into()?;
             tss_digest_values.count += 1;
@@ -3,7 +3,7 @@
 use crate::constants::ecc::EccCurveIdentifier;
 use crate::tss2_esys::{TPM2_ECC_CURVE, TPML_ECC_CURVE};
 use crate::{Error, Result, WrapperErrorKind};
-use log::error;
+use log::{error, warn};
 use std::convert::TryFrom;
 use std::ops::Deref;
 
@@ -59,11 +59,17 @@ impl TryFrom<TPML_ECC_CURVE> for EccCurveList {
             error!("Error: Invalid TPML_ECC_CURVE count(> {})", Self::MAX_SIZE);
             return Err(Error::local_error(WrapperErrorKind::InvalidParam));
         }
-        ecc_curves.eccCurves[..ecc_curve_count]
+        // TPMs may report curves that are not known by the crate,
+        // these are skipped so that the known curves can still be used.
+        let ecc_curves = ecc_curves.eccCurves[..ecc_curve_count]
             .iter()
-            .map(|&cc| EccCurveIdentifier::try_from(cc))
-            .collect::<Result<Vec<EccCurveIdentifier>>>()
-            .map(|ecc_curves| EccCurveList { ecc_curves })
+            .filter_map(|&cc| {
+                EccCurveIdentifier::try_from(cc)
+                    .map_err(|_| warn!("Skipping unknown ECC curve identifier {:#06X}", cc))
+                    .ok()
+            })
+            .collect();
+        Ok(EccCurveList { ecc_curves })
     }
 }
 
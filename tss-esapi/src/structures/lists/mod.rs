@@ -1,5 +1,8 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+#[cfg(has_act)]
+pub mod act_data;
+pub mod algorithm;
 pub mod algorithm_property;
 pub mod command_code;
 pub mod command_code_attributes;
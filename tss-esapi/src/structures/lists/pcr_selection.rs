@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::interface_types::algorithm::HashingAlgorithm;
 use crate::structures::{PcrSelectSize, PcrSelection, PcrSlot};
+use crate::traits::impl_mu_standard;
 use crate::tss2_esys::TPML_PCR_SELECTION;
 use crate::{Error, Result, WrapperErrorKind};
 use log::error;
@@ -124,6 +125,8 @@ impl TryFrom<TPML_PCR_SELECTION> for PcrSelectionList {
     }
 }
 
+impl_mu_standard!(PcrSelectionList, TPML_PCR_SELECTION);
+
 /// A builder for the PcrSelectionList struct.
 #[derive(Debug, Default)]
 pub struct PcrSelectionListBuilder {
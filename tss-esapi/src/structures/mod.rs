@@ -81,6 +81,11 @@ pub mod pcr_select_size {
 /// The lists section
 /////////////////////////////////////////////////////////
 mod lists;
+pub use self::algorithm_list::AlgorithmList;
+pub mod algorithm_list {
+    pub use super::lists::algorithm::*;
+}
+
 pub use self::digest_list::DigestList;
 pub mod digest_list {
     pub use super::lists::digest::*;
@@ -132,6 +137,13 @@ pub mod command_code_attributes_list {
     pub use super::lists::command_code_attributes::*;
 }
 
+#[cfg(has_act)]
+pub use self::act_data_list::ActDataList;
+#[cfg(has_act)]
+pub mod act_data_list {
+    pub use super::lists::act_data::*;
+}
+
 pub(crate) use pcr::slot_collection::PcrSlotCollection;
 /////////////////////////////////////////////////////////
 /// The parameters section
@@ -172,7 +184,9 @@ pub use tagged::{
 /// ECC structures
 /////////////////////////////////////////////////////////
 mod ecc;
-pub use ecc::point::EccPoint;
+pub use ecc::{
+    algorithm_detail::AlgorithmDetailEcc, commit_counter::CommitCounter, point::EccPoint,
+};
 /////////////////////////////////////////////////////////
 /// Signatures structures
 /////////////////////////////////////////////////////////
@@ -197,16 +211,18 @@ pub use clock::{clock_info::ClockInfo, time_info::TimeInfo};
 /// Property Structures
 /////////////////////////////////////////////////////////
 mod property;
+#[cfg(has_act)]
+pub use property::act_data::ActData;
 pub use property::{
-    algorithm_property::AlgorithmProperty, tagged_pcr_select::TaggedPcrSelect,
-    tagged_property::TaggedProperty,
+    algorithm_property::AlgorithmProperty, resource_budget::ResourceBudget,
+    tagged_pcr_select::TaggedPcrSelect, tagged_property::TaggedProperty,
 };
 
 /////////////////////////////////////////////////////////
 /// NV structures
 /////////////////////////////////////////////////////////
 mod nv;
-pub use nv::storage::{NvPublic, NvPublicBuilder};
+pub use nv::storage::{NvPinCounter, NvPublic, NvPublicBuilder};
 /////////////////////////////////////////////////////////
 /// Algorithm Structures
 /////////////////////////////////////////////////////////
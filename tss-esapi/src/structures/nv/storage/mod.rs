@@ -4,6 +4,8 @@
 /// This module contains code that deaals with non volatile storage
 /// in the TPM.
 ///
+mod pin_counter;
 mod public;
 
+pub use pin_counter::NvPinCounter;
 pub use public::{NvPublic, NvPublicBuilder};
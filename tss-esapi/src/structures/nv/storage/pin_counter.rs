@@ -0,0 +1,109 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    structures::MaxNvBuffer, tss2_esys::TPMS_NV_PIN_COUNTER_PARAMETERS, Error, Result,
+    WrapperErrorKind,
+};
+use log::error;
+use std::convert::{TryFrom, TryInto};
+
+/// Representation of the data stored in a PIN pass
+/// or PIN fail NV index.
+///
+/// # Details
+/// Corresponds to `TPMS_NV_PIN_COUNTER_PARAMETERS`.
+///
+/// For a PIN fail index `pin_count` is the number of failed
+/// authorization attempts, for a PIN pass index it is the number
+/// of successful authorization attempts. Authorization using the
+/// index is only possible while `pin_count` is less than `pin_limit`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NvPinCounter {
+    pin_count: u32,
+    pin_limit: u32,
+}
+
+impl NvPinCounter {
+    /// The size of the data in a PIN pass or PIN fail NV index.
+    pub const SIZE: usize = 8;
+
+    /// Creates a new NvPinCounter
+    pub const fn new(pin_count: u32, pin_limit: u32) -> Self {
+        NvPinCounter {
+            pin_count,
+            pin_limit,
+        }
+    }
+
+    /// Returns the PIN count
+    pub const fn pin_count(&self) -> u32 {
+        self.pin_count
+    }
+
+    /// Returns the PIN limit
+    pub const fn pin_limit(&self) -> u32 {
+        self.pin_limit
+    }
+
+    /// Creates a NvPinCounter from the big endian encoded data of an NV index.
+    pub fn from_be_bytes(bytes: [u8; NvPinCounter::SIZE]) -> Self {
+        let (pin_count, pin_limit) = bytes.split_at(4);
+        NvPinCounter {
+            // The slices are four bytes long so this cannot fail.
+            pin_count: u32::from_be_bytes(pin_count.try_into().unwrap()),
+            pin_limit: u32::from_be_bytes(pin_limit.try_into().unwrap()),
+        }
+    }
+
+    /// Returns the big endian encoded data as it is stored in an NV index.
+    pub fn to_be_bytes(&self) -> [u8; NvPinCounter::SIZE] {
+        let mut bytes = [0u8; NvPinCounter::SIZE];
+        bytes[..4].copy_from_slice(&self.pin_count.to_be_bytes());
+        bytes[4..].copy_from_slice(&self.pin_limit.to_be_bytes());
+        bytes
+    }
+}
+
+impl From<TPMS_NV_PIN_COUNTER_PARAMETERS> for NvPinCounter {
+    fn from(tss_nv_pin_counter: TPMS_NV_PIN_COUNTER_PARAMETERS) -> Self {
+        NvPinCounter {
+            pin_count: tss_nv_pin_counter.pinCount,
+            pin_limit: tss_nv_pin_counter.pinLimit,
+        }
+    }
+}
+
+impl From<NvPinCounter> for TPMS_NV_PIN_COUNTER_PARAMETERS {
+    fn from(nv_pin_counter: NvPinCounter) -> Self {
+        TPMS_NV_PIN_COUNTER_PARAMETERS {
+            pinCount: nv_pin_counter.pin_count,
+            pinLimit: nv_pin_counter.pin_limit,
+        }
+    }
+}
+
+impl TryFrom<MaxNvBuffer> for NvPinCounter {
+    type Error = Error;
+
+    fn try_from(max_nv_buffer: MaxNvBuffer) -> Result<Self> {
+        let bytes: [u8; NvPinCounter::SIZE] =
+            max_nv_buffer.as_bytes().try_into().map_err(|_| {
+                error!(
+                    "Invalid NV PIN counter data size ({} != {})",
+                    max_nv_buffer.len(),
+                    NvPinCounter::SIZE
+                );
+                Error::local_error(WrapperErrorKind::WrongParamSize)
+            })?;
+        Ok(NvPinCounter::from_be_bytes(bytes))
+    }
+}
+
+impl TryFrom<NvPinCounter> for MaxNvBuffer {
+    type Error = Error;
+
+    fn try_from(nv_pin_counter: NvPinCounter) -> Result<Self> {
+        MaxNvBuffer::from_bytes(&nv_pin_counter.to_be_bytes())
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     attributes::NvIndexAttributes,
+    constants::NvIndexType,
     handles::NvIndexTpmHandle,
     interface_types::algorithm::HashingAlgorithm,
     structures::Digest,
@@ -95,7 +96,7 @@ impl TryFrom<NvPublic> for TPM2B_NV_PUBLIC {
 /// Builder for NvPublic.
 ///
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct NvPublicBuilder {
     nv_index: Option<NvIndexTpmHandle>,
     name_algorithm: Option<HashingAlgorithm>,
@@ -140,44 +141,78 @@ impl NvPublicBuilder {
         self
     }
 
+    /// Builds the NvPublic.
+    ///
+    /// # Details
+    /// The size of the data area is checked against the requirements
+    /// of the type of the index. The maximum size of the data area is
+    /// specific to the TPM, and it is checked when the index is defined
+    /// with [nv_define_space](crate::Context::nv_define_space).
+    ///
+    /// # Errors
+    /// * if the NV index, name algorithm, attributes or data size are missing,
+    ///   a `ParamsMissing` wrapper error is returned.
+    /// * if the attributes are in conflict with each other, an error is returned
+    ///   as described in [NvIndexAttributes::validate].
+    /// * if the data size is too large, an `InvalidParam` wrapper error is returned.
+    /// * if the data size does not match the size required by the type of the
+    ///   index, an `InconsistentParams` wrapper error is returned.
     pub fn build(self) -> Result<NvPublic> {
         // TODO: Do some clever checking of the values in
         // order to determine some defaults values when
         // some params have not been specified.
         //
 
+        // Nv Index
+        let nv_index = self.nv_index.ok_or_else(|| {
+            error!("No NV index was specified");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        // Hashing algorithm for the name of index
+        let name_algorithm = self.name_algorithm.ok_or_else(|| {
+            error!("No name algorithm was specified");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        // Index attributes
+        let attributes = self.attributes.ok_or_else(|| {
+            error!("No attributes were specified");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        attributes.validate()?;
+        // Size of the data area of the index
+        let data_size = self.data_size.ok_or_else(|| {
+            error!("No data size specified");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        if data_size > std::u16::MAX.into() {
+            error!("data area size is too large (>{})", std::u16::MAX);
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        let required_data_size = match attributes.index_type()? {
+            NvIndexType::Counter
+            | NvIndexType::Bits
+            | NvIndexType::PinFail
+            | NvIndexType::PinPass => Some(8),
+            NvIndexType::Extend => name_algorithm.digest_size(),
+            NvIndexType::Ordinary => None,
+        };
+        if let Some(required_data_size) = required_data_size {
+            if data_size != required_data_size {
+                error!(
+                    "The data area size of the index type must be {} (was {})",
+                    required_data_size, data_size
+                );
+                return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+            }
+        }
+
         Ok(NvPublic {
-            // Nv Index
-            nv_index: self.nv_index.ok_or_else(|| {
-                error!("No NV index was specified");
-                Error::local_error(WrapperErrorKind::ParamsMissing)
-            })?,
-            // Hashing algorithm for the name of index
-            name_algorithm: self.name_algorithm.ok_or_else(|| {
-                error!("No name algorithm was specified");
-                Error::local_error(WrapperErrorKind::ParamsMissing)
-            })?,
-            // Index attributes
-            attributes: self.attributes.ok_or_else(|| {
-                error!("No attributes were specified");
-                Error::local_error(WrapperErrorKind::ParamsMissing)
-            })?,
+            nv_index,
+            name_algorithm,
+            attributes,
             // Index Auth policy
             authorization_policy: self.authorization_policy.unwrap_or_default(),
-            // Size of the data area of the index
-            data_size: self
-                .data_size
-                .ok_or_else(|| {
-                    error!("No data size specified");
-                    Error::local_error(WrapperErrorKind::ParamsMissing)
-                })
-                .and_then(|v| {
-                    if v > std::u16::MAX.into() {
-                        error!("data area size is too large (>{})", std::u16::MAX);
-                        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
-                    }
-                    Ok(v)
-                })?,
+            data_size,
         })
     }
 }
@@ -0,0 +1,66 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    attributes::ActAttributes, handles::PermanentTpmHandle, tss2_esys::TPMS_ACT_DATA, Error, Result,
+};
+use std::convert::TryFrom;
+
+/// Struct representing the state of an authenticated countdown timer.
+///
+/// # Details
+/// This corresponds to TPMS_ACT_DATA
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ActData {
+    handle: PermanentTpmHandle,
+    timeout: u32,
+    attributes: ActAttributes,
+}
+
+impl ActData {
+    /// Creates a new ActData
+    pub const fn new(handle: PermanentTpmHandle, timeout: u32, attributes: ActAttributes) -> Self {
+        ActData {
+            handle,
+            timeout,
+            attributes,
+        }
+    }
+
+    /// Returns the handle of the ACT
+    pub const fn handle(&self) -> PermanentTpmHandle {
+        self.handle
+    }
+
+    /// Returns the number of seconds remaining before the ACT signals
+    pub const fn timeout(&self) -> u32 {
+        self.timeout
+    }
+
+    /// Returns the attributes of the ACT
+    pub const fn attributes(&self) -> ActAttributes {
+        self.attributes
+    }
+}
+
+impl TryFrom<TPMS_ACT_DATA> for ActData {
+    type Error = Error;
+
+    fn try_from(tpms_act_data: TPMS_ACT_DATA) -> Result<Self> {
+        Ok(ActData {
+            handle: PermanentTpmHandle::new(tpms_act_data.handle)?,
+            timeout: tpms_act_data.timeout,
+            attributes: tpms_act_data.attributes.into(),
+        })
+    }
+}
+
+impl From<ActData> for TPMS_ACT_DATA {
+    fn from(act_data: ActData) -> Self {
+        TPMS_ACT_DATA {
+            handle: act_data.handle.into(),
+            timeout: act_data.timeout,
+            attributes: act_data.attributes.into(),
+        }
+    }
+}
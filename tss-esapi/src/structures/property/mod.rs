@@ -1,5 +1,8 @@
 // Copyright 2022 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+#[cfg(has_act)]
+pub mod act_data;
 pub mod algorithm_property;
+pub mod resource_budget;
 pub mod tagged_pcr_select;
 pub mod tagged_property;
@@ -0,0 +1,47 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Struct representing the number of TPM slots that are
+/// still available for transient objects and sessions.
+///
+/// # Details
+/// The values correspond to the `TPM2_PT_HR_TRANSIENT_AVAIL`,
+/// `TPM2_PT_HR_LOADED_AVAIL` and `TPM2_PT_HR_ACTIVE_AVAIL`
+/// properties. They are only an estimate, the TPM may still
+/// run out of memory before they reach zero.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ResourceBudget {
+    transient_available: u32,
+    loaded_sessions_available: u32,
+    active_sessions_available: u32,
+}
+
+impl ResourceBudget {
+    /// Creates a new ResourceBudget
+    pub const fn new(
+        transient_available: u32,
+        loaded_sessions_available: u32,
+        active_sessions_available: u32,
+    ) -> Self {
+        ResourceBudget {
+            transient_available,
+            loaded_sessions_available,
+            active_sessions_available,
+        }
+    }
+
+    /// Returns the number of transient objects that can still be loaded
+    pub const fn transient_available(&self) -> u32 {
+        self.transient_available
+    }
+
+    /// Returns the number of sessions that can still be loaded
+    pub const fn loaded_sessions_available(&self) -> u32 {
+        self.loaded_sessions_available
+    }
+
+    /// Returns the number of sessions that can still be started
+    pub const fn active_sessions_available(&self) -> u32 {
+        self.active_sessions_available
+    }
+}
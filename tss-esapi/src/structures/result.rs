@@ -6,6 +6,9 @@ use crate::{
     structures::{CreationData, CreationTicket, Digest, Private, Public},
 };
 
+#[cfg(feature = "openssl")]
+use crate::{structures::Name, Result};
+
 #[allow(missing_debug_implementations)]
 pub struct CreateKeyResult {
     pub out_private: Private,
@@ -15,7 +18,23 @@ pub struct CreateKeyResult {
     pub creation_ticket: CreationTicket,
 }
 
-#[allow(missing_debug_implementations)]
+impl CreateKeyResult {
+    /// Computes the name of the created object from its public area.
+    ///
+    /// # Details
+    /// The name is the one the TPM will use once the object is loaded,
+    /// so it does not need to be read back using
+    /// [read_public][crate::Context::read_public].
+    ///
+    /// # Errors
+    /// * the errors of [Public::name] are returned.
+    #[cfg(feature = "openssl")]
+    pub fn computed_name(&self) -> Result<Name> {
+        self.out_public.name()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct CreatePrimaryKeyResult {
     pub key_handle: KeyHandle,
     pub out_public: Public,
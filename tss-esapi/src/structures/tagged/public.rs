@@ -6,22 +6,31 @@ pub mod rsa;
 
 use crate::{
     attributes::ObjectAttributes,
-    interface_types::algorithm::{HashingAlgorithm, PublicAlgorithm},
-    structures::{Digest, EccPoint, PublicKeyRsa, SymmetricCipherParameters},
+    interface_types::{
+        algorithm::{AsymmetricAlgorithm, HashingAlgorithm, PublicAlgorithm},
+        ecc::EccCurve,
+        key_bits::RsaKeyBits,
+    },
+    structures::{
+        Digest, EccPoint, PublicKeyRsa, SymmetricCipherParameters, SymmetricDefinitionObject,
+    },
     traits::{impl_mu_standard, Marshall, UnMarshall},
     tss2_esys::{TPM2B_PUBLIC, TPMT_PUBLIC},
     Error, Result, ReturnCode, WrapperErrorKind,
 };
 
-use ecc::PublicEccParameters;
+use ecc::{PublicEccParameters, PublicEccParametersBuilder};
 use keyed_hash::PublicKeyedHashParameters;
-use rsa::PublicRsaParameters;
+use rsa::{PublicRsaParameters, PublicRsaParametersBuilder, RsaExponent};
 
 use log::error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::{TryFrom, TryInto};
 use tss_esapi_sys::{TPMU_PUBLIC_ID, TPMU_PUBLIC_PARMS};
 
+#[cfg(feature = "openssl")]
+use crate::{structures::Name, tss2_esys::TPMI_ALG_HASH};
+
 /// A builder for the [Public] type.
 #[derive(Debug, Clone)]
 pub struct PublicBuilder {
@@ -372,6 +381,28 @@ impl Public {
         }
     }
 
+    /// Computes the name of the object in software.
+    ///
+    /// # Details
+    /// The name is made of the name hashing algorithm followed by
+    /// the digest, using that algorithm, of the marshalled public
+    /// area. It is the same name the TPM returns in
+    /// [read_public][crate::Context::read_public] for the object.
+    ///
+    /// # Errors
+    /// * if the name hashing algorithm cannot be used to compute
+    ///   the digest, the error of [HashingAlgorithm::digest] is returned.
+    #[cfg(feature = "openssl")]
+    pub fn name(&self) -> Result<Name> {
+        let name_hashing_algorithm = self.name_hashing_algorithm();
+        let digest = name_hashing_algorithm.digest(&self.marshall()?)?;
+        let mut name = TPMI_ALG_HASH::from(name_hashing_algorithm)
+            .to_be_bytes()
+            .to_vec();
+        name.extend_from_slice(digest.as_bytes());
+        Name::try_from(name)
+    }
+
     /// Returns the auth policy digest.
     pub fn auth_policy(&self) -> &Digest {
         match self {
@@ -382,6 +413,66 @@ impl Public {
         }
     }
 
+    /// Creates the template of a storage parent key.
+    ///
+    /// # Details
+    /// The template is the one of a restricted decryption key, which
+    /// can be used as the parent of other objects, using RSA 2048 or
+    /// the NIST P-256 curve depending on `asymmetric_algorithm`.
+    /// `symmetric` is the algorithm used by the TPM to protect the
+    /// sensitive areas of the children of the key.
+    ///
+    /// # Errors
+    /// * if `asymmetric_algorithm` is [AsymmetricAlgorithm::Null], an
+    ///   `InvalidParam` wrapper error is returned.
+    /// * if `symmetric` is [SymmetricDefinitionObject::Null], the error
+    ///   of the parameters builder is returned.
+    pub fn storage_parent(
+        asymmetric_algorithm: AsymmetricAlgorithm,
+        name_hashing_algorithm: HashingAlgorithm,
+        symmetric: SymmetricDefinitionObject,
+    ) -> Result<Public> {
+        let object_attributes = ObjectAttributes::builder()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_restricted(true)
+            .build()?;
+        let builder = PublicBuilder::new()
+            .with_name_hashing_algorithm(name_hashing_algorithm)
+            .with_object_attributes(object_attributes);
+        let builder = match asymmetric_algorithm {
+            AsymmetricAlgorithm::Rsa => builder
+                .with_public_algorithm(PublicAlgorithm::Rsa)
+                .with_rsa_parameters(
+                    PublicRsaParametersBuilder::new_restricted_decryption_key(
+                        symmetric,
+                        RsaKeyBits::Rsa2048,
+                        RsaExponent::default(),
+                    )
+                    .build()?,
+                )
+                .with_rsa_unique_identifier(PublicKeyRsa::default()),
+            AsymmetricAlgorithm::Ecc => builder
+                .with_public_algorithm(PublicAlgorithm::Ecc)
+                .with_ecc_parameters(
+                    PublicEccParametersBuilder::new_restricted_decryption_key(
+                        symmetric,
+                        EccCurve::NistP256,
+                    )
+                    .build()?,
+                )
+                .with_ecc_unique_identifier(EccPoint::default()),
+            AsymmetricAlgorithm::Null => {
+                error!("A storage parent key must use either RSA or ECC");
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        };
+        builder.build()
+    }
+
     /// Get a builder for this structure
     pub const fn builder() -> PublicBuilder {
         PublicBuilder::new()
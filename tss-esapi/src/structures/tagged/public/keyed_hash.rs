@@ -18,6 +18,11 @@ impl PublicKeyedHashParameters {
     pub const fn new(keyed_hash_scheme: KeyedHashScheme) -> PublicKeyedHashParameters {
         PublicKeyedHashParameters { keyed_hash_scheme }
     }
+
+    /// Returns the keyed hash scheme
+    pub const fn keyed_hash_scheme(&self) -> KeyedHashScheme {
+        self.keyed_hash_scheme
+    }
 }
 
 impl TryFrom<TPMS_KEYEDHASH_PARMS> for PublicKeyedHashParameters {
@@ -6,6 +6,7 @@ use crate::{
         RsaDecryptAlgorithm, RsaSchemeAlgorithm, SignatureSchemeAlgorithm,
     },
     structures::schemes::{EcDaaScheme, HashScheme, HmacScheme, XorScheme},
+    traits::impl_mu_standard,
     tss2_esys::{
         TPMT_ECC_SCHEME, TPMT_KDF_SCHEME, TPMT_KEYEDHASH_SCHEME, TPMT_RSA_DECRYPT, TPMT_RSA_SCHEME,
         TPMT_SIG_SCHEME, TPMU_ASYM_SCHEME, TPMU_KDF_SCHEME, TPMU_SCHEME_KEYEDHASH, TPMU_SIG_SCHEME,
@@ -27,9 +28,31 @@ pub enum KeyedHashScheme {
 }
 
 impl KeyedHashScheme {
-    pub const HMAC_SHA_256: KeyedHashScheme = KeyedHashScheme::Hmac {
-        hmac_scheme: HmacScheme::new(HashingAlgorithm::Sha256),
-    };
+    pub const HMAC_SHA_256: KeyedHashScheme = KeyedHashScheme::hmac(HashingAlgorithm::Sha256);
+
+    /// Creates a keyed hash scheme for HMAC keys.
+    ///
+    /// # Arguments
+    /// * `hashing_algorithm` - The hashing algorithm used to compute the HMACs.
+    pub const fn hmac(hashing_algorithm: HashingAlgorithm) -> KeyedHashScheme {
+        KeyedHashScheme::Hmac {
+            hmac_scheme: HmacScheme::new(hashing_algorithm),
+        }
+    }
+
+    /// Creates a keyed hash scheme for XOR obfuscation.
+    ///
+    /// # Arguments
+    /// * `hashing_algorithm` - The hashing algorithm used by the key derivation function.
+    /// * `key_derivation_function` - The key derivation function generating the mask.
+    pub const fn xor(
+        hashing_algorithm: HashingAlgorithm,
+        key_derivation_function: KeyDerivationFunction,
+    ) -> KeyedHashScheme {
+        KeyedHashScheme::Xor {
+            xor_scheme: XorScheme::new(hashing_algorithm, key_derivation_function),
+        }
+    }
 }
 
 impl From<KeyedHashScheme> for TPMT_KEYEDHASH_SCHEME {
@@ -70,6 +93,8 @@ impl TryFrom<TPMT_KEYEDHASH_SCHEME> for KeyedHashScheme {
     }
 }
 
+impl_mu_standard!(KeyedHashScheme, TPMT_KEYEDHASH_SCHEME);
+
 /// Enum representing the rsa scheme
 ///
 /// # Details
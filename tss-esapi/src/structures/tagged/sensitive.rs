@@ -8,13 +8,19 @@ use crate::{
     Error, Result, ReturnCode, WrapperErrorKind,
 };
 use log::error;
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
 
 /// Enum describing the Sensitive part of an object.
 ///
 /// # Details
 /// This corresponds to TPMT_SENSITIVE
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// The [Debug] implementation does not print the seed
+/// value nor the sensitive value of the object.
+#[derive(Clone, Eq, PartialEq)]
 pub enum Sensitive {
     Rsa {
         auth_value: Auth,
@@ -76,6 +82,22 @@ impl Sensitive {
     }
 }
 
+impl fmt::Debug for Sensitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Sensitive::Rsa { .. } => "Rsa",
+            Sensitive::Ecc { .. } => "Ecc",
+            Sensitive::Bits { .. } => "Bits",
+            Sensitive::Symmetric { .. } => "Symmetric",
+        };
+        f.debug_struct(name)
+            .field("auth_value", self.auth_value())
+            .field("seed_value", &format_args!("[redacted]"))
+            .field("sensitive", &format_args!("[redacted]"))
+            .finish()
+    }
+}
+
 impl From<Sensitive> for TPMT_SENSITIVE {
     fn from(sensitive: Sensitive) -> Self {
         #[allow(non_snake_case)]
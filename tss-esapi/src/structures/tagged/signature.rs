@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     interface_types::algorithm::SignatureSchemeAlgorithm,
-    structures::{EccSignature, HashAgile, RsaSignature},
+    structures::{EccParameter, EccSignature, HashAgile, PublicKeyRsa, RsaSignature},
     traits::impl_mu_complex,
     tss2_esys::{TPMT_SIGNATURE, TPMU_SIGNATURE},
     Error, Result,
@@ -38,6 +38,26 @@ impl Signature {
             Signature::Null => SignatureSchemeAlgorithm::Null,
         }
     }
+
+    /// Returns the r and s values of the signature, if it is an ECDSA signature.
+    pub fn as_ecdsa(&self) -> Option<(&EccParameter, &EccParameter)> {
+        match self {
+            Signature::EcDsa(ecc_signature) => {
+                Some((ecc_signature.signature_r(), ecc_signature.signature_s()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the signature value, if it is an RSASSA or RSAPSS signature.
+    pub fn as_rsa(&self) -> Option<&PublicKeyRsa> {
+        match self {
+            Signature::RsaSsa(rsa_signature) | Signature::RsaPss(rsa_signature) => {
+                Some(rsa_signature.signature())
+            }
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<Signature> for TPMT_SIGNATURE {
@@ -4,6 +4,7 @@ use crate::{
     constants::StructureTag,
     handles::TpmHandle,
     interface_types::reserved_handles::Hierarchy,
+    traits::impl_mu_complex,
     tss2_esys::{
         TPM2B_DIGEST, TPMT_TK_AUTH, TPMT_TK_CREATION, TPMT_TK_HASHCHECK, TPMT_TK_VERIFIED,
     },
@@ -120,6 +121,8 @@ impl Ticket for AuthTicket {
 
 impl_ticket_try_froms!(AuthTicket, TPMT_TK_AUTH);
 
+impl_mu_complex!(AuthTicket, TPMT_TK_AUTH);
+
 #[derive(Debug, Clone)]
 pub struct HashcheckTicket {
     tag: StructureTag,
@@ -189,7 +192,7 @@ impl Ticket for VerifiedTicket {
 impl_ticket_try_froms!(VerifiedTicket, TPMT_TK_VERIFIED);
 
 /// Rust native wrapper for `TPMT_TK_CREATION` objects.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CreationTicket {
     tag: StructureTag,
     hierarchy: Hierarchy,
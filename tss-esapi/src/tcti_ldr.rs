@@ -5,7 +5,12 @@
 //! See section 3.5 of the TCG TSS 2.0 TPM Command Transmission Interface(TCTI) API
 //! Specification.
 
-use crate::{Error, Result, ReturnCode, WrapperErrorKind};
+#[cfg(feature = "integration-tests")]
+mod custom_tcti;
+
+use crate::{constants::BaseError, ffi::FfiSizeType, Error, Result, ReturnCode, WrapperErrorKind};
+#[cfg(feature = "integration-tests")]
+use custom_tcti::CustomTctiContext;
 use log::error;
 use regex::Regex;
 use std::convert::TryFrom;
@@ -13,10 +18,14 @@ use std::env;
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::net::IpAddr;
+use std::os::raw::{c_int, c_short};
 use std::path::PathBuf;
 use std::ptr::null_mut;
 use std::str::FromStr;
 
+#[cfg(feature = "integration-tests")]
+pub use custom_tcti::CustomTcti;
+
 const DEVICE: &str = "device";
 const MSSIM: &str = "mssim";
 const SWTPM: &str = "swtpm";
@@ -28,6 +37,10 @@ const TABRMD: &str = "tabrmd";
 #[allow(missing_copy_implementations)]
 pub struct TctiContext {
     tcti_context: *mut tss_esapi_sys::TSS2_TCTI_CONTEXT,
+    /// The TCTI implemented in Rust that `tcti_context` points to,
+    /// if the context was not created by the TCTI loader.
+    #[cfg(feature = "integration-tests")]
+    custom_tcti: Option<Box<CustomTctiContext>>,
 }
 
 impl TctiContext {
@@ -46,7 +59,75 @@ impl TctiContext {
             },
         )?;
 
-        Ok(TctiContext { tcti_context })
+        Ok(TctiContext {
+            tcti_context,
+            #[cfg(feature = "integration-tests")]
+            custom_tcti: None,
+        })
+    }
+
+    /// Create a TctiContext forwarding the calls to a TCTI implemented in Rust.
+    ///
+    /// # Details
+    /// This is only available with the `integration-tests` feature, in
+    /// order to mock a TPM in tests. The custom TCTI does not support
+    /// poll handles, cancellation or setting the locality.
+    #[cfg(feature = "integration-tests")]
+    pub fn from_custom(tcti: Box<dyn CustomTcti>) -> Self {
+        let mut custom_tcti = CustomTctiContext::new(tcti);
+        TctiContext {
+            tcti_context: custom_tcti.tcti_context_ptr(),
+            custom_tcti: Some(custom_tcti),
+        }
+    }
+
+    /// Get the handles which can be polled to wait for a response from the TPM.
+    ///
+    /// # Details
+    /// The handles can be registered with an event loop in order to avoid
+    /// blocking while the TPM processes a command.
+    ///
+    /// # Errors
+    /// * if the TCTI does not support polling, an `UnsupportedParam` wrapper
+    ///   error is returned.
+    pub fn poll_handles(&self) -> Result<Vec<PollHandle>> {
+        // All TCTI contexts start with the common structure which holds the function pointers.
+        let get_poll_handles = unsafe {
+            (*(self.tcti_context as *const tss_esapi_sys::TSS2_TCTI_CONTEXT_COMMON_V1))
+                .getPollHandles
+        }
+        .ok_or_else(|| {
+            error!("The TCTI does not support poll handles");
+            Error::local_error(WrapperErrorKind::UnsupportedParam)
+        })?;
+
+        let mut num_handles = FfiSizeType::from(0);
+        ReturnCode::ensure_success(
+            unsafe { get_poll_handles(self.tcti_context, null_mut(), num_handles.as_mut_ptr()) },
+            |ret| {
+                error!("Error when getting the number of poll handles: {}", ret);
+            },
+        )
+        .map_err(poll_handles_error)?;
+
+        let mut handles =
+            vec![tss_esapi_sys::TSS2_TCTI_POLL_HANDLE::default(); usize::try_from(num_handles)?];
+        ReturnCode::ensure_success(
+            unsafe {
+                get_poll_handles(
+                    self.tcti_context,
+                    handles.as_mut_ptr(),
+                    num_handles.as_mut_ptr(),
+                )
+            },
+            |ret| {
+                error!("Error when getting the poll handles: {}", ret);
+            },
+        )
+        .map_err(poll_handles_error)?;
+        handles.truncate(usize::try_from(num_handles)?);
+
+        Ok(handles.into_iter().map(PollHandle::from).collect())
     }
 
     /// Get access to the inner C pointer
@@ -55,8 +136,56 @@ impl TctiContext {
     }
 }
 
+/// Maps the TCTI not implemented error to an `UnsupportedParam` wrapper error.
+fn poll_handles_error(error: Error) -> Error {
+    match error {
+        Error::TssError(ReturnCode::Tcti(tcti_return_code))
+            if tcti_return_code.base_error() == BaseError::NotImplemented =>
+        {
+            error!("The TCTI does not support poll handles");
+            Error::local_error(WrapperErrorKind::UnsupportedParam)
+        }
+        _ => error,
+    }
+}
+
+/// Handle which can be polled to wait for a response from the TPM.
+///
+/// Wrapper around the TSS2_TCTI_POLL_HANDLE structure.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PollHandle {
+    fd: c_int,
+    events: c_short,
+}
+
+impl PollHandle {
+    /// Returns the file descriptor to poll.
+    pub const fn fd(&self) -> c_int {
+        self.fd
+    }
+
+    /// Returns the events to poll the file descriptor for.
+    pub const fn events(&self) -> c_short {
+        self.events
+    }
+}
+
+impl From<tss_esapi_sys::TSS2_TCTI_POLL_HANDLE> for PollHandle {
+    fn from(tss_poll_handle: tss_esapi_sys::TSS2_TCTI_POLL_HANDLE) -> Self {
+        PollHandle {
+            fd: tss_poll_handle.fd,
+            events: tss_poll_handle.events,
+        }
+    }
+}
+
 impl Drop for TctiContext {
     fn drop(&mut self) {
+        // A custom TCTI is freed when its box is dropped.
+        #[cfg(feature = "integration-tests")]
+        if self.custom_tcti.is_some() {
+            return;
+        }
         unsafe {
             tss_esapi_sys::Tss2_TctiLdr_Finalize(&mut self.tcti_context);
         }
@@ -523,6 +652,154 @@ fn validate_from_str_networktpm_config() {
     let _ = TpmSimulatorConfig::from_str("port=,host=,yas").unwrap_err();
 }
 
+/// Configuration for an Mssim TCTI context connecting over TCP
+///
+/// The Microsoft TPM simulator listens on a command port and on a
+/// platform port. The mssim TCTI always uses the port following the
+/// command port as the platform port, so the platform port defaults to
+/// `port + 1`. A different platform port can be set, for example when
+/// starting the simulator, but such a configuration cannot be converted
+/// into a [`TctiNameConf`].
+///
+/// The default configuration will point to `localhost:2321`, with the
+/// platform port `2322`.
+///
+/// Example: `mssim:host=localhost,port=2321`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MssimConfig {
+    host: ServerAddress,
+    port: u16,
+    platform_port: u16,
+}
+
+impl MssimConfig {
+    /// Creates a new MssimConfig with the platform port set to `port + 1`.
+    ///
+    /// # Errors
+    /// * if `port` is `u16::MAX`, an `InvalidParam` wrapper error is returned
+    ///   as there is no port following it.
+    pub fn new(host: ServerAddress, port: u16) -> Result<Self> {
+        let platform_port = port.checked_add(1).ok_or_else(|| {
+            error!("No platform port follows the command port {}", port);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        Ok(MssimConfig {
+            host,
+            port,
+            platform_port,
+        })
+    }
+
+    /// Sets the platform port explicitly.
+    pub fn with_platform_port(mut self, platform_port: u16) -> Self {
+        self.platform_port = platform_port;
+        self
+    }
+
+    /// Returns the address of the server.
+    pub fn host(&self) -> &ServerAddress {
+        &self.host
+    }
+
+    /// Returns the command port.
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the platform port.
+    pub const fn platform_port(&self) -> u16 {
+        self.platform_port
+    }
+}
+
+impl Default for MssimConfig {
+    fn default() -> Self {
+        MssimConfig {
+            host: Default::default(),
+            port: TpmSimulatorConfig::DEFAULT_PORT_CONFIG,
+            platform_port: TpmSimulatorConfig::DEFAULT_PORT_CONFIG + 1,
+        }
+    }
+}
+
+impl FromStr for MssimConfig {
+    type Err = Error;
+
+    /// Parses either a full TCTI name and configuration, e.g.
+    /// `mssim:host=localhost,port=2321`, or only the configuration part.
+    fn from_str(config_str: &str) -> Result<Self> {
+        let config_str = config_str
+            .strip_prefix(MSSIM)
+            .map(|conf| conf.strip_prefix(':').unwrap_or(conf))
+            .unwrap_or(config_str);
+        match TpmSimulatorConfig::from_str(config_str)? {
+            TpmSimulatorConfig::Tcp { host, port } => MssimConfig::new(host, port),
+            TpmSimulatorConfig::Unix { .. } => {
+                error!("A unix socket path cannot be used in an MssimConfig");
+                Err(Error::local_error(WrapperErrorKind::InvalidParam))
+            }
+        }
+    }
+}
+
+impl TryFrom<MssimConfig> for TctiNameConf {
+    type Error = Error;
+
+    fn try_from(mssim_config: MssimConfig) -> Result<Self> {
+        if Some(mssim_config.platform_port) != mssim_config.port.checked_add(1) {
+            error!(
+                "The mssim TCTI does not support the platform port {} with the command port {}",
+                mssim_config.platform_port, mssim_config.port
+            );
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        Ok(TctiNameConf::Mssim(TpmSimulatorConfig::Tcp {
+            host: mssim_config.host,
+            port: mssim_config.port,
+        }))
+    }
+}
+
+#[test]
+fn validate_mssim_config() {
+    let config = MssimConfig::from_str("").unwrap();
+    assert_eq!(config, Default::default());
+    assert_eq!(config.platform_port(), 2322);
+
+    let config = MssimConfig::from_str("mssim:host=127.0.0.1,port=4321").unwrap();
+    assert_eq!(
+        config,
+        MssimConfig::new(
+            ServerAddress::Ip(IpAddr::V4(std::net::Ipv4Addr::new(127, 0, 0, 1))),
+            4321
+        )
+        .unwrap()
+    );
+    assert_eq!(config.platform_port(), 4322);
+    assert_eq!(
+        config,
+        MssimConfig::from_str("host=127.0.0.1,port=4321").unwrap()
+    );
+
+    let tcti_conf = CString::try_from(TctiNameConf::try_from(config.clone()).unwrap()).unwrap();
+    assert_eq!(
+        tcti_conf.to_str().unwrap(),
+        "mssim:host=127.0.0.1,port=4321"
+    );
+    assert_eq!(
+        config,
+        MssimConfig::from_str(tcti_conf.to_str().unwrap()).unwrap()
+    );
+
+    let config = config.with_platform_port(5000);
+    assert_eq!(config.platform_port(), 5000);
+    let _ = TctiNameConf::try_from(config).unwrap_err();
+
+    let _ = MssimConfig::new(Default::default(), u16::MAX).unwrap_err();
+    let _ = MssimConfig::from_str("mssim:path=/foo/bar").unwrap_err();
+    let _ = MssimConfig::from_str("mssim:port=abdef").unwrap_err();
+}
+
 /// Address of a TPM server
 ///
 /// The default value is `localhost`
@@ -0,0 +1,176 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::tss::{
+        TSS2_BASE_RC_GENERAL_FAILURE, TSS2_BASE_RC_INSUFFICIENT_BUFFER, TSS2_BASE_RC_IO_ERROR,
+        TSS2_BASE_RC_TRY_AGAIN, TSS2_RC_SUCCESS, TSS2_TCTI_RC_LAYER,
+    },
+    ffi::FfiSizeType,
+    Result,
+};
+use log::error;
+use std::convert::TryFrom;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+use std::time::Duration;
+use tss_esapi_sys::{
+    size_t, TSS2_RC, TSS2_TCTI_CONTEXT, TSS2_TCTI_CONTEXT_COMMON_V1, TSS2_TCTI_TIMEOUT_BLOCK,
+};
+
+/// Magic value identifying a TCTI implemented by a [CustomTcti].
+const CUSTOM_TCTI_MAGIC: u64 = 0x7473_735f_6375_7374;
+
+/// Trait for TCTIs implemented in Rust.
+///
+/// # Details
+/// A custom TCTI is used to mock a TPM in tests. It is turned into
+/// a [TctiContext](super::TctiContext) using
+/// [TctiContext::from_custom](super::TctiContext::from_custom).
+///
+/// If one of its methods panics, the panic is caught and a general
+/// failure is reported to ESYS.
+pub trait CustomTcti: Send {
+    /// Transmits a marshalled command to the TPM.
+    fn transmit(&mut self, command: &[u8]) -> Result<()>;
+
+    /// Receives the marshalled response to the last command.
+    ///
+    /// # Details
+    /// If `timeout` is `None` the call blocks until the response is
+    /// available. Otherwise `None` is returned if the response is not
+    /// available once the timeout has elapsed.
+    fn receive(&mut self, timeout: Option<Duration>) -> Result<Option<Vec<u8>>>;
+}
+
+/// TCTI context forwarding the calls to a [CustomTcti].
+#[repr(C)]
+pub struct CustomTctiContext {
+    // Must be the first field, the TCTI functions are
+    // looked up through a pointer to the structure.
+    common: TSS2_TCTI_CONTEXT_COMMON_V1,
+    tcti: Box<dyn CustomTcti>,
+    response: Option<Vec<u8>>,
+}
+
+impl CustomTctiContext {
+    /// Creates a new CustomTctiContext forwarding the calls to `tcti`.
+    pub fn new(tcti: Box<dyn CustomTcti>) -> Box<CustomTctiContext> {
+        Box::new(CustomTctiContext {
+            common: TSS2_TCTI_CONTEXT_COMMON_V1 {
+                magic: CUSTOM_TCTI_MAGIC,
+                version: 1,
+                transmit: Some(transmit),
+                receive: Some(receive),
+                // The custom TCTI is dropped with the TctiContext.
+                finalize: None,
+                cancel: None,
+                getPollHandles: None,
+                setLocality: None,
+            },
+            tcti,
+            response: None,
+        })
+    }
+
+    /// Get access to the C pointer given to ESYS.
+    pub fn tcti_context_ptr(&mut self) -> *mut TSS2_TCTI_CONTEXT {
+        (self as *mut CustomTctiContext).cast()
+    }
+}
+
+impl fmt::Debug for CustomTctiContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomTctiContext")
+            .field("response", &self.response)
+            .finish()
+    }
+}
+
+/// Returns the CustomTctiContext behind a TCTI context pointer given by ESYS.
+///
+/// # Safety
+/// The pointer must have been created by [CustomTctiContext::tcti_context_ptr].
+unsafe fn custom_tcti<'a>(tcti_context: *mut TSS2_TCTI_CONTEXT) -> &'a mut CustomTctiContext {
+    &mut *tcti_context.cast::<CustomTctiContext>()
+}
+
+const fn tcti_error(base_error: u32) -> TSS2_RC {
+    TSS2_TCTI_RC_LAYER | base_error
+}
+
+unsafe extern "C" fn transmit(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    size: size_t,
+    command: *const u8,
+) -> TSS2_RC {
+    let tcti = custom_tcti(tcti_context);
+    let size = match usize::try_from(FfiSizeType::from(size)) {
+        Ok(size) => size,
+        Err(_) => return tcti_error(TSS2_BASE_RC_IO_ERROR),
+    };
+    tcti.response = None;
+    let command = slice::from_raw_parts(command, size);
+    // Unwinding out of the TCTI callback would abort the process.
+    match catch_unwind(AssertUnwindSafe(|| tcti.tcti.transmit(command))) {
+        Ok(Ok(())) => TSS2_RC_SUCCESS,
+        Ok(Err(e)) => {
+            error!("The custom TCTI failed to transmit the command: {}", e);
+            tcti_error(TSS2_BASE_RC_IO_ERROR)
+        }
+        Err(_) => {
+            error!("The custom TCTI panicked while transmitting the command");
+            tcti_error(TSS2_BASE_RC_GENERAL_FAILURE)
+        }
+    }
+}
+
+unsafe extern "C" fn receive(
+    tcti_context: *mut TSS2_TCTI_CONTEXT,
+    size: *mut size_t,
+    response: *mut u8,
+    timeout: i32,
+) -> TSS2_RC {
+    let tcti = custom_tcti(tcti_context);
+    if tcti.response.is_none() {
+        let timeout = match timeout {
+            TSS2_TCTI_TIMEOUT_BLOCK => None,
+            timeout => Some(Duration::from_millis(u64::try_from(timeout).unwrap_or(0))),
+        };
+        // Unwinding out of the TCTI callback would abort the process.
+        match catch_unwind(AssertUnwindSafe(|| tcti.tcti.receive(timeout))) {
+            Ok(Ok(Some(received))) => tcti.response = Some(received),
+            Ok(Ok(None)) => return tcti_error(TSS2_BASE_RC_TRY_AGAIN),
+            Ok(Err(e)) => {
+                error!("The custom TCTI failed to receive the response: {}", e);
+                return tcti_error(TSS2_BASE_RC_IO_ERROR);
+            }
+            Err(_) => {
+                error!("The custom TCTI panicked while receiving the response");
+                return tcti_error(TSS2_BASE_RC_GENERAL_FAILURE);
+            }
+        }
+    }
+    let response_size = tcti.response.as_ref().map_or(0, Vec::len);
+    let capacity = match usize::try_from(FfiSizeType::from(*size)) {
+        Ok(capacity) => capacity,
+        Err(_) => return tcti_error(TSS2_BASE_RC_IO_ERROR),
+    };
+    let ffi_response_size = match FfiSizeType::try_from(response_size) {
+        Ok(ffi_response_size) => ffi_response_size,
+        Err(_) => return tcti_error(TSS2_BASE_RC_IO_ERROR),
+    };
+    // Without a buffer only the size of the response is queried.
+    if response.is_null() {
+        *size = ffi_response_size.into();
+        return TSS2_RC_SUCCESS;
+    }
+    if capacity < response_size {
+        return tcti_error(TSS2_BASE_RC_INSUFFICIENT_BUFFER);
+    }
+    if let Some(received) = tcti.response.take() {
+        slice::from_raw_parts_mut(response, response_size).copy_from_slice(&received);
+    }
+    *size = ffi_response_size.into();
+    TSS2_RC_SUCCESS
+}
@@ -43,8 +43,13 @@ tpm2_startup -c -T mssim
 # Build the crate #
 ###################
 RUST_BACKTRACE=1 cargo build --features "$FEATURES"
+RUST_BACKTRACE=1 cargo build --no-default-features --features "$FEATURES"
 
 #################
 # Run the tests #
 #################
 TEST_TCTI=mssim: RUST_BACKTRACE=1 RUST_LOG=info cargo test --features "${FEATURES}" -- --test-threads=1 --nocapture
+TEST_TCTI=mssim: RUST_BACKTRACE=1 RUST_LOG=info cargo test --no-default-features --features "${FEATURES}" -- --test-threads=1 --nocapture
+TEST_TCTI=mssim: RUST_BACKTRACE=1 RUST_LOG=info cargo test --features "${FEATURES} openssl" -- --test-threads=1 --nocapture
+TEST_TCTI=mssim: RUST_BACKTRACE=1 RUST_LOG=info cargo test --features "${FEATURES} rand" -- --test-threads=1 --nocapture
+TEST_TCTI=mssim: RUST_BACKTRACE=1 RUST_LOG=info cargo test --features "${FEATURES} serde_json" -- --test-threads=1 --nocapture
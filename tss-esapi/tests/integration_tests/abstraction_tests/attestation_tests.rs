@@ -0,0 +1,64 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    abstraction::{
+        attestation::{self, AkTemplate},
+        AsymmetricAlgorithmSelection,
+    },
+    interface_types::{
+        algorithm::{HashingAlgorithm, SignatureSchemeAlgorithm},
+        key_bits::RsaKeyBits,
+        structure_tags::AttestationType,
+    },
+    structures::{AttestInfo, Data, PcrSelectionListBuilder, PcrSlot, SignatureScheme},
+};
+
+use crate::common::create_ctx_without_session;
+
+#[test]
+fn test_provision_ak_and_quote() {
+    let mut context = create_ctx_without_session();
+
+    let provisioned = attestation::provision_ak(
+        &mut context,
+        AsymmetricAlgorithmSelection::Rsa(RsaKeyBits::Rsa2048),
+        AkTemplate {
+            hash_alg: HashingAlgorithm::Sha256,
+            key_alg: AsymmetricAlgorithmSelection::Rsa(RsaKeyBits::Rsa2048),
+            sign_alg: SignatureSchemeAlgorithm::RsaSsa,
+        },
+        None,
+    )
+    .expect("Failed to provision AK");
+
+    let (_, ak_name, _) = context
+        .read_public(provisioned.ak_handle)
+        .expect("Failed to read the AK public");
+    assert_eq!(ak_name, provisioned.ak_name);
+
+    let pcr_selection_list = PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0])
+        .build()
+        .expect("Failed to create PcrSelectionList");
+    let qualifying_data = vec![0xff; 16];
+    let (attest, _signature) = context
+        .execute_with_nullauth_session(|ctx| {
+            ctx.quote(
+                provisioned.ak_handle,
+                Data::try_from(qualifying_data.clone()).unwrap(),
+                SignatureScheme::Null,
+                pcr_selection_list.clone(),
+            )
+        })
+        .expect("Failed to get a quote");
+
+    assert_eq!(attest.attestation_type(), AttestationType::Quote);
+    assert_eq!(attest.extra_data().as_bytes(), qualifying_data);
+    match attest.attested() {
+        AttestInfo::Quote { info } => {
+            assert_eq!(info.pcr_selection(), &pcr_selection_list);
+        }
+        _ => panic!("Attested did not contain the expected variant."),
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::create_ctx_with_session;
+use std::convert::TryFrom;
+use tss_esapi::{
+    abstraction::ecdaa,
+    attributes::ObjectAttributesBuilder,
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm},
+        ecc::EccCurve,
+        reserved_handles::Hierarchy,
+    },
+    structures::{
+        Digest, EcDaaScheme, EccPoint, EccScheme, KeyDerivationFunctionScheme, Public,
+        PublicBuilder, PublicEccParametersBuilder, Signature,
+    },
+};
+
+fn ecdaa_key_pub() -> Public {
+    let ecc_parameters = PublicEccParametersBuilder::new()
+        .with_ecc_scheme(EccScheme::EcDaa(EcDaaScheme::new(
+            HashingAlgorithm::Sha256,
+            0,
+        )))
+        .with_curve(EccCurve::BnP256)
+        .with_is_signing_key(true)
+        .with_is_decryption_key(false)
+        .with_restricted(false)
+        .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+        .build()
+        .expect("Failed to build ECC parameters");
+
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .expect("Failed to build object attributes");
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_ecc_parameters(ecc_parameters)
+        .with_ecc_unique_identifier(EccPoint::default())
+        .build()
+        .expect("Failed to build public")
+}
+
+#[test]
+fn test_sign_ecdaa() {
+    let mut context = create_ctx_with_session();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, ecdaa_key_pub(), None, None, None, None)
+        .expect("Failed to create ECDAA key")
+        .key_handle;
+    let message_digest = Digest::try_from(vec![0xab; 32]).expect("Failed to create digest");
+
+    let signatures = (0..2)
+        .map(|_| {
+            ecdaa::sign_ecdaa(
+                &mut context,
+                key_handle,
+                HashingAlgorithm::Sha256,
+                message_digest.clone(),
+            )
+            .expect("Failed to sign with the ECDAA key")
+        })
+        .collect::<Vec<Signature>>();
+
+    for signature in &signatures {
+        match signature {
+            Signature::EcDaa(ecc_signature) => {
+                assert_eq!(ecc_signature.hashing_algorithm(), HashingAlgorithm::Sha256);
+                assert!(!ecc_signature.signature_r().is_empty());
+                assert!(!ecc_signature.signature_s().is_empty());
+                assert!(ecc_signature.signature_s().len() <= 32);
+            }
+            _ => panic!("Expected an ECDAA signature, got {:?}", signature),
+        }
+    }
+    // Every signature uses a new commit.
+    assert_ne!(signatures[0], signatures[1]);
+}
@@ -1,9 +1,15 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 mod ak_tests;
+mod attestation_tests;
+mod ecdaa_tests;
 mod ek_tests;
 mod nv_tests;
 mod pcr_data_tests;
 mod pcr_tests;
+mod policy_tests;
+mod primary_tests;
 mod public_tests;
+mod rng_tests;
+mod signing_tests;
 mod transient_key_context_tests;
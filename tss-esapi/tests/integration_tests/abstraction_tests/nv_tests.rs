@@ -1,16 +1,21 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::io::{ErrorKind, Seek, SeekFrom, Write};
+use std::{
+    convert::TryFrom,
+    io::{ErrorKind, Seek, SeekFrom, Write},
+};
 use tss_esapi::{
     abstraction::nv,
     attributes::NvIndexAttributesBuilder,
+    constants::NvIndexType,
     handles::{NvIndexHandle, NvIndexTpmHandle},
     interface_types::{
         algorithm::HashingAlgorithm,
         reserved_handles::{NvAuth, Provision},
     },
-    structures::NvPublicBuilder,
+    structures::{Auth, MaxNvBuffer, NvPinCounter, NvPublic, NvPublicBuilder},
+    Error, WrapperErrorKind,
 };
 
 use crate::common::{create_ctx_with_session, write_nv_index};
@@ -83,6 +88,91 @@ fn read_full() {
     assert_eq!(read_result[1024..1031], [1, 2, 3, 4, 5, 6, 7]);
 }
 
+#[test]
+fn read_full_not_written() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500016).unwrap();
+
+    let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+    let owner_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(owner_nv_index_attributes)
+        .with_data_area_size(32)
+        .build()
+        .unwrap();
+    let initial_owner_nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, owner_nv_public)
+        .expect("Call to nv_define_space failed");
+
+    let read_result = nv::read_full(&mut context, NvAuth::Owner, nv_index);
+
+    let owner_nv_index_handle = context
+        .tr_from_tpm_public(nv_index.into())
+        .map_or_else(|_| initial_owner_nv_index_handle, NvIndexHandle::from);
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert_eq!(
+        read_result,
+        Err(Error::WrapperError(WrapperErrorKind::NvIndexNotWritten))
+    );
+}
+
+#[test]
+fn read_full_partially_written() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500017).unwrap();
+
+    let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+    let owner_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(owner_nv_index_attributes)
+        .with_data_area_size(32)
+        .build()
+        .unwrap();
+    let initial_owner_nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, owner_nv_public)
+        .expect("Call to nv_define_space failed");
+
+    // Only write the first half of the index.
+    let value = [1u8; 16];
+    let write_result = context.nv_write(
+        NvAuth::Owner,
+        initial_owner_nv_index_handle,
+        MaxNvBuffer::try_from(value.to_vec()).unwrap(),
+        0,
+    );
+
+    let read_result =
+        write_result.and_then(|_| nv::read_full(&mut context, NvAuth::Owner, nv_index));
+
+    let owner_nv_index_handle = context
+        .tr_from_tpm_public(nv_index.into())
+        .map_or_else(|_| initial_owner_nv_index_handle, NvIndexHandle::from);
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    // The index counts as written, and the whole data area is read
+    // back, not only the part that has been written.
+    let read_result = read_result.expect("Call to read_full failed");
+    assert_eq!(read_result.len(), 32);
+    assert_eq!(read_result[0..16], value);
+}
+
 #[test]
 fn write() {
     let mut context = create_ctx_with_session();
@@ -136,3 +226,264 @@ fn write() {
         .nv_undefine_space(Provision::Owner, owner_nv_index_handle.into())
         .expect("Call to nv_undefine_space failed");
 }
+
+#[test]
+fn pin_fail_index() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x0150002D).unwrap();
+
+    let pin_fail_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_nv_index_type(NvIndexType::PinFail)
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .with_auth_read(true)
+        .with_no_da(true)
+        .build()
+        .expect("Failed to create pin fail nv index attributes");
+    let pin_fail_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(pin_fail_nv_index_attributes)
+        .with_data_area_size(NvPinCounter::SIZE)
+        .build()
+        .unwrap();
+    let pin = Auth::try_from(vec![1, 2, 3, 4]).unwrap();
+    let nv_index_handle = nv::define_pin_index(
+        &mut context,
+        Provision::Owner,
+        Some(pin.clone()),
+        pin_fail_nv_public,
+        NvPinCounter::new(0, 5),
+    )
+    .expect("Call to nv::define_pin_index failed");
+
+    let initial_pin_counter = nv::read_pin_counter(&mut context, NvAuth::Owner, nv_index_handle);
+    // A successful authorization does not change the PIN count.
+    let correct_pin_read_result = nv::read_pin_counter(
+        &mut context,
+        NvAuth::NvIndex(nv_index_handle),
+        nv_index_handle,
+    );
+    // A failed authorization increments the PIN count.
+    let wrong_pin_read_result = context
+        .tr_set_auth(
+            nv_index_handle.into(),
+            Auth::try_from(vec![4, 3, 2, 1]).unwrap(),
+        )
+        .and_then(|_| {
+            nv::read_pin_counter(
+                &mut context,
+                NvAuth::NvIndex(nv_index_handle),
+                nv_index_handle,
+            )
+        });
+    let final_pin_counter = nv::read_pin_counter(&mut context, NvAuth::Owner, nv_index_handle);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert_eq!(
+        initial_pin_counter.expect("Failed to read initial pin counter"),
+        NvPinCounter::new(0, 5)
+    );
+    assert_eq!(
+        correct_pin_read_result.expect("Failed to read using the correct pin"),
+        NvPinCounter::new(0, 5)
+    );
+    assert!(wrong_pin_read_result.is_err());
+    assert_eq!(
+        final_pin_counter.expect("Failed to read final pin counter"),
+        NvPinCounter::new(1, 5)
+    );
+}
+
+#[test]
+fn define_pin_index_with_ordinary_index() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create nv index attributes");
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(NvIndexTpmHandle::new(0x0150002E).unwrap())
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(nv_index_attributes)
+        .with_data_area_size(NvPinCounter::SIZE)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        nv::define_pin_index(
+            &mut context,
+            Provision::Owner,
+            None,
+            nv_public,
+            NvPinCounter::new(0, 5),
+        ),
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam))
+    );
+}
+
+fn write_auto_nv_public(nv_index: u32, attributes_builder: NvIndexAttributesBuilder) -> NvPublic {
+    NvPublicBuilder::new()
+        .with_nv_index(NvIndexTpmHandle::new(nv_index).unwrap())
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(
+            attributes_builder
+                .build()
+                .expect("Failed to create nv index attributes"),
+        )
+        .with_data_area_size(4)
+        .build()
+        .expect("Failed to build nv public")
+}
+
+#[test]
+fn write_auto_owner_write() {
+    let mut context = create_ctx_with_session();
+    let nv_public = write_auto_nv_public(
+        0x01500037,
+        NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true),
+    );
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, nv_public)
+        .expect("Call to nv_define_space failed");
+
+    let data = MaxNvBuffer::try_from(vec![1, 2, 3, 4]).unwrap();
+    let write_result = nv::write_auto(&mut context, nv_index_handle, data.clone());
+    let read_result = context.nv_read(NvAuth::Owner, nv_index_handle, 4, 0);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    write_result.expect("Call to nv::write_auto failed");
+    assert_eq!(read_result.expect("Call to nv_read failed"), data);
+}
+
+#[test]
+fn write_auto_auth_write() {
+    let mut context = create_ctx_with_session();
+    let nv_public = write_auto_nv_public(
+        0x01500038,
+        NvIndexAttributesBuilder::new()
+            .with_auth_write(true)
+            .with_auth_read(true),
+    );
+    let nv_index_handle = context
+        .nv_define_space(
+            Provision::Owner,
+            Some(Auth::try_from(vec![5, 6, 7, 8]).unwrap()),
+            nv_public,
+        )
+        .expect("Call to nv_define_space failed");
+
+    let data = MaxNvBuffer::try_from(vec![4, 3, 2, 1]).unwrap();
+    let write_result = nv::write_auto(&mut context, nv_index_handle, data.clone());
+    let read_result = context.nv_read(NvAuth::NvIndex(nv_index_handle), nv_index_handle, 4, 0);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    write_result.expect("Call to nv::write_auto failed");
+    assert_eq!(read_result.expect("Call to nv_read failed"), data);
+}
+
+#[test]
+fn write_auto_policy_write_without_policy_session() {
+    let mut context = create_ctx_with_session();
+    let nv_public = write_auto_nv_public(
+        0x01500039,
+        NvIndexAttributesBuilder::new()
+            .with_policy_write(true)
+            .with_owner_read(true),
+    );
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, nv_public)
+        .expect("Call to nv_define_space failed");
+
+    let write_result = nv::write_auto(
+        &mut context,
+        nv_index_handle,
+        MaxNvBuffer::try_from(vec![1, 2, 3, 4]).unwrap(),
+    );
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert_eq!(
+        write_result,
+        Err(Error::WrapperError(WrapperErrorKind::MissingAuthSession))
+    );
+}
+
+fn define_counter_index(context: &mut tss_esapi::Context, nv_index: u32) -> NvIndexHandle {
+    let attributes = NvIndexAttributesBuilder::new()
+        .with_nv_index_type(NvIndexType::Counter)
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create counter nv index attributes");
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(NvIndexTpmHandle::new(nv_index).unwrap())
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(attributes)
+        .with_data_area_size(8)
+        .build()
+        .expect("Failed to build nv public");
+    context
+        .nv_define_space(Provision::Owner, None, nv_public)
+        .expect("Call to nv_define_space failed")
+}
+
+#[test]
+fn read_counter_uninitialized() {
+    let mut context = create_ctx_with_session();
+    let nv_index_handle = define_counter_index(&mut context, 0x0150003A);
+
+    let initial_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index_handle, false);
+    let increment_result = context.nv_increment(NvAuth::Owner, nv_index_handle);
+    let first_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index_handle, false);
+    let increment_result_2 = context.nv_increment(NvAuth::Owner, nv_index_handle);
+    let second_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index_handle, false);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert_eq!(initial_value.expect("Call to nv::read_counter failed"), 0);
+    increment_result.expect("Call to nv_increment failed");
+    increment_result_2.expect("Call to nv_increment failed");
+    assert_eq!(
+        second_value.expect("Call to nv::read_counter failed"),
+        first_value.expect("Call to nv::read_counter failed") + 1
+    );
+}
+
+#[test]
+fn read_counter_initialize() {
+    let mut context = create_ctx_with_session();
+    let nv_index_handle = define_counter_index(&mut context, 0x0150003B);
+
+    let initial_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index_handle, true);
+    let increment_result = context.nv_increment(NvAuth::Owner, nv_index_handle);
+    let incremented_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index_handle, true);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    increment_result.expect("Call to nv_increment failed");
+    assert_eq!(
+        incremented_value.expect("Call to nv::read_counter failed"),
+        initial_value.expect("Call to nv::read_counter failed") + 1
+    );
+}
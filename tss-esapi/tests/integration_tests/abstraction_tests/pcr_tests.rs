@@ -139,3 +139,28 @@ fn test_pcr_read_all() {
                 })
         })
 }
+
+#[test]
+fn test_pcr_read_all_banks() {
+    let mut context = create_ctx_without_session();
+
+    let pcr_banks = tss_esapi::abstraction::pcr::read_all_banks(&mut context)
+        .expect("Call to pcr::read_all_banks failed");
+
+    let sha256_bank = pcr_banks
+        .get(&HashingAlgorithm::Sha256)
+        .expect("The SHA-256 bank was not read");
+    assert_eq!(sha256_bank.len(), 24);
+    assert!(sha256_bank.keys().copied().eq(0..24));
+    assert!(sha256_bank.values().all(|digest| digest.len() == 32));
+
+    let (_, _, pcr_digests) = context
+        .pcr_read(
+            PcrSelectionListBuilder::new()
+                .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0])
+                .build()
+                .expect("Failed to create PcrSelectionList"),
+        )
+        .expect("Call to pcr_read failed");
+    assert_eq!(sha256_bank.get(&0), pcr_digests.value().first());
+}
@@ -0,0 +1,597 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::{
+    create_ctx_with_session, create_ctx_without_session, decryption_key_pub, signing_key_pub,
+};
+
+use std::convert::TryFrom;
+use tss_esapi::{
+    abstraction::policy,
+    attributes::{NvIndexAttributesBuilder, ObjectAttributesBuilder},
+    constants::{CommandCode, SessionType},
+    handles::{KeyHandle, NvIndexHandle, NvIndexTpmHandle},
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm},
+        ecc::EccCurve,
+        reserved_handles::{Hierarchy, NvAuth, Provision},
+        session_handles::PolicySession,
+    },
+    structures::{
+        Digest, DigestList, EccPoint, EccScheme, HashScheme, KeyDerivationFunctionScheme,
+        MaxBuffer, MaxNvBuffer, Name, Nonce, NvPublicBuilder, PcrSelectionList,
+        PcrSelectionListBuilder, PcrSlot, PublicBuilder, PublicEccParametersBuilder,
+        SignatureScheme, SymmetricDefinition,
+    },
+    Context, Error, Result, WrapperErrorKind,
+};
+
+#[test]
+fn test_compute_policy_digest_pcr_and_auth_value() {
+    let mut context = create_ctx_without_session();
+
+    let pcr_selection_list = PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+        .build()
+        .expect("Failed to create PcrSelectionList");
+    let pcr_digest = Digest::try_from(vec![0xab; 32]).expect("Failed to create digest");
+
+    let policy_digest =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_pcr(session, Some(pcr_digest.clone()), pcr_selection_list)?;
+            ctx.policy_auth_value(session)
+        })
+        .expect("Failed to compute policy digest");
+
+    use sha2::Digest as _;
+    // PolicyPCR: H(0...0 || TPM_CC_PolicyPCR || pcrs || pcrDigest)
+    let mut hasher = sha2::Sha256::new();
+    hasher.update([0u8; 32]);
+    hasher.update(0x0000017Fu32.to_be_bytes());
+    hasher.update([0x00, 0x00, 0x00, 0x01, 0x00, 0x0B, 0x03, 0x03, 0x00, 0x00]);
+    hasher.update(pcr_digest.as_bytes());
+    let pcr_policy_digest = hasher.finalize();
+    // PolicyAuthValue: H(policyDigest || TPM_CC_PolicyAuthValue)
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(pcr_policy_digest);
+    hasher.update(0x0000016Bu32.to_be_bytes());
+    let expected_policy_digest = hasher.finalize();
+
+    assert_eq!(policy_digest.as_bytes(), expected_policy_digest.as_slice());
+}
+
+#[test]
+fn test_compute_policy_digest_propagates_errors() {
+    let mut context = create_ctx_without_session();
+
+    let result = policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |_, _| {
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam))
+    });
+    assert_eq!(
+        result,
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam))
+    );
+
+    // The trial session must have been flushed, so computing a
+    // new digest should still be possible.
+    let _ =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_auth_value(session)
+        })
+        .expect("Failed to compute policy digest");
+}
+
+#[cfg(feature = "openssl")]
+#[test]
+fn test_pcr_policy_digest_matches_tpm() {
+    let mut context = create_ctx_without_session();
+    let pcr_digest = Digest::try_from(vec![0xab; 32]).expect("Failed to create digest");
+
+    let tpm_policy_digest =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_pcr(session, Some(pcr_digest.clone()), pcr_selection_list())
+        })
+        .expect("Failed to compute policy digest");
+    let software_policy_digest =
+        policy::pcr_policy_digest(HashingAlgorithm::Sha256, &pcr_selection_list(), &pcr_digest)
+            .expect("Failed to compute PCR policy digest in software");
+
+    assert_eq!(software_policy_digest, tpm_policy_digest);
+}
+
+fn signing_key_template() -> PublicBuilder {
+    let ecc_parameters = PublicEccParametersBuilder::new()
+        .with_ecc_scheme(EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)))
+        .with_curve(EccCurve::NistP256)
+        .with_is_signing_key(true)
+        .with_is_decryption_key(false)
+        .with_restricted(false)
+        .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+        .build()
+        .expect("Failed to build ECC parameters");
+    // The key can only be used by satisfying its policy.
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(false)
+        .with_sign_encrypt(true)
+        .build()
+        .expect("Failed to build object attributes");
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_ecc_parameters(ecc_parameters)
+        .with_ecc_unique_identifier(EccPoint::default())
+}
+
+fn pcr_selection_list() -> PcrSelectionList {
+    PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+        .build()
+        .expect("Failed to create PcrSelectionList")
+}
+
+#[test]
+fn test_create_policy_bound_key() {
+    let mut context = create_ctx_with_session();
+    let parent = context
+        .create_primary(
+            Hierarchy::Owner,
+            decryption_key_pub(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("Failed to create primary key")
+        .key_handle;
+
+    // The digest of the current PCR values is used by the trial session.
+    let (private, public) = policy::create_policy_bound_key(
+        &mut context,
+        parent,
+        signing_key_template(),
+        |ctx, session| ctx.policy_pcr(session, None, pcr_selection_list()),
+    )
+    .expect("Failed to create policy bound key");
+
+    let expected_policy_digest =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_pcr(session, None, pcr_selection_list())
+        })
+        .expect("Failed to compute policy digest");
+    assert_eq!(public.auth_policy(), &expected_policy_digest);
+
+    let key_handle = context
+        .load(parent, private, public)
+        .expect("Failed to load policy bound key");
+    let digest = Digest::try_from(vec![0xa5; 32]).expect("Failed to create digest");
+
+    // The key cannot be used with its auth value.
+    let _ = context
+        .sign(key_handle, digest.clone(), SignatureScheme::Null, None)
+        .expect_err("Signed without satisfying the policy");
+
+    let policy_auth_session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Policy,
+            SymmetricDefinition::AES_128_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .expect("Failed to start policy session")
+        .expect("Received invalid handle");
+    let policy_session =
+        PolicySession::try_from(policy_auth_session).expect("Failed to get policy session");
+    context
+        .policy_pcr(policy_session, None, pcr_selection_list())
+        .expect("Failed to call policy_pcr");
+
+    let _ = context
+        .execute_with_session(Some(policy_auth_session), |ctx| {
+            ctx.sign(key_handle, digest, SignatureScheme::Null, None)
+        })
+        .expect("Failed to sign after satisfying the policy");
+}
+
+#[test]
+fn test_compute_cp_hash() {
+    let mut context = create_ctx_without_session();
+    let name = Name::try_from(vec![0x00, 0x0B, 0xaa, 0xbb]).expect("Failed to create name");
+    let parameters = [0x00, 0x02, 0x01, 0x02, 0x00, 0x00];
+
+    let cp_hash = policy::compute_cp_hash(
+        &mut context,
+        HashingAlgorithm::Sha256,
+        CommandCode::NvWrite,
+        &[name.clone(), name.clone()],
+        &parameters,
+    )
+    .expect("Failed to compute cpHash");
+
+    use sha2::Digest as _;
+    // cpHash: H(TPM_CC_NV_Write || authName || nvIndexName || parameters)
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(0x00000137u32.to_be_bytes());
+    hasher.update(name.value());
+    hasher.update(name.value());
+    hasher.update(parameters);
+    assert_eq!(cp_hash.as_bytes(), hasher.finalize().as_slice());
+}
+
+#[test]
+fn test_compute_name_hash() {
+    let mut context = create_ctx_without_session();
+    let first_name = Name::try_from(vec![0x00, 0x0B, 0xaa, 0xbb]).expect("Failed to create name");
+    let second_name = Name::try_from(vec![0x00, 0x0B, 0xcc]).expect("Failed to create name");
+
+    let name_hash = policy::compute_name_hash(
+        &mut context,
+        HashingAlgorithm::Sha256,
+        &[first_name.clone(), second_name.clone()],
+    )
+    .expect("Failed to compute nameHash");
+
+    use sha2::Digest as _;
+    // nameHash: H(name1 || name2)
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(first_name.value());
+    hasher.update(second_name.value());
+    assert_eq!(name_hash.as_bytes(), hasher.finalize().as_slice());
+}
+
+/// Marshalls the parameters of TPM2_NV_Write.
+fn nv_write_parameters(data: &[u8], offset: u16) -> Vec<u8> {
+    let mut parameters = (data.len() as u16).to_be_bytes().to_vec();
+    parameters.extend_from_slice(data);
+    parameters.extend_from_slice(&offset.to_be_bytes());
+    parameters
+}
+
+/// Defines an NV index that can be written with any policy approved by the key.
+fn define_authorized_nv_index(
+    context: &mut Context,
+    key_name: &Name,
+    nv_index: NvIndexTpmHandle,
+) -> (NvIndexHandle, Name) {
+    // H(H(0...0 || TPM_CC_PolicyAuthorize || keyName) || policyRef)
+    use sha2::Digest as _;
+    let mut hasher = sha2::Sha256::new();
+    hasher.update([0u8; 32]);
+    hasher.update(0x0000016Au32.to_be_bytes());
+    hasher.update(key_name.value());
+    let policy_digest = hasher.finalize();
+    let auth_policy = Digest::try_from(sha2::Sha256::digest(policy_digest).to_vec())
+        .expect("Failed to create auth policy");
+
+    let nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_policy_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create nv index attributes");
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(nv_index_attributes)
+        .with_index_auth_policy(auth_policy)
+        .with_data_area_size(32)
+        .build()
+        .expect("Failed to build NvPublic");
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, nv_public)
+        .expect("Call to nv_define_space failed");
+    let (_, nv_index_name) = context
+        .nv_read_public(nv_index_handle)
+        .expect("Failed to read the NV index public area");
+    (nv_index_handle, nv_index_name)
+}
+
+/// Writes `data` to the NV index using a policy session in which the
+/// policy executed by `approved_policy_fn` has been authorized by the key.
+fn write_with_approved_policy<F>(
+    context: &mut Context,
+    key_handle: KeyHandle,
+    nv_index_handle: NvIndexHandle,
+    data: &[u8],
+    approved_policy_fn: F,
+) -> Result<()>
+where
+    F: Fn(&mut Context, PolicySession) -> Result<()>,
+{
+    let approved_policy =
+        policy::compute_policy_digest(context, HashingAlgorithm::Sha256, &approved_policy_fn)
+            .expect("Failed to compute approved policy");
+    // aHash ≔ H(approvedPolicy || policyRef), the policyRef is empty.
+    let (ahash, _) = context
+        .execute_without_session(|ctx| {
+            ctx.hash(
+                MaxBuffer::from_bytes(approved_policy.as_bytes())?,
+                HashingAlgorithm::Sha256,
+                Hierarchy::Null,
+            )
+        })
+        .expect("Failed to hash the approved policy");
+    let signature = context
+        .sign(key_handle, ahash.clone(), SignatureScheme::Null, None)
+        .expect("Failed to sign the approved policy");
+    let ticket = context
+        .verify_signature(key_handle, ahash, signature)
+        .expect("Failed to verify the approved policy signature");
+    let key_name = context
+        .tr_get_name(key_handle.into())
+        .expect("Failed to get key name");
+
+    let sessions = context.sessions();
+    context.clear_sessions();
+    let policy_auth_session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Policy,
+            SymmetricDefinition::AES_128_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .expect("Failed to start policy session")
+        .expect("Received invalid handle");
+    let policy_session =
+        PolicySession::try_from(policy_auth_session).expect("Failed to get policy session");
+    approved_policy_fn(context, policy_session).expect("Failed to execute the approved policy");
+    context
+        .policy_authorize(
+            policy_session,
+            approved_policy,
+            Nonce::default(),
+            &key_name,
+            ticket,
+        )
+        .expect("Failed to call policy_authorize");
+
+    context.set_sessions((Some(policy_auth_session), None, None));
+    let result = context.nv_write(
+        NvAuth::NvIndex(nv_index_handle),
+        nv_index_handle,
+        MaxNvBuffer::try_from(data.to_vec()).expect("Failed to create data"),
+        0,
+    );
+    context.set_sessions(sessions);
+    result
+}
+
+#[test]
+fn test_cp_hash_policy_authorizes_single_nv_write() {
+    let mut context = create_ctx_with_session();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+        .expect("Failed to create signing key")
+        .key_handle;
+    let key_name = context
+        .tr_get_name(key_handle.into())
+        .expect("Failed to get key name");
+    let (nv_index_handle, nv_index_name) = define_authorized_nv_index(
+        &mut context,
+        &key_name,
+        NvIndexTpmHandle::new(0x01500034).unwrap(),
+    );
+
+    let data = [1, 2, 3, 4];
+    let cp_hash = policy::compute_cp_hash(
+        &mut context,
+        HashingAlgorithm::Sha256,
+        CommandCode::NvWrite,
+        &[nv_index_name.clone(), nv_index_name],
+        &nv_write_parameters(&data, 0),
+    )
+    .expect("Failed to compute cpHash");
+
+    // Only the write matching the cpHash is authorized.
+    let policy_fn = |ctx: &mut Context, session| ctx.policy_cp_hash(session, cp_hash.clone());
+    let other_write_result =
+        write_with_approved_policy(&mut context, key_handle, nv_index_handle, &[5], policy_fn);
+    let write_result =
+        write_with_approved_policy(&mut context, key_handle, nv_index_handle, &data, policy_fn);
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert!(other_write_result
+        .expect_err("A different write should not satisfy the policy")
+        .is_policy_fail());
+    write_result.expect("The write should satisfy the policy");
+}
+
+#[test]
+fn test_name_hash_policy_authorizes_single_nv_index() {
+    let mut context = create_ctx_with_session();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+        .expect("Failed to create signing key")
+        .key_handle;
+    let key_name = context
+        .tr_get_name(key_handle.into())
+        .expect("Failed to get key name");
+    let (nv_index_handle, nv_index_name) = define_authorized_nv_index(
+        &mut context,
+        &key_name,
+        NvIndexTpmHandle::new(0x01500035).unwrap(),
+    );
+    let (other_nv_index_handle, _) = define_authorized_nv_index(
+        &mut context,
+        &key_name,
+        NvIndexTpmHandle::new(0x01500036).unwrap(),
+    );
+
+    // The approved policy only applies to commands acting on the first index,
+    // which is both the authorization handle and the index of NV_Write.
+    let name_hash = policy::compute_name_hash(
+        &mut context,
+        HashingAlgorithm::Sha256,
+        &[nv_index_name.clone(), nv_index_name],
+    )
+    .expect("Failed to compute nameHash");
+    let policy_fn = |ctx: &mut Context, session| ctx.policy_name_hash(session, name_hash.clone());
+    let other_write_result = write_with_approved_policy(
+        &mut context,
+        key_handle,
+        other_nv_index_handle,
+        &[1, 2, 3, 4],
+        policy_fn,
+    );
+    let write_result = write_with_approved_policy(
+        &mut context,
+        key_handle,
+        nv_index_handle,
+        &[1, 2, 3, 4],
+        policy_fn,
+    );
+
+    context
+        .nv_undefine_space(Provision::Owner, other_nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert!(other_write_result
+        .expect_err("A write to another index should not satisfy the policy")
+        .is_policy_fail());
+    write_result.expect("The write should satisfy the policy");
+}
+
+#[test]
+fn test_satisfy_or() {
+    let mut context = create_ctx_without_session();
+    let auth_value_branch =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_auth_value(session)
+        })
+        .expect("Failed to compute auth value policy digest");
+    let command_code_branch =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_command_code(session, CommandCode::Sign)
+        })
+        .expect("Failed to compute command code policy digest");
+    let branches = [auth_value_branch, command_code_branch];
+    let expected_policy_digest =
+        policy::compute_policy_digest(&mut context, HashingAlgorithm::Sha256, |ctx, session| {
+            ctx.policy_auth_value(session)?;
+            ctx.policy_or(session, DigestList::try_from(branches.to_vec())?)
+        })
+        .expect("Failed to compute OR policy digest");
+
+    let mut start_policy_session = || -> PolicySession {
+        PolicySession::try_from(
+            context
+                .start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+                .expect("Failed to start policy session")
+                .expect("Received invalid handle"),
+        )
+        .expect("Failed to convert auth session into policy session")
+    };
+    let policy_session = start_policy_session();
+    let unsatisfied_policy_session = start_policy_session();
+
+    context
+        .policy_auth_value(policy_session)
+        .expect("Failed to call policy_auth_value");
+    policy::satisfy_or(&mut context, policy_session, &branches)
+        .expect("Failed to satisfy policy OR");
+    assert_eq!(
+        context
+            .policy_get_digest(policy_session)
+            .expect("Failed to call policy_get_digest"),
+        expected_policy_digest
+    );
+
+    // A session which is on none of the branches is rejected before
+    // the command is sent.
+    assert_eq!(
+        policy::satisfy_or(&mut context, unsatisfied_policy_session, &branches),
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam))
+    );
+
+    // A single branch is rejected even though the session is on it.
+    assert_eq!(
+        policy::satisfy_or(&mut context, policy_session, &branches[..1]),
+        Err(Error::WrapperError(WrapperErrorKind::WrongParamSize))
+    );
+}
+
+#[test]
+fn test_policy_secret_owner_authorizes_nv_write() {
+    let mut context = create_ctx_with_session();
+    let auth_policy = policy::compute_policy_digest(
+        &mut context,
+        HashingAlgorithm::Sha256,
+        policy::policy_secret_owner,
+    )
+    .expect("Failed to compute owner secret policy digest");
+
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(NvIndexTpmHandle::new(0x0150003C).unwrap())
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(
+            NvIndexAttributesBuilder::new()
+                .with_policy_write(true)
+                .with_owner_read(true)
+                .build()
+                .expect("Failed to create nv index attributes"),
+        )
+        .with_index_auth_policy(auth_policy)
+        .with_data_area_size(4)
+        .build()
+        .expect("Failed to build NvPublic");
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, nv_public)
+        .expect("Call to nv_define_space failed");
+
+    let policy_auth_session = context
+        .execute_without_session(|ctx| {
+            ctx.start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Policy,
+                SymmetricDefinition::AES_128_CFB,
+                HashingAlgorithm::Sha256,
+            )
+        })
+        .expect("Failed to start policy session")
+        .expect("Received invalid handle");
+    let policy_session =
+        PolicySession::try_from(policy_auth_session).expect("Failed to get policy session");
+
+    let missing_session_result =
+        context.execute_without_session(|ctx| policy::policy_secret_owner(ctx, policy_session));
+    let write_result = policy::policy_secret_owner(&mut context, policy_session).and_then(|_| {
+        context.execute_with_session(Some(policy_auth_session), |ctx| {
+            ctx.nv_write(
+                NvAuth::NvIndex(nv_index_handle),
+                nv_index_handle,
+                MaxNvBuffer::try_from(vec![1, 2, 3, 4]).unwrap(),
+                0,
+            )
+        })
+    });
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .expect("Call to nv_undefine_space failed");
+
+    assert_eq!(
+        missing_session_result,
+        Err(Error::WrapperError(WrapperErrorKind::MissingAuthSession))
+    );
+    write_result.expect("Failed to write with the owner secret policy");
+}
@@ -0,0 +1,61 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use tss_esapi::{
+    abstraction::primary,
+    interface_types::{reserved_handles::Hierarchy, session_handles::AuthSession},
+};
+
+use crate::common::{create_ctx_without_session, decryption_key_pub, signing_key_pub};
+
+#[test]
+fn test_create_primary_deterministic_returns_same_key() {
+    let mut context = create_ctx_without_session();
+    let (first, second, other) = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+        let first =
+            primary::create_primary_deterministic(ctx, Hierarchy::Owner, decryption_key_pub())
+                .expect("Failed to create primary key");
+        let second =
+            primary::create_primary_deterministic(ctx, Hierarchy::Owner, decryption_key_pub())
+                .expect("Failed to create primary key a second time");
+        let other = primary::create_primary_deterministic(ctx, Hierarchy::Owner, signing_key_pub())
+            .expect("Failed to create primary key from another template");
+        (first, second, other)
+    });
+
+    assert_eq!(first.key_handle, second.key_handle);
+    assert_ne!(first.key_handle, other.key_handle);
+    assert_eq!(context.transient_handle_count(), 2);
+
+    let first_name = context
+        .tr_get_name(first.key_handle.into())
+        .expect("Failed to get name of the first key");
+    let second_name = context
+        .tr_get_name(second.key_handle.into())
+        .expect("Failed to get name of the second key");
+    assert_eq!(first_name, second_name);
+}
+
+#[test]
+fn test_create_primary_deterministic_recreates_flushed_key() {
+    let mut context = create_ctx_without_session();
+    let first = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+        primary::create_primary_deterministic(ctx, Hierarchy::Owner, decryption_key_pub())
+            .expect("Failed to create primary key")
+    });
+    let first_name = context
+        .tr_get_name(first.key_handle.into())
+        .expect("Failed to get name of the first key");
+    context
+        .flush_context(first.key_handle.into())
+        .expect("Failed to flush the primary key");
+
+    let second = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+        primary::create_primary_deterministic(ctx, Hierarchy::Owner, decryption_key_pub())
+            .expect("Failed to recreate primary key")
+    });
+    let second_name = context
+        .tr_get_name(second.key_handle.into())
+        .expect("Failed to get name of the recreated key");
+    assert_eq!(first_name, second_name);
+    assert_eq!(context.transient_handle_count(), 1);
+}
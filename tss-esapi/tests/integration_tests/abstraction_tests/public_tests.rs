@@ -198,3 +198,238 @@ mod public_ecc_test {
         }
     }
 }
+
+#[cfg(feature = "openssl")]
+mod public_openssl_test {
+    use crate::common::{create_ctx_with_session, signing_key_pub};
+    use openssl::{bn::BigNum, ecdsa::EcdsaSig, hash::MessageDigest, sign::Verifier};
+    use sha2::Digest as _;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        abstraction::public::to_openssl_pkey,
+        interface_types::{
+            algorithm::HashingAlgorithm, ecc::EccCurve, reserved_handles::Hierarchy,
+        },
+        structures::{Digest, EccScheme, HashScheme, Signature, SignatureScheme},
+        utils::create_unrestricted_signing_ecc_public,
+    };
+
+    const MESSAGE: &[u8] = b"Message signed by the TPM";
+
+    #[test]
+    fn test_verify_rsa_signature_with_openssl_pkey() {
+        let mut context = create_ctx_with_session();
+        let key = context
+            .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+            .expect("Failed to create RSA signing key");
+        let digest = Digest::try_from(sha2::Sha256::digest(MESSAGE).to_vec())
+            .expect("Failed to create digest");
+        let signature = context
+            .sign(key.key_handle, digest, SignatureScheme::Null, None)
+            .expect("Failed to sign digest");
+        context
+            .flush_context(key.key_handle.into())
+            .expect("Failed to flush key");
+
+        let signature = match signature {
+            Signature::RsaSsa(signature) => signature.signature().as_bytes().to_vec(),
+            _ => panic!("Unexpected signature type"),
+        };
+        let pkey = to_openssl_pkey(&key.out_public).expect("Failed to convert RSA public key");
+        let mut verifier =
+            Verifier::new(MessageDigest::sha256(), &pkey).expect("Failed to create verifier");
+        verifier.update(MESSAGE).expect("Failed to update verifier");
+        assert!(verifier
+            .verify(&signature)
+            .expect("Failed to verify signature"));
+    }
+
+    #[test]
+    fn test_verify_ecc_signature_with_openssl_pkey() {
+        let mut context = create_ctx_with_session();
+        let key = context
+            .create_primary(
+                Hierarchy::Owner,
+                create_unrestricted_signing_ecc_public(
+                    EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    EccCurve::NistP256,
+                )
+                .expect("Failed to create ECC public"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create ECC signing key");
+        let digest = Digest::try_from(sha2::Sha256::digest(MESSAGE).to_vec())
+            .expect("Failed to create digest");
+        let signature = context
+            .sign(key.key_handle, digest, SignatureScheme::Null, None)
+            .expect("Failed to sign digest");
+        context
+            .flush_context(key.key_handle.into())
+            .expect("Failed to flush key");
+
+        let signature = match signature {
+            Signature::EcDsa(signature) => EcdsaSig::from_private_components(
+                BigNum::from_slice(signature.signature_r().as_bytes()).unwrap(),
+                BigNum::from_slice(signature.signature_s().as_bytes()).unwrap(),
+            )
+            .and_then(|signature| signature.to_der())
+            .expect("Failed to encode ECDSA signature"),
+            _ => panic!("Unexpected signature type"),
+        };
+        let pkey = to_openssl_pkey(&key.out_public).expect("Failed to convert ECC public key");
+        let mut verifier =
+            Verifier::new(MessageDigest::sha256(), &pkey).expect("Failed to create verifier");
+        verifier.update(MESSAGE).expect("Failed to update verifier");
+        assert!(verifier
+            .verify(&signature)
+            .expect("Failed to verify signature"));
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod public_jwk_test {
+    use crate::common::{create_ctx_with_session, signing_key_pub};
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use tss_esapi::{
+        abstraction::public::to_jwk,
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm},
+            ecc::EccCurve,
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            EccParameter, EccPoint, EccScheme, HashScheme, KeyDerivationFunctionScheme, Public,
+            PublicBuilder, PublicEccParametersBuilder,
+        },
+        utils::create_unrestricted_signing_ecc_public,
+    };
+
+    /// Coordinates of the P-256 key from appendix A.1 of RFC 7517, whose JWK
+    /// members are "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4" and
+    /// "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM".
+    const RFC_7517_EC_X: [u8; 32] = [
+        0x30, 0xa0, 0x42, 0x4c, 0xd2, 0x1c, 0x29, 0x44, 0x83, 0x8a, 0x2d, 0x75, 0xc9, 0x2b, 0x37,
+        0xe7, 0x6e, 0xa2, 0x0d, 0x9f, 0x00, 0x89, 0x3a, 0x3b, 0x4e, 0xee, 0x8a, 0x3c, 0x0a, 0xaf,
+        0xec, 0x3e,
+    ];
+    const RFC_7517_EC_Y: [u8; 32] = [
+        0xe0, 0x4b, 0x65, 0xe9, 0x24, 0x56, 0xd9, 0x88, 0x8b, 0x52, 0xb3, 0x79, 0xbd, 0xfb, 0xd5,
+        0x1e, 0xe8, 0x69, 0xef, 0x1f, 0x0f, 0xc6, 0x5b, 0x66, 0x59, 0x69, 0x5b, 0x6c, 0xce, 0x08,
+        0x17, 0x23,
+    ];
+
+    fn base64url_decode(encoded: &str) -> Vec<u8> {
+        URL_SAFE_NO_PAD
+            .decode(encoded)
+            .expect("Invalid base64url value")
+    }
+
+    #[test]
+    fn test_rsa_jwk() {
+        let mut context = create_ctx_with_session();
+        let key = context
+            .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+            .expect("Failed to create RSA signing key");
+        context
+            .flush_context(key.key_handle.into())
+            .expect("Failed to flush key");
+
+        let jwk = to_jwk(&key.out_public).expect("Failed to convert RSA public key");
+        let modulus = match &key.out_public {
+            Public::Rsa { unique, .. } => unique.as_bytes().to_vec(),
+            _ => panic!("Unexpected public type"),
+        };
+        assert_eq!(jwk["kty"], "RSA");
+        assert_eq!(
+            base64url_decode(jwk["n"].as_str().expect("n is not a string")),
+            modulus
+        );
+        assert_eq!(jwk["e"], "AQAB");
+    }
+
+    #[test]
+    fn test_ecc_jwk() {
+        let mut context = create_ctx_with_session();
+        let key = context
+            .create_primary(
+                Hierarchy::Owner,
+                create_unrestricted_signing_ecc_public(
+                    EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    EccCurve::NistP256,
+                )
+                .expect("Failed to create ECC public"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create ECC signing key");
+        context
+            .flush_context(key.key_handle.into())
+            .expect("Failed to flush key");
+
+        let jwk = to_jwk(&key.out_public).expect("Failed to convert ECC public key");
+        let (x, y) = match &key.out_public {
+            Public::Ecc { unique, .. } => (
+                unique.x().as_bytes().to_vec(),
+                unique.y().as_bytes().to_vec(),
+            ),
+            _ => panic!("Unexpected public type"),
+        };
+        assert_eq!(jwk["kty"], "EC");
+        assert_eq!(jwk["crv"], "P-256");
+        assert_eq!(
+            base64url_decode(jwk["x"].as_str().expect("x is not a string")),
+            x
+        );
+        assert_eq!(
+            base64url_decode(jwk["y"].as_str().expect("y is not a string")),
+            y
+        );
+    }
+
+    #[test]
+    fn test_ecc_jwk_rfc_7517() {
+        let ecc_parameters = PublicEccParametersBuilder::new()
+            .with_ecc_scheme(EccScheme::Null)
+            .with_curve(EccCurve::NistP256)
+            .with_is_signing_key(true)
+            .with_is_decryption_key(false)
+            .with_restricted(false)
+            .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+            .build()
+            .expect("Failed to build PublicEccParameters");
+        let public = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(
+                ObjectAttributesBuilder::new()
+                    .with_user_with_auth(true)
+                    .with_sign_encrypt(true)
+                    .build()
+                    .expect("Failed to build object attributes"),
+            )
+            .with_ecc_parameters(ecc_parameters)
+            .with_ecc_unique_identifier(EccPoint::new(
+                EccParameter::from_bytes(&RFC_7517_EC_X).expect("Failed to construct x"),
+                EccParameter::from_bytes(&RFC_7517_EC_Y).expect("Failed to construct y"),
+            ))
+            .build()
+            .expect("Failed to build Public structure");
+
+        let jwk = to_jwk(&public).expect("Failed to convert ECC public key");
+        assert_eq!(
+            jwk,
+            serde_json::json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "x": "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+                "y": "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+            })
+        );
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::create_ctx_without_session;
+#[cfg(feature = "rand")]
+use rand_core::RngCore;
+use tss_esapi::abstraction::rng;
+#[cfg(feature = "rand")]
+use tss_esapi::abstraction::rng::TpmRng;
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_fill_bytes() {
+    let mut context = create_ctx_without_session();
+    let mut rng = TpmRng::new(&mut context);
+
+    // Larger than what the TPM returns in a single call.
+    let mut buffer = [0u8; 100];
+    rng.try_fill_bytes(&mut buffer)
+        .expect("Failed to get random bytes");
+    assert!(buffer.iter().any(|&byte| byte != 0));
+
+    let mut other_buffer = [0u8; 100];
+    rng.fill_bytes(&mut other_buffer);
+    assert_ne!(buffer, other_buffer);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_next_u64() {
+    let mut context = create_ctx_without_session();
+    let mut rng = TpmRng::new(&mut context);
+
+    assert_ne!(rng.next_u64(), rng.next_u64());
+}
+
+#[test]
+fn test_reseed_and_get_random() {
+    let mut context = create_ctx_without_session();
+
+    let random_bytes = rng::reseed_and_get_random(&mut context, &[], 16)
+        .expect("Failed to get random bytes without extra entropy");
+    assert_eq!(random_bytes.len(), 16);
+
+    // More entropy and bytes than can be handled in a single call.
+    let random_bytes = rng::reseed_and_get_random(&mut context, &[0xa5; 300], 100)
+        .expect("Failed to get random bytes with extra entropy");
+    assert_eq!(random_bytes.len(), 100);
+    assert!(random_bytes.iter().any(|&byte| byte != 0));
+}
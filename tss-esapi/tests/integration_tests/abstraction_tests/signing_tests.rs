@@ -0,0 +1,337 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::{create_ctx_with_session, signing_key_pub, HASH};
+use sha2::{Digest as _, Sha256};
+use std::convert::TryFrom;
+use tss_esapi::{
+    abstraction::signing::{der_to_ecdsa, ecdsa_to_der, hash_and_sign, sign_with_key},
+    attributes::ObjectAttributesBuilder,
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
+        ecc::EccCurve,
+        key_bits::RsaKeyBits,
+        reserved_handles::Hierarchy,
+    },
+    structures::{
+        Digest, EccParameter, EccScheme, EccSignature, HashScheme, Public, PublicBuilder,
+        PublicKeyRsa, PublicRsaParametersBuilder, RsaExponent, RsaScheme, Signature,
+        SignatureScheme,
+    },
+    utils, Context, Error, WrapperErrorKind,
+};
+
+fn sign_and_verify(context: &mut Context, key_public: Public) -> Signature {
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, key_public.clone(), None, None, None, None)
+        .expect("Failed to create primary key")
+        .key_handle;
+    let digest = Digest::try_from(HASH[..32].to_vec()).unwrap();
+
+    let signature = sign_with_key(context, key_handle, &key_public, digest.clone(), None)
+        .expect("Failed to sign with key");
+    context
+        .verify_signature(key_handle, digest, signature.clone())
+        .expect("Failed to verify signature");
+    context
+        .flush_context(key_handle.into())
+        .expect("Failed to flush key");
+    signature
+}
+
+#[test]
+fn sign_with_rsassa_key() {
+    let mut context = create_ctx_with_session();
+    let signature = sign_and_verify(&mut context, signing_key_pub());
+    assert!(matches!(signature, Signature::RsaSsa(_)));
+}
+
+#[test]
+fn sign_with_ecdsa_key() {
+    let mut context = create_ctx_with_session();
+    let key_public = utils::create_unrestricted_signing_ecc_public(
+        EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+        EccCurve::NistP256,
+    )
+    .expect("Failed to create ECC public");
+    let signature = sign_and_verify(&mut context, key_public);
+    assert!(matches!(signature, Signature::EcDsa(_)));
+}
+
+#[test]
+fn sign_with_null_scheme_key() {
+    let mut context = create_ctx_with_session();
+    let key_public = utils::create_unrestricted_signing_rsa_public(
+        RsaScheme::Null,
+        RsaKeyBits::Rsa2048,
+        RsaExponent::default(),
+    )
+    .expect("Failed to create RSA public");
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, key_public.clone(), None, None, None, None)
+        .expect("Failed to create primary key")
+        .key_handle;
+    let digest = Digest::try_from(HASH[..32].to_vec()).unwrap();
+
+    assert_eq!(
+        sign_with_key(&mut context, key_handle, &key_public, digest.clone(), None).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+
+    let scheme_hint = SignatureScheme::RsaPss {
+        scheme: HashScheme::new(HashingAlgorithm::Sha256),
+    };
+    let signature = sign_with_key(
+        &mut context,
+        key_handle,
+        &key_public,
+        digest,
+        Some(scheme_hint),
+    )
+    .expect("Failed to sign with key");
+    assert!(matches!(signature, Signature::RsaPss(_)));
+    context
+        .flush_context(key_handle.into())
+        .expect("Failed to flush key");
+}
+
+#[test]
+fn ecdsa_der_round_trip() {
+    let mut context = create_ctx_with_session();
+    let key_public = utils::create_unrestricted_signing_ecc_public(
+        EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+        EccCurve::NistP256,
+    )
+    .expect("Failed to create ECC public");
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, key_public.clone(), None, None, None, None)
+        .expect("Failed to create primary key")
+        .key_handle;
+    let digest = Digest::try_from(HASH[..32].to_vec()).unwrap();
+    let signature = sign_with_key(&mut context, key_handle, &key_public, digest.clone(), None)
+        .expect("Failed to sign with key");
+
+    let der = ecdsa_to_der(&signature).expect("Failed to encode signature");
+    let decoded = der_to_ecdsa(&der, EccCurve::NistP256, HashingAlgorithm::Sha256)
+        .expect("Failed to decode signature");
+    context
+        .verify_signature(key_handle, digest, decoded)
+        .expect("Failed to verify decoded signature");
+    context
+        .flush_context(key_handle.into())
+        .expect("Failed to flush key");
+}
+
+#[test]
+fn ecdsa_der_integer_encoding() {
+    // r has leading zeros that must be stripped, s has its most
+    // significant bit set and needs a leading zero.
+    let mut r = vec![0x00; 2];
+    r.extend_from_slice(&[0x01; 30]);
+    let mut s = vec![0x80];
+    s.extend_from_slice(&[0x00; 31]);
+    let signature = Signature::EcDsa(
+        EccSignature::create(
+            HashingAlgorithm::Sha256,
+            EccParameter::try_from(r).unwrap(),
+            EccParameter::try_from(s).unwrap(),
+        )
+        .unwrap(),
+    );
+
+    let mut expected = vec![0x30, 0x43, 0x02, 0x1e];
+    expected.extend_from_slice(&[0x01; 30]);
+    expected.extend_from_slice(&[0x02, 0x21, 0x00, 0x80]);
+    expected.extend_from_slice(&[0x00; 31]);
+
+    let der = ecdsa_to_der(&signature).expect("Failed to encode signature");
+    assert_eq!(der, expected);
+    assert_eq!(
+        der_to_ecdsa(&der, EccCurve::NistP256, HashingAlgorithm::Sha256)
+            .expect("Failed to decode signature"),
+        signature
+    );
+}
+
+#[test]
+fn ecdsa_der_invalid_input() {
+    let invalid_ders: [&[u8]; 3] = [
+        // Negative r
+        &[0x30, 0x06, 0x02, 0x01, 0x80, 0x02, 0x01, 0x01],
+        // r not minimally encoded
+        &[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+        // Not a sequence
+        &[0x02, 0x01, 0x01],
+    ];
+    for der in invalid_ders.iter() {
+        assert_eq!(
+            der_to_ecdsa(der, EccCurve::NistP256, HashingAlgorithm::Sha256).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InvalidParam)
+        );
+    }
+
+    // r is larger than the curve size
+    let mut der = vec![0x30, 0x26, 0x02, 0x21];
+    der.extend_from_slice(&[0x01; 33]);
+    der.extend_from_slice(&[0x02, 0x01, 0x01]);
+    assert_eq!(
+        der_to_ecdsa(&der, EccCurve::NistP256, HashingAlgorithm::Sha256).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+
+    assert_eq!(
+        ecdsa_to_der(&Signature::Null).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+#[cfg(feature = "openssl")]
+mod ecdsa_der_openssl_tests {
+    use crate::common::create_ctx_with_session;
+    use openssl::{bn::BigNum, ecdsa::EcdsaSig, hash::MessageDigest, sign::Verifier};
+    use sha2::Digest as _;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        abstraction::{
+            public::to_openssl_pkey,
+            signing::{der_to_ecdsa, ecdsa_to_der},
+        },
+        interface_types::{
+            algorithm::HashingAlgorithm, ecc::EccCurve, reserved_handles::Hierarchy,
+        },
+        structures::{Digest, EccScheme, HashScheme, SignatureScheme},
+        utils::create_unrestricted_signing_ecc_public,
+    };
+
+    const MESSAGE: &[u8] = b"Message signed by the TPM";
+
+    #[test]
+    fn ecdsa_der_verified_by_openssl() {
+        let mut context = create_ctx_with_session();
+        let key = context
+            .create_primary(
+                Hierarchy::Owner,
+                create_unrestricted_signing_ecc_public(
+                    EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    EccCurve::NistP256,
+                )
+                .expect("Failed to create ECC public"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create ECC signing key");
+        let digest = Digest::try_from(sha2::Sha256::digest(MESSAGE).to_vec())
+            .expect("Failed to create digest");
+        let signature = context
+            .sign(key.key_handle, digest, SignatureScheme::Null, None)
+            .expect("Failed to sign digest");
+        context
+            .flush_context(key.key_handle.into())
+            .expect("Failed to flush key");
+
+        let der = ecdsa_to_der(&signature).expect("Failed to encode signature");
+        let (r, s) = signature.as_ecdsa().expect("Unexpected signature type");
+        let openssl_der = EcdsaSig::from_private_components(
+            BigNum::from_slice(r.as_bytes()).unwrap(),
+            BigNum::from_slice(s.as_bytes()).unwrap(),
+        )
+        .and_then(|signature| signature.to_der())
+        .expect("Failed to encode ECDSA signature with openssl");
+        assert_eq!(der, openssl_der);
+
+        let pkey = to_openssl_pkey(&key.out_public).expect("Failed to convert ECC public key");
+        let mut verifier =
+            Verifier::new(MessageDigest::sha256(), &pkey).expect("Failed to create verifier");
+        verifier.update(MESSAGE).expect("Failed to update verifier");
+        assert!(verifier.verify(&der).expect("Failed to verify signature"));
+
+        let decoded = der_to_ecdsa(&openssl_der, EccCurve::NistP256, HashingAlgorithm::Sha256)
+            .expect("Failed to decode signature");
+        assert_eq!(ecdsa_to_der(&decoded).unwrap(), openssl_der);
+    }
+}
+
+fn restricted_signing_key_pub() -> Public {
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .with_restricted(true)
+        .build()
+        .expect("Failed to build object attributes");
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Rsa)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_rsa_parameters(
+            PublicRsaParametersBuilder::new()
+                .with_scheme(
+                    RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+                        .expect("Failed to create RSA scheme"),
+                )
+                .with_key_bits(RsaKeyBits::Rsa2048)
+                .with_exponent(RsaExponent::default())
+                .with_is_signing_key(true)
+                .with_restricted(true)
+                .build()
+                .expect("Failed to build RSA parameters"),
+        )
+        .with_rsa_unique_identifier(PublicKeyRsa::default())
+        .build()
+        .expect("Failed to build restricted signing key public")
+}
+
+fn hash_sign_and_verify(context: &mut Context, message: &[u8]) {
+    let key_public = restricted_signing_key_pub();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, key_public.clone(), None, None, None, None)
+        .expect("Failed to create restricted signing key")
+        .key_handle;
+
+    let signature = hash_and_sign(context, key_handle, &key_public, message)
+        .expect("Failed to hash and sign message");
+    let digest = Digest::try_from(Sha256::digest(message).to_vec()).unwrap();
+    context
+        .verify_signature(key_handle, digest, signature)
+        .expect("Failed to verify signature");
+    context
+        .flush_context(key_handle.into())
+        .expect("Failed to flush key");
+}
+
+#[test]
+fn hash_and_sign_short_message_with_restricted_key() {
+    let mut context = create_ctx_with_session();
+    hash_sign_and_verify(&mut context, b"Data to be signed");
+}
+
+#[test]
+fn hash_and_sign_long_message_with_restricted_key() {
+    let mut context = create_ctx_with_session();
+    let message = (0..5000u32).map(|i| i as u8).collect::<Vec<u8>>();
+    hash_sign_and_verify(&mut context, &message);
+}
+
+#[test]
+fn hash_and_sign_with_null_scheme_key() {
+    let mut context = create_ctx_with_session();
+    let key_public = utils::create_unrestricted_signing_rsa_public(
+        RsaScheme::Null,
+        RsaKeyBits::Rsa2048,
+        RsaExponent::default(),
+    )
+    .expect("Failed to create key public");
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, key_public.clone(), None, None, None, None)
+        .expect("Failed to create primary key")
+        .key_handle;
+
+    assert_eq!(
+        hash_and_sign(&mut context, key_handle, &key_public, b"Data to be signed").unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+}
@@ -2,25 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::convert::{TryFrom, TryInto};
 use tss_esapi::{
-    abstraction::transient::{KeyParams, ObjectWrapper, TransientKeyContextBuilder},
+    abstraction::transient::{
+        get_or_create_persistent_primary, KeyParams, ObjectWrapper, TransientKeyContextBuilder,
+    },
     abstraction::{ek, AsymmetricAlgorithmSelection},
-    constants::return_code::{TpmFormatOneError, TpmFormatZeroError},
+    constants::{
+        return_code::{TpmFormatOneError, TpmFormatZeroError},
+        tss::TPM2_TRANSIENT_FIRST,
+        CapabilityType,
+    },
     error::{TpmFormatZeroResponseCode, TpmResponseCode},
+    handles::PersistentTpmHandle,
     interface_types::{
         algorithm::{EccSchemeAlgorithm, HashingAlgorithm, RsaSchemeAlgorithm},
+        data_handles::Persistent,
         ecc::EccCurve,
         key_bits::RsaKeyBits,
-        reserved_handles::Hierarchy,
+        reserved_handles::{Hierarchy, Provision},
+        session_handles::AuthSession,
     },
     structures::{
-        Auth, CreateKeyResult, Digest, EccScheme, Public, PublicKeyRsa, RsaExponent, RsaScheme,
-        RsaSignature, Signature, SymmetricDefinitionObject,
+        Auth, CapabilityData, CreateKeyResult, Digest, EccScheme, HandleList, Public, PublicKeyRsa,
+        RsaExponent, RsaScheme, RsaSignature, Signature, SymmetricDefinitionObject,
     },
     utils::{create_restricted_decryption_rsa_public, PublicKey},
     Error, ReturnCode, TransientKeyContext, WrapperErrorKind as ErrorKind,
 };
 
-use crate::common::create_tcti;
+use crate::common::{create_ctx_without_session, create_tcti, decryption_key_pub, signing_key_pub};
 
 const HASH: [u8; 32] = [
     0x69, 0x3E, 0xDB, 0x1B, 0x22, 0x79, 0x03, 0xF4, 0xC0, 0xBF, 0xD6, 0x91, 0x76, 0x37, 0x84, 0xA2,
@@ -423,6 +432,74 @@ fn full_test() {
     }
 }
 
+fn loaded_transient_handles(ctx: &mut TransientKeyContext) -> usize {
+    let (capability_data, _) = ctx
+        .as_mut()
+        .execute_without_session(|context| {
+            context.get_capability(
+                CapabilityType::Handles,
+                TPM2_TRANSIENT_FIRST,
+                HandleList::MAX_SIZE as u32,
+            )
+        })
+        .expect("Failed to get loaded transient handles");
+    match capability_data {
+        CapabilityData::Handles(handles) => handles.len(),
+        _ => panic!("Unexpected capability data"),
+    }
+}
+
+#[test]
+fn operations_flush_transient_handles() {
+    let mut ctx = create_ctx();
+    let loaded_handles = loaded_transient_handles(&mut ctx);
+    let key_params = KeyParams::Rsa {
+        size: RsaKeyBits::Rsa2048,
+        scheme: RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+            .expect("Failed to create RSA scheme"),
+        pub_exponent: RsaExponent::default(),
+    };
+
+    let (key, auth) = ctx.create_key(key_params, 16).unwrap();
+    assert_eq!(loaded_transient_handles(&mut ctx), loaded_handles);
+
+    let signature = ctx
+        .sign(
+            key.clone(),
+            key_params,
+            auth,
+            Digest::try_from(HASH.to_vec()).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(loaded_transient_handles(&mut ctx), loaded_handles);
+
+    let pub_key = ctx
+        .load_external_public_key(key.public().clone(), key_params)
+        .unwrap();
+    assert_eq!(loaded_transient_handles(&mut ctx), loaded_handles);
+
+    let _ = ctx
+        .verify_signature(
+            pub_key,
+            key_params,
+            Digest::try_from(HASH.to_vec()).unwrap(),
+            signature,
+        )
+        .unwrap();
+    assert_eq!(loaded_transient_handles(&mut ctx), loaded_handles);
+
+    // Failed operations must not leave the key loaded either.
+    let _ = ctx
+        .sign(
+            key,
+            key_params,
+            None,
+            Digest::try_from(HASH.to_vec()).unwrap(),
+        )
+        .unwrap_err();
+    assert_eq!(loaded_transient_handles(&mut ctx), loaded_handles);
+}
+
 #[test]
 fn create_ecc_key() {
     let mut ctx = create_ctx();
@@ -875,3 +952,61 @@ fn get_random_from_tkc() {
         .execute_without_session(|ctx| ctx.get_random(16))
         .expect("Failed to get random bytes");
 }
+
+#[test]
+fn get_or_create_persistent_primary_test() {
+    let persistent = PersistentTpmHandle::new(u32::from_be_bytes([0x81, 0x00, 0x00, 0x10]))
+        .expect("Failed to create persistent tpm handle");
+    let mut context = create_ctx_without_session();
+    context.set_sessions((Some(AuthSession::Password), None, None));
+
+    let first_handle = get_or_create_persistent_primary(
+        &mut context,
+        Provision::Owner,
+        persistent,
+        decryption_key_pub(),
+    )
+    .expect("Failed to create persistent primary");
+    let (first_public, first_name, _) = context
+        .read_public(first_handle)
+        .expect("Failed to read public of the persistent primary");
+
+    // The second call must find the existing object instead of re-creating it.
+    let second_handle = get_or_create_persistent_primary(
+        &mut context,
+        Provision::Owner,
+        persistent,
+        decryption_key_pub(),
+    )
+    .expect("Failed to get persistent primary");
+    let (second_public, second_name, _) = context
+        .read_public(second_handle)
+        .expect("Failed to read public of the persistent primary");
+    assert_eq!(first_public, second_public);
+    assert_eq!(first_name, second_name);
+
+    // A different template replaces the persistent object.
+    let third_handle = get_or_create_persistent_primary(
+        &mut context,
+        Provision::Owner,
+        persistent,
+        signing_key_pub(),
+    )
+    .expect("Failed to re-create persistent primary");
+    let (third_public, third_name, _) = context
+        .read_public(third_handle)
+        .expect("Failed to read public of the persistent primary");
+    assert_ne!(first_name, third_name);
+    assert_eq!(
+        third_public.object_attributes(),
+        signing_key_pub().object_attributes()
+    );
+
+    let _ = context
+        .evict_control(
+            Provision::Owner,
+            third_handle.into(),
+            Persistent::Persistent(persistent),
+        )
+        .expect("Failed to evict persistent primary");
+}
@@ -322,3 +322,17 @@ fn test_attributes_builder() {
             .0
     );
 }
+
+#[test]
+fn test_nv_index_attributes_display() {
+    let nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .with_nv_index_type(NvIndexType::Counter)
+        .build()
+        .expect("Failed to create nv index attributes");
+    assert_eq!(
+        "OWNERWRITE | OWNERREAD (TPM_NT: Counter)",
+        nv_index_attributes.to_string()
+    );
+}
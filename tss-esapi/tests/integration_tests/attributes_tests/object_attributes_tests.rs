@@ -0,0 +1,39 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use tss_esapi::attributes::ObjectAttributes;
+
+#[test]
+fn test_display_restricted_signing_key() {
+    let object_attributes = ObjectAttributes::builder()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .with_restricted(true)
+        .build()
+        .expect("Failed to build object attributes");
+
+    let formatted = object_attributes.to_string();
+    assert!(formatted.contains("sign"));
+    assert!(formatted.contains("restricted"));
+    assert!(!formatted.contains("decrypt"));
+    assert_eq!(
+        "fixedTPM | fixedParent | sensitiveDataOrigin | userWithAuth | restricted | sign",
+        formatted
+    );
+}
+
+#[test]
+fn test_display_without_attributes() {
+    assert_eq!("none", ObjectAttributes(0).to_string());
+}
+
+#[test]
+fn test_debug_contains_raw_value_and_names() {
+    let object_attributes = ObjectAttributes::new_fixed_parent_key();
+    assert_eq!(
+        "ObjectAttributes(0x00030072: fixedTPM | fixedParent | sensitiveDataOrigin | userWithAuth | restricted | decrypt)",
+        format!("{:?}", object_attributes)
+    );
+}
@@ -239,3 +239,20 @@ fn test_session_attributes_builder() {
         "SessionAttributes builder did not produce the expected session attributes mask value"
     )
 }
+
+#[test]
+fn test_session_attributes_display() {
+    let (session_attributes, _) = SessionAttributesBuilder::new()
+        .with_continue_session(true)
+        .with_decrypt(true)
+        .with_encrypt(true)
+        .build();
+    assert_eq!(
+        "continueSession | decrypt | encrypt",
+        session_attributes.to_string()
+    );
+    assert_eq!(
+        "SessionAttributes(0x61: continueSession | decrypt | encrypt)",
+        format!("{:?}", session_attributes)
+    );
+}
@@ -0,0 +1,114 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+use tss_esapi::tcti_ldr::{MssimConfig, ServerAddress, TctiNameConf};
+
+/// Simulator command used by the TCTI to send a TPM command.
+const TPM_SEND_COMMAND: u32 = 8;
+
+/// Simulator which records the TPM commands it receives and answers
+/// them with predefined responses, without a TPM behind it.
+///
+/// The platform commands sent when the TCTI is initialized
+/// are acknowledged, so that a context can be created.
+pub struct MockSimulator {
+    port: u16,
+    commands: Receiver<Vec<u8>>,
+}
+
+impl MockSimulator {
+    /// Starts a simulator answering the TPM commands, in order, with
+    /// `responses`. Once all the responses have been used the
+    /// connection is closed.
+    pub fn start(responses: Vec<Vec<u8>>) -> Self {
+        let (command_listener, platform_listener) = (0..100)
+            .find_map(|_| {
+                let command_listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).ok()?;
+                let port = command_listener.local_addr().ok()?.port().checked_add(1)?;
+                let platform_listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok()?;
+                Some((command_listener, platform_listener))
+            })
+            .expect("Failed to bind two consecutive ports");
+        let port = command_listener.local_addr().unwrap().port();
+
+        let _ = thread::spawn(move || {
+            for mut stream in platform_listener.incoming().flatten() {
+                let mut platform_command = [0u8; 4];
+                while stream.read_exact(&mut platform_command).is_ok() {
+                    if stream.write_all(&[0u8; 4]).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let (sender, commands) = mpsc::channel();
+        let _ = thread::spawn(move || {
+            let mut responses = responses.into_iter();
+            for stream in command_listener.incoming().flatten() {
+                if MockSimulator::serve(stream, &mut responses, &sender).is_err() {
+                    break;
+                }
+            }
+        });
+
+        MockSimulator { port, commands }
+    }
+
+    /// Returns the TCTI to connect to the simulator.
+    pub fn tcti(&self) -> TctiNameConf {
+        TctiNameConf::Mssim(
+            MssimConfig::new(
+                ServerAddress::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+                self.port,
+            )
+            .expect("Failed to create Mssim config"),
+        )
+    }
+
+    /// Returns the next TPM command received by the simulator.
+    pub fn received_command(&self) -> Vec<u8> {
+        self.commands
+            .recv_timeout(Duration::from_secs(10))
+            .expect("The simulator did not receive a command")
+    }
+
+    /// Answers the TPM commands of a connection until it is closed
+    /// or there are no responses left.
+    fn serve(
+        mut stream: TcpStream,
+        responses: &mut impl Iterator<Item = Vec<u8>>,
+        sender: &Sender<Vec<u8>>,
+    ) -> std::io::Result<()> {
+        let mut simulator_command = [0u8; 4];
+        while stream.read_exact(&mut simulator_command).is_ok() {
+            if u32::from_be_bytes(simulator_command) != TPM_SEND_COMMAND {
+                // The session is ending.
+                break;
+            }
+            let mut locality = [0u8; 1];
+            let mut size = [0u8; 4];
+            stream.read_exact(&mut locality)?;
+            stream.read_exact(&mut size)?;
+            let mut command = vec![0u8; u32::from_be_bytes(size) as usize];
+            stream.read_exact(&mut command)?;
+            let _ = sender.send(command);
+
+            let response = match responses.next() {
+                Some(response) => response,
+                None => break,
+            };
+            stream.write_all(&(response.len() as u32).to_be_bytes())?;
+            stream.write_all(&response)?;
+            // The response is followed by an acknowledgement.
+            stream.write_all(&[0u8; 4])?;
+        }
+        Ok(())
+    }
+}
@@ -3,12 +3,13 @@
 use std::{
     convert::{TryFrom, TryInto},
     env,
+    io::{Read, Write},
+    net::TcpStream,
     str::FromStr,
     sync::Once,
 };
 
 use tss_esapi::{
-    abstraction::{cipher::Cipher, pcr::PcrData},
     attributes::ObjectAttributes,
     attributes::{NvIndexAttributesBuilder, ObjectAttributesBuilder, SessionAttributesBuilder},
     constants::SessionType,
@@ -18,22 +19,28 @@ use tss_esapi::{
         algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
         key_bits::RsaKeyBits,
         key_bits::{AesKeyBits, Sm4KeyBits},
-        reserved_handles::{Hierarchy, NvAuth, Provision},
-        session_handles::PolicySession,
+        reserved_handles::{NvAuth, Provision},
     },
     structures::{
         Digest, EccParameter, EccPoint, EccScheme, EccSignature, HashAgile, HashScheme, HmacScheme,
-        KeyDerivationFunctionScheme, KeyedHashScheme, MaxBuffer, MaxNvBuffer, NvPublicBuilder,
-        PcrSelectionListBuilder, PcrSlot, Public, PublicBuilder, PublicEccParameters, PublicKeyRsa,
-        PublicKeyedHashParameters, PublicRsaParameters, RsaExponent, RsaScheme, RsaSignature,
-        Sensitive, Signature, SymmetricCipherParameters, SymmetricDefinition,
-        SymmetricDefinitionObject,
+        KeyDerivationFunctionScheme, KeyedHashScheme, MaxNvBuffer, NvPublicBuilder, Public,
+        PublicBuilder, PublicEccParameters, PublicKeyRsa, PublicKeyedHashParameters,
+        PublicRsaParameters, RsaExponent, RsaScheme, RsaSignature, Sensitive, Signature,
+        SymmetricCipherParameters, SymmetricDefinition, SymmetricDefinitionObject,
     },
-    tcti_ldr::TctiNameConf,
+    tcti_ldr::{TctiContext, TctiNameConf},
     utils, Context,
 };
 
+#[cfg(feature = "abstraction")]
+use tss_esapi::{
+    abstraction::pcr::PcrData,
+    interface_types::{reserved_handles::Hierarchy, session_handles::PolicySession},
+    structures::{MaxBuffer, PcrSelectionListBuilder, PcrSlot},
+};
+
 mod marshall;
+mod mock_simulator;
 mod serde;
 mod tpm2b_types_equality_checks;
 mod tpma_types_equality_checks;
@@ -42,6 +49,7 @@ mod tpms_types_equality_checks;
 mod tpmt_types_equality_checks;
 pub use self::serde::*;
 pub use marshall::*;
+pub use mock_simulator::*;
 pub use tpm2b_types_equality_checks::*;
 pub use tpma_types_equality_checks::*;
 pub use tpml_types_equality_checks::*;
@@ -195,12 +203,49 @@ pub fn create_tcti() -> TctiNameConf {
     }
 }
 
+/// Platform command turning the simulated TPM on.
+const TPM_SIGNAL_POWER_ON: u32 = 1;
+/// Platform command turning the simulated TPM off.
+const TPM_SIGNAL_POWER_OFF: u32 = 2;
+
+/// Power cycles the TPM simulator through its platform port, after
+/// which the TPM has to be started up again.
+///
+/// Returns `false` without doing anything if the tests are not run
+/// directly against a simulator, e.g. through a resource manager.
+#[allow(dead_code)]
+pub fn power_cycle_simulator() -> bool {
+    let config = match create_tcti() {
+        TctiNameConf::Mssim(config) => config,
+        _ => return false,
+    };
+    let mut platform = TcpStream::connect((config.host().to_string(), config.platform_port()))
+        .expect("Failed to connect to the simulator platform port");
+    for signal in [TPM_SIGNAL_POWER_OFF, TPM_SIGNAL_POWER_ON] {
+        platform
+            .write_all(&signal.to_be_bytes())
+            .expect("Failed to send a platform command");
+        let mut response = [0u8; 4];
+        platform
+            .read_exact(&mut response)
+            .expect("Failed to receive the platform response");
+        assert_eq!(response, [0u8; 4], "The platform command failed");
+    }
+    true
+}
+
 #[allow(dead_code)]
 pub fn create_ctx_without_session() -> Context {
     let tcti = create_tcti();
     Context::new(tcti).unwrap()
 }
 
+#[allow(dead_code)]
+pub fn create_ctx_with_intercepted_tcti() -> Context {
+    let tcti = create_tcti();
+    Context::new_with_intercepted_tcti(TctiContext::initialize(tcti).unwrap()).unwrap()
+}
+
 #[allow(dead_code)]
 pub fn create_ctx_with_session() -> Context {
     let mut ctx = create_ctx_without_session();
@@ -232,9 +277,7 @@ pub fn create_ctx_with_session() -> Context {
 #[allow(dead_code)]
 pub fn decryption_key_pub() -> Public {
     utils::create_restricted_decryption_rsa_public(
-        Cipher::aes_256_cfb()
-            .try_into()
-            .expect("Failed to create symmetric object"),
+        SymmetricDefinitionObject::AES_256_CFB,
         RsaKeyBits::Rsa2048,
         RsaExponent::default(),
     )
@@ -261,6 +304,7 @@ pub fn signing_key_pub() -> Public {
     .expect("Failed to create an unrestricted signing rsa public structure")
 }
 
+#[cfg(feature = "abstraction")]
 #[allow(dead_code)]
 pub fn get_pcr_policy_digest(
     context: &mut Context,
@@ -356,7 +400,7 @@ pub fn get_pcr_policy_digest(
             .expect("Failed to convert auth session into policy session");
         // There should be no errors setting pcr policy for trial session.
         context
-            .policy_pcr(trial_policy_session, hashed_data, pcr_selection_list)
+            .policy_pcr(trial_policy_session, Some(hashed_data), pcr_selection_list)
             .expect("Failed to call policy pcr");
 
         // There is now a policy digest that can be retrieved and used.
@@ -400,7 +444,7 @@ pub fn get_pcr_policy_digest(
             .expect("Failed to convert auth session into policy session");
         // There should be no errors setting pcr policy for trial session.
         context
-            .policy_pcr(policy_session, hashed_data, pcr_selection_list)
+            .policy_pcr(policy_session, Some(hashed_data), pcr_selection_list)
             .expect("Failed to call policy_pcr");
 
         // There is now a policy digest that can be retrieved and used.
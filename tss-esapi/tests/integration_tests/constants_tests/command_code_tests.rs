@@ -225,3 +225,21 @@ fn test_marshall_unmarshall() {
     crate::common::check_marshall_unmarshall(&cc);
     crate::common::check_marshall_unmarshall_offset(&cc);
 }
+
+#[test]
+fn test_display() {
+    assert_eq!("TPM2_Create", CommandCode::Create.to_string());
+    assert_eq!("TPM2_CreatePrimary", CommandCode::CreatePrimary.to_string());
+    assert_eq!("TPM2_Unseal", CommandCode::Unseal.to_string());
+    assert_eq!("TPM2_NV_ChangeAuth", CommandCode::NvChangeAuth.to_string());
+    assert_eq!("TPM2_PCR_Extend", CommandCode::PcrExtend.to_string());
+    assert_eq!(
+        "TPM2_PolicyCommandCode",
+        CommandCode::PolicyCommandCode.to_string()
+    );
+    assert_eq!("TPM2_ECDH_KeyGen", CommandCode::EcdhKeyGen.to_string());
+    assert_eq!(
+        "TPM2_NV_UndefineSpaceSpecial",
+        format!("{}", CommandCode::NvUndefineSpaceSpecial)
+    );
+}
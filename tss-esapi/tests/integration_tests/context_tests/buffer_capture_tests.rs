@@ -0,0 +1,99 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::{create_ctx_with_intercepted_tcti, create_ctx_without_session};
+use std::sync::{Arc, Mutex};
+use tss_esapi::{constants::tss::TPM2_CC_GetRandom, Context, Error, WrapperErrorKind};
+
+type CapturedBuffers = Arc<Mutex<Vec<(Vec<u8>, Vec<u8>)>>>;
+
+fn capture_buffers(context: &mut Context) -> CapturedBuffers {
+    let captured_buffers = CapturedBuffers::default();
+    let hook_buffers = captured_buffers.clone();
+    context
+        .on_command_buffers(Box::new(move |command, response| {
+            hook_buffers
+                .lock()
+                .unwrap()
+                .push((command.to_vec(), response.to_vec()))
+        }))
+        .expect("Failed to set the command buffers hook");
+    captured_buffers
+}
+
+#[test]
+fn test_buffer_capture_get_random() {
+    let mut context = create_ctx_with_intercepted_tcti();
+    let captured_buffers = capture_buffers(&mut context);
+
+    context.set_buffer_redaction(false);
+    context
+        .set_buffer_capture(true)
+        .expect("Failed to set the buffer capture");
+    let random = context.get_random(8).expect("Failed to call get_random");
+    context
+        .set_buffer_capture(false)
+        .expect("Failed to set the buffer capture");
+
+    let captured_buffers = captured_buffers.lock().unwrap();
+    assert_eq!(captured_buffers.len(), 1);
+    let (command, response) = &captured_buffers[0];
+    assert!(!command.is_empty());
+    assert!(!response.is_empty());
+    assert_eq!(command[6..10], TPM2_CC_GetRandom.to_be_bytes());
+    // The response code is success and the random bytes are at the end.
+    assert_eq!(response[6..10], [0; 4]);
+    assert!(response.ends_with(random.as_bytes()));
+}
+
+#[test]
+fn test_buffer_capture_disabled() {
+    let mut context = create_ctx_with_intercepted_tcti();
+    let captured_buffers = capture_buffers(&mut context);
+
+    let _ = context.get_random(8).expect("Failed to call get_random");
+    assert!(captured_buffers.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_buffer_capture_redaction() {
+    let mut context = create_ctx_with_intercepted_tcti();
+    let captured_buffers = capture_buffers(&mut context);
+
+    // The buffers are redacted by default.
+    context
+        .set_buffer_capture(true)
+        .expect("Failed to set the buffer capture");
+    let _ = context.get_random(8).expect("Failed to call get_random");
+    context
+        .set_buffer_capture(false)
+        .expect("Failed to set the buffer capture");
+
+    let captured_buffers = captured_buffers.lock().unwrap();
+    assert_eq!(captured_buffers.len(), 1);
+    let (command, response) = &captured_buffers[0];
+    // Only the headers are kept.
+    assert_eq!(command[6..10], TPM2_CC_GetRandom.to_be_bytes());
+    assert!(command.len() > 10 && command[10..].iter().all(|&byte| byte == 0));
+    assert!(response.len() > 10 && response[10..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_buffer_capture_not_intercepted() {
+    let mut context = create_ctx_without_session();
+    assert_eq!(
+        context.set_buffer_capture(true),
+        Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam))
+    );
+    assert_eq!(
+        context.on_command_buffers(Box::new(|_, _| {})),
+        Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam))
+    );
+}
+
+#[test]
+fn test_buffer_capture_context_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<Context>();
+    #[cfg(feature = "abstraction")]
+    assert_send::<tss_esapi::TransientKeyContext>();
+}
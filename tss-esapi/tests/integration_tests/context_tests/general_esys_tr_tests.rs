@@ -379,6 +379,61 @@ mod test_tr_from_tpm_public {
         assert_eq!(expected_data, actual_data);
     }
 
+    #[test]
+    fn test_tr_from_tpm_public_persistent_key() {
+        let persistent_tpm_handle =
+            PersistentTpmHandle::new(u32::from_be_bytes([0x81, 0x00, 0x00, 0x11]))
+                .expect("Failed to create persistent tpm handle");
+
+        // Make a key persistent using one context.
+        let (expected_public, expected_name) = {
+            let mut context = create_ctx_with_session();
+            let primary_key_handle = context
+                .create_primary(
+                    Hierarchy::Owner,
+                    decryption_key_pub(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .expect("Failed to create primary key")
+                .key_handle;
+            let _ = context
+                .evict_control(
+                    Provision::Owner,
+                    primary_key_handle.into(),
+                    Persistent::Persistent(persistent_tpm_handle),
+                )
+                .expect("Failed to make the primary key persistent");
+            let (public, name, _) = context
+                .read_public(primary_key_handle)
+                .expect("Failed to read public of primary key");
+            (public, name)
+        };
+
+        // Adopt the persistent key in a fresh context.
+        let mut context = create_ctx_without_session();
+        let persistent_key_handle = context
+            .tr_from_tpm_public(TpmHandle::Persistent(persistent_tpm_handle))
+            .expect("Failed to get the persistent key from the TPM");
+        let read_public_result = context.read_public(persistent_key_handle.into());
+
+        context.set_sessions((Some(AuthSession::Password), None, None));
+        let _ = context
+            .evict_control(
+                Provision::Owner,
+                persistent_key_handle,
+                Persistent::Persistent(persistent_tpm_handle),
+            )
+            .expect("Failed to remove the persistent key");
+
+        let (public, name, _) =
+            read_public_result.expect("Failed to read public of persistent key");
+        assert_eq!(public, expected_public);
+        assert_eq!(name, expected_name);
+    }
+
     #[cfg(has_esys_tr_get_tpm_handle)]
     #[test]
     fn test_tr_get_tpm_handle() {
@@ -484,9 +539,21 @@ mod test_tr_serialize_tr_deserialize {
         drop(context);
         // Load handle in a new context
         let mut new_context = create_ctx_without_session();
-        let new_handle = new_context.tr_deserialize(&data)?.into();
+        let new_handle = new_context.tr_deserialize(&data)?;
+        assert_eq!(new_context.tracked_handle_count(), 1);
         // Check it is the same key via the public key included in Public
-        assert_eq!(public, new_context.read_public(new_handle)?);
+        assert_eq!(public, new_context.read_public(new_handle.into())?);
+        // Check that the key can be used through the deserialized handle
+        let _ = new_context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.create(
+                new_handle.into(),
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+        })?;
         Ok(())
     }
 }
@@ -1,2 +1,6 @@
+mod buffer_capture_tests;
 mod general_esys_tr_tests;
+mod reconnect_tests;
+mod sessions_tests;
+mod timeout_tests;
 mod tpm_commands;
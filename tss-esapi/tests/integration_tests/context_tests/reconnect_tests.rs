@@ -0,0 +1,148 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::{create_ctx_without_session, create_tcti, MockSimulator};
+use std::net::{IpAddr, Ipv4Addr};
+use tss_esapi::{
+    constants::PropertyTag,
+    tcti_ldr::{MssimConfig, ServerAddress, TctiNameConf},
+    Context, Error, WrapperErrorKind,
+};
+
+#[cfg(has_esys_tr_get_tpm_handle)]
+#[test]
+fn test_reconnect() {
+    use crate::common::decryption_key_pub;
+    use tss_esapi::interface_types::{reserved_handles::Hierarchy, session_handles::AuthSession};
+
+    let mut context = create_ctx_without_session();
+    let key_handle = context
+        .execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+        })
+        .expect("Failed to create primary key")
+        .key_handle;
+    let tpm_handle = context
+        .tr_get_tpm_handle(key_handle.into())
+        .expect("Failed to get TPM handle");
+    assert_eq!(context.tracked_handle_count(), 1);
+
+    context
+        .reconnect(create_tcti())
+        .expect("Failed to reconnect");
+
+    // The handles of the old connection are forgotten.
+    assert_eq!(context.tracked_handle_count(), 0);
+    let _ = context.get_random(16).expect("Failed to call get_random");
+
+    // Without a resource manager the key is still loaded in the TPM,
+    // so it is tracked again in order to be flushed with the context.
+    let _ = context.tr_from_tpm_public(tpm_handle);
+}
+
+#[test]
+fn test_reconnect_keeps_cached_properties() {
+    let mut context = create_ctx_without_session();
+    let manufacturer = context
+        .get_tpm_property(PropertyTag::Manufacturer)
+        .expect("Failed to get manufacturer");
+    assert!(manufacturer.is_some());
+
+    // Nothing is expected to be listening on this port.
+    let unreachable_tcti = TctiNameConf::Mssim(
+        MssimConfig::new(ServerAddress::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST)), 1)
+            .expect("Failed to create Mssim config"),
+    );
+    let _ = context.reconnect(unreachable_tcti).unwrap_err();
+
+    // Without a connection only the cached properties are available.
+    let _ = context.get_random(16).unwrap_err();
+    assert_eq!(
+        context
+            .get_tpm_property(PropertyTag::Manufacturer)
+            .expect("Failed to get cached manufacturer"),
+        manufacturer
+    );
+
+    context
+        .reconnect(create_tcti())
+        .expect("Failed to reconnect");
+    let _ = context.get_random(16).expect("Failed to call get_random");
+    assert_eq!(
+        context
+            .get_tpm_property(PropertyTag::Manufacturer)
+            .expect("Failed to get manufacturer"),
+        manufacturer
+    );
+}
+
+#[test]
+fn test_reconnect_after_transport_error() {
+    // The simulator closes the connection without answering the command.
+    let simulator = MockSimulator::start(Vec::new());
+    let mut context = Context::new(simulator.tcti()).expect("Failed to create context");
+
+    let error = context.get_random(8).unwrap_err();
+    let _ = simulator.received_command();
+    assert!(error.is_transport_error());
+    assert!(matches!(
+        error,
+        Error::WrapperError(WrapperErrorKind::Transport(_))
+    ));
+    let io_error = std::io::Error::from(error);
+    assert_eq!(
+        io_error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<Error>()),
+        Some(&error)
+    );
+
+    context
+        .reconnect(create_tcti())
+        .expect("Failed to reconnect");
+    let _ = context.get_random(8).expect("Failed to call get_random");
+}
+
+#[cfg(feature = "integration-tests")]
+#[test]
+fn test_tcti_failure_is_not_transport_error() {
+    use std::time::Duration;
+    use tss_esapi::{
+        constants::BaseError,
+        error::ReturnCode,
+        tcti_ldr::{CustomTcti, TctiContext},
+        Result,
+    };
+
+    /// TCTI whose transmit method panics.
+    struct PanickingTcti;
+
+    impl CustomTcti for PanickingTcti {
+        fn transmit(&mut self, _command: &[u8]) -> Result<()> {
+            panic!("The TCTI failed to transmit the command");
+        }
+
+        fn receive(&mut self, _timeout: Option<Duration>) -> Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+    }
+
+    let mut context = Context::new_with_tcti(TctiContext::from_custom(Box::new(PanickingTcti)))
+        .expect("Failed to create context");
+
+    // The panic is reported as a general failure of the TCTI.
+    let error = context.get_random(8).unwrap_err();
+    assert!(!error.is_transport_error());
+    match error {
+        Error::TssError(ReturnCode::Tcti(tcti_return_code)) => {
+            assert_eq!(tcti_return_code.base_error(), BaseError::GeneralFailure)
+        }
+        _ => panic!("Unexpected error: {}", error),
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::create_ctx_without_session;
+use tss_esapi::{
+    constants::SessionType,
+    interface_types::{algorithm::HashingAlgorithm, session_handles::AuthSession},
+    structures::SymmetricDefinition,
+    Error, WrapperErrorKind,
+};
+
+#[test]
+fn try_set_sessions_requires_first_session() {
+    let mut context = create_ctx_without_session();
+    context.set_sessions((Some(AuthSession::Password), None, None));
+
+    assert_eq!(
+        context.try_set_sessions((None, None, None), true),
+        Err(Error::WrapperError(WrapperErrorKind::MissingAuthSession))
+    );
+    // The sessions are not changed when the validation fails.
+    assert_eq!(
+        context.sessions(),
+        (Some(AuthSession::Password), None, None)
+    );
+
+    // No session is needed if the commands do not require authorization.
+    context
+        .try_set_sessions((None, None, None), false)
+        .expect("Failed to set empty sessions");
+    assert_eq!(context.sessions(), (None, None, None));
+}
+
+#[test]
+fn try_set_sessions_rejects_duplicate_sessions() {
+    let mut context = create_ctx_without_session();
+    let session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Hmac,
+            SymmetricDefinition::AES_256_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .expect("Failed to create session");
+
+    assert_eq!(
+        context.try_set_sessions((session, session, None), true),
+        Err(Error::WrapperError(WrapperErrorKind::InconsistentParams))
+    );
+
+    context
+        .try_set_sessions(
+            (
+                Some(AuthSession::Password),
+                Some(AuthSession::Password),
+                session,
+            ),
+            true,
+        )
+        .expect("Failed to set sessions");
+    assert_eq!(
+        context.sessions(),
+        (
+            Some(AuthSession::Password),
+            Some(AuthSession::Password),
+            session
+        )
+    );
+}
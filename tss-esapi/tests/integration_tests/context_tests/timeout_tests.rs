@@ -0,0 +1,194 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::common::{create_ctx_with_intercepted_tcti, create_ctx_without_session};
+use std::time::Duration;
+use tss_esapi::{Error, WrapperErrorKind};
+
+#[test]
+fn test_execute_with_timeout_not_intercepted() {
+    let mut context = create_ctx_without_session();
+    assert_eq!(
+        context
+            .execute_with_timeout(Duration::from_secs(60), |ctx| ctx.get_random(8))
+            .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::UnsupportedParam)
+    );
+}
+
+#[test]
+fn test_execute_with_timeout() {
+    let mut context = create_ctx_with_intercepted_tcti();
+    let random = context
+        .execute_with_timeout(Duration::from_secs(60), |ctx| ctx.get_random(8))
+        .expect("Failed to call get_random with a timeout");
+    assert_eq!(random.len(), 8);
+
+    // The timeout only applies to the closure.
+    let _ = context.get_random(8).expect("Failed to call get_random");
+}
+
+/// Tests using a mocked TPM, which needs custom TCTIs.
+#[cfg(feature = "integration-tests")]
+mod mocked_tpm_tests {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+    use tss_esapi::{
+        constants::tss::TPM2_CC_FlushContext,
+        interface_types::algorithm::HashingAlgorithm,
+        tcti_ldr::{CustomTcti, TctiContext},
+        Context, Error, Result, WrapperErrorKind,
+    };
+
+    /// Response to GetRandom with 8 random bytes.
+    const GET_RANDOM_RESPONSE: [u8; 20] = [
+        0x80, 0x01, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x01, 0x02, 0x03,
+        0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+
+    /// Response to HashSequenceStart with the sequence handle 0x80000000.
+    const HASH_SEQUENCE_START_RESPONSE: [u8; 14] = [
+        0x80, 0x01, 0x00, 0x00, 0x00, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+    ];
+
+    /// Response to FlushContext.
+    const FLUSH_CONTEXT_RESPONSE: [u8; 10] =
+        [0x80, 0x01, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x00];
+
+    type ReceivedCommands = Arc<Mutex<Vec<Vec<u8>>>>;
+
+    /// TCTI which records the commands it receives and answers
+    /// them, in order, with predefined responses after a delay.
+    struct DelayedTcti {
+        responses: VecDeque<(Duration, Vec<u8>)>,
+        pending_response: Option<(Instant, Vec<u8>)>,
+        commands: ReceivedCommands,
+    }
+
+    impl DelayedTcti {
+        fn context(responses: Vec<(Duration, Vec<u8>)>) -> (Context, ReceivedCommands) {
+            let commands = ReceivedCommands::default();
+            let tcti = DelayedTcti {
+                responses: responses.into(),
+                pending_response: None,
+                commands: commands.clone(),
+            };
+            let context =
+                Context::new_with_intercepted_tcti(TctiContext::from_custom(Box::new(tcti)))
+                    .expect("Failed to create context");
+            (context, commands)
+        }
+    }
+
+    impl CustomTcti for DelayedTcti {
+        fn transmit(&mut self, command: &[u8]) -> Result<()> {
+            self.commands.lock().unwrap().push(command.to_vec());
+            let (delay, response) = self
+                .responses
+                .pop_front()
+                .ok_or(Error::WrapperError(WrapperErrorKind::InternalError))?;
+            self.pending_response = Some((Instant::now() + delay, response));
+            Ok(())
+        }
+
+        fn receive(&mut self, timeout: Option<Duration>) -> Result<Option<Vec<u8>>> {
+            let (ready_at, _) = self
+                .pending_response
+                .as_ref()
+                .ok_or(Error::WrapperError(WrapperErrorKind::InternalError))?;
+            let remaining = ready_at.saturating_duration_since(Instant::now());
+            match timeout {
+                Some(timeout) if timeout < remaining => {
+                    thread::sleep(timeout);
+                    Ok(None)
+                }
+                _ => {
+                    thread::sleep(remaining);
+                    Ok(self.pending_response.take().map(|(_, response)| response))
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_with_timeout_without_response() {
+        let (mut context, commands) = DelayedTcti::context(vec![(
+            Duration::from_secs(60),
+            GET_RANDOM_RESPONSE.to_vec(),
+        )]);
+
+        let start = Instant::now();
+        assert_eq!(
+            context
+                .execute_with_timeout(Duration::from_millis(200), |ctx| ctx.get_random(8))
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::Timeout)
+        );
+        // The late response is waited for once more before the connection
+        // is closed, but not until the TPM responds.
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(commands.lock().unwrap().len(), 1);
+
+        // The connection is closed after a timeout.
+        let _ = context.get_random(8).unwrap_err();
+    }
+
+    #[test]
+    fn test_execute_with_timeout_delayed_response() {
+        let (mut context, commands) = DelayedTcti::context(vec![(
+            Duration::from_millis(100),
+            GET_RANDOM_RESPONSE.to_vec(),
+        )]);
+
+        let random = context
+            .execute_with_timeout(Duration::from_secs(60), |ctx| ctx.get_random(8))
+            .expect("Failed to call get_random with a timeout");
+        assert_eq!(random.as_bytes(), &GET_RANDOM_RESPONSE[12..]);
+        assert_eq!(commands.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_execute_with_elapsed_timeout() {
+        let (mut context, commands) =
+            DelayedTcti::context(vec![(Duration::ZERO, GET_RANDOM_RESPONSE.to_vec())]);
+
+        assert_eq!(
+            context
+                .execute_with_timeout(Duration::from_secs(0), |ctx| ctx.get_random(8))
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::Timeout)
+        );
+        // The command is not sent once the timeout has elapsed.
+        assert!(commands.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_execute_with_timeout_flushes_handles() {
+        let (mut context, commands) = DelayedTcti::context(vec![
+            (Duration::ZERO, HASH_SEQUENCE_START_RESPONSE.to_vec()),
+            (Duration::from_millis(750), GET_RANDOM_RESPONSE.to_vec()),
+            (Duration::ZERO, FLUSH_CONTEXT_RESPONSE.to_vec()),
+        ]);
+
+        let _ = context
+            .hash_sequence_start(HashingAlgorithm::Sha256, None)
+            .expect("Failed to start hash sequence");
+        assert_eq!(
+            context
+                .execute_with_timeout(Duration::from_millis(500), |ctx| ctx.get_random(8))
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::Timeout)
+        );
+
+        // Once the TPM has responded to the command that timed out,
+        // the sequence object is flushed.
+        let commands = commands.lock().unwrap();
+        assert_eq!(commands.len(), 3);
+        let flush_command = &commands[2];
+        assert_eq!(flush_command[6..10], TPM2_CC_FlushContext.to_be_bytes());
+        assert_eq!(flush_command[10..14], [0x80, 0x00, 0x00, 0x00]);
+    }
+}
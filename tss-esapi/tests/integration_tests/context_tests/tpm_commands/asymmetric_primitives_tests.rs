@@ -106,3 +106,26 @@ mod test_rsa_encrypt_decrypt {
         assert_eq!(z_point.x().as_bytes(), param.x().as_bytes());
     }
 }
+
+mod test_ecc_parameters {
+    use crate::common::create_ctx_without_session;
+    use tss_esapi::interface_types::ecc::EccCurve;
+
+    #[test]
+    fn test_nist_p256_parameters() {
+        let mut context = create_ctx_without_session();
+        let parameters = context
+            .ecc_parameters(EccCurve::NistP256)
+            .expect("Failed to get the parameters of NIST P-256");
+
+        let expected_p = [
+            0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        assert_eq!(parameters.curve(), EccCurve::NistP256);
+        assert_eq!(parameters.key_size(), 256);
+        assert_eq!(parameters.p().as_bytes(), expected_p);
+        assert_eq!(parameters.generator().x(), parameters.g_x());
+    }
+}
@@ -220,4 +220,63 @@ mod test_quote {
         assert_eq!(attest.attestation_type(), AttestationType::Creation);
         assert!(matches!(attest.attested(), AttestInfo::Creation { .. }));
     }
+
+    #[test]
+    fn certify_creation_of_primary_key_creation_data() {
+        let mut context = create_ctx_with_session();
+
+        let sign_key_handle = context
+            .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+            .unwrap()
+            .key_handle;
+
+        let create_result = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        // The creation hash is the digest of the creation data.
+        assert!(!create_result.creation_hash.is_empty());
+        use sha2::Digest as _;
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(
+            create_result
+                .creation_data
+                .marshall()
+                .expect("Failed to marshall creation data"),
+        );
+        assert_eq!(
+            create_result.creation_hash.as_bytes(),
+            hasher.finalize().as_slice()
+        );
+
+        let (attest, _) = context
+            .execute_with_sessions((Some(AuthSession::Password), None, None), |ctx| {
+                ctx.certify_creation(
+                    sign_key_handle,
+                    create_result.key_handle.into(),
+                    Data::default(),
+                    create_result.creation_hash.clone(),
+                    SignatureScheme::Null,
+                    create_result.creation_ticket,
+                )
+            })
+            .expect("Failed to certify object handle creation");
+
+        let (_, expected_name, _) = context
+            .read_public(create_result.key_handle)
+            .expect("Failed to read public part of the created key");
+        if let AttestInfo::Creation { info } = attest.attested() {
+            assert_eq!(info.creation_hash(), &create_result.creation_hash);
+            assert_eq!(info.object_name(), &expected_name);
+        } else {
+            panic!("Attested data was not creation data");
+        }
+    }
 }
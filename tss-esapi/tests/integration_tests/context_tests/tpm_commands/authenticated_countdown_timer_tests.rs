@@ -1,2 +1,106 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_act_set_timeout {
+    use crate::common::create_ctx_without_session;
+    use tss_esapi::{
+        constants::{tss::TPM2_RH_ACT_0, CapabilityType},
+        handles::{AuthHandle, PermanentTpmHandle},
+        interface_types::session_handles::AuthSession,
+        structures::CapabilityData,
+        Error, WrapperErrorKind,
+    };
+
+    #[test]
+    fn test_act_set_timeout() {
+        let mut context = create_ctx_without_session();
+        let act_handle = AuthHandle::act(0).expect("Failed to create handle for ACT_0");
+        let act_tpm_handle =
+            PermanentTpmHandle::new(TPM2_RH_ACT_0).expect("Failed to create TPM handle for ACT_0");
+
+        let result = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.act_set_timeout(act_handle, 100)
+        });
+        // ACT availability is TPM dependent.
+        if let Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam)) = result {
+            return;
+        }
+        result.expect("Failed to set ACT timeout");
+
+        let (capability_data, _) = context
+            .get_capability(CapabilityType::Act, TPM2_RH_ACT_0, 1)
+            .expect("Failed to get ACT capability");
+        let remaining_time = match capability_data {
+            CapabilityData::ActData(act_data_list) => act_data_list
+                .find(act_tpm_handle)
+                .expect("ACT_0 was missing from the capability data")
+                .timeout(),
+            _ => panic!("Got wrong type of capability data: {:?}", capability_data),
+        };
+        assert!(remaining_time > 0 && remaining_time <= 100);
+
+        // Stop the countdown
+        context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.act_set_timeout(act_handle, 0)
+            })
+            .expect("Failed to stop the ACT countdown");
+    }
+
+    #[test]
+    fn test_act_set_timeout_invalid_handle() {
+        let mut context = create_ctx_without_session();
+        assert_eq!(
+            Err(Error::WrapperError(WrapperErrorKind::InvalidParam)),
+            context.execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.act_set_timeout(AuthHandle::Owner, 100)
+            })
+        );
+    }
+
+    #[test]
+    fn test_act_status() {
+        let mut context = create_ctx_without_session();
+        let act_handle = AuthHandle::act(0).expect("Failed to create handle for ACT_0");
+
+        let result = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.act_set_timeout(act_handle, 100)
+        });
+        // ACT availability is TPM dependent.
+        if let Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam)) = result {
+            assert_eq!(
+                context.act_status(act_handle),
+                Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam))
+            );
+            return;
+        }
+        result.expect("Failed to set ACT timeout");
+
+        let first_status = context
+            .act_status(act_handle)
+            .expect("Failed to read ACT status");
+        std::thread::sleep(std::time::Duration::from_millis(1500));
+        let second_status = context
+            .act_status(act_handle)
+            .expect("Failed to read ACT status");
+
+        // Stop the countdown
+        context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.act_set_timeout(act_handle, 0)
+            })
+            .expect("Failed to stop the ACT countdown");
+
+        assert!(first_status.timeout() > 0 && first_status.timeout() <= 100);
+        assert!(second_status.timeout() <= first_status.timeout());
+        assert!(!second_status.attributes().signaled());
+    }
+
+    #[test]
+    fn test_act_status_invalid_handle() {
+        let mut context = create_ctx_without_session();
+        assert_eq!(
+            Err(Error::WrapperError(WrapperErrorKind::InvalidParam)),
+            context.act_status(AuthHandle::Owner)
+        );
+    }
+}
@@ -1,12 +1,47 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 mod test_get_capability {
-    use crate::common::create_ctx_without_session;
+    use crate::common::{
+        create_ctx_with_intercepted_tcti, create_ctx_without_session, decryption_key_pub,
+        MockSimulator,
+    };
+    use std::{
+        convert::TryFrom,
+        sync::{Arc, Mutex},
+    };
     use tss_esapi::{
-        constants::{tss::TPM2_PT_VENDOR_STRING_1, CapabilityType, PropertyTag},
+        constants::{
+            tss::{TPM2_CC_GetCapability, TPM2_CAP_TPM_PROPERTIES, TPM2_PT_VENDOR_STRING_1},
+            AlgorithmIdentifier, CapabilityType, CommandCode, HandleRange, PropertyTag,
+        },
+        handles::{PersistentTpmHandle, TpmHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            data_handles::Persistent,
+            ecc::EccCurve,
+            reserved_handles::{Hierarchy, Provision},
+            session_handles::AuthSession,
+        },
         structures::CapabilityData,
+        traits::UnMarshall,
+        Context,
     };
 
+    /// Captures the commands sent to the TPM from now on.
+    fn capture_commands(context: &mut Context) -> Arc<Mutex<Vec<Vec<u8>>>> {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let hook_commands = commands.clone();
+        context
+            .on_command_buffers(Box::new(move |command, _response| {
+                hook_commands.lock().unwrap().push(command.to_vec())
+            }))
+            .expect("Failed to set the command buffers hook");
+        context
+            .set_buffer_capture(true)
+            .expect("Failed to enable the buffer capture");
+        commands
+    }
+
     #[test]
     fn test_get_capability() {
         let mut context = create_ctx_without_session();
@@ -21,6 +56,24 @@ mod test_get_capability {
         };
     }
 
+    #[test]
+    fn test_get_capability_raw() {
+        let mut context = create_ctx_without_session();
+        let (capability_data, more_data) = context
+            .get_capability(CapabilityType::TpmProperties, TPM2_PT_VENDOR_STRING_1, 4)
+            .expect("Failed to call get_capability");
+        let (raw_capability_data, raw_more_data) = context
+            .get_capability_raw(TPM2_CAP_TPM_PROPERTIES, TPM2_PT_VENDOR_STRING_1, 4)
+            .expect("Failed to call get_capability_raw");
+
+        assert_eq!(more_data, raw_more_data);
+        assert_eq!(
+            capability_data,
+            CapabilityData::unmarshall(&raw_capability_data)
+                .expect("Failed to unmarshall raw capability data")
+        );
+    }
+
     #[test]
     fn test_get_tpm_property() {
         let mut context = create_ctx_without_session();
@@ -37,4 +90,264 @@ mod test_get_capability {
             .expect("The TPM did not have a value for the Year property tag");
         assert_ne!(year, 0);
     }
+
+    #[test]
+    fn test_vendor_strings() {
+        let mut context = create_ctx_without_session();
+
+        let vendor_string = context
+            .vendor_strings()
+            .expect("Failed to call vendor_strings");
+        assert!(!vendor_string.is_empty());
+        assert!(!vendor_string.ends_with('\0'));
+        assert!(vendor_string.is_ascii());
+    }
+
+    #[test]
+    fn test_manufacturer() {
+        let mut context = create_ctx_without_session();
+
+        let manufacturer = context.manufacturer().expect("Failed to call manufacturer");
+        assert!(!manufacturer.is_empty());
+        assert!(manufacturer.len() <= 4);
+        assert!(manufacturer.is_ascii());
+    }
+
+    #[test]
+    fn test_resource_budget() {
+        let mut context = create_ctx_without_session();
+
+        let budget = context
+            .resource_budget()
+            .expect("Failed to call resource_budget");
+        assert_ne!(budget.transient_available(), 0);
+
+        let key_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.create_primary(
+                    Hierarchy::Owner,
+                    decryption_key_pub(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            })
+            .expect("Failed to create primary key")
+            .key_handle;
+
+        let loaded_budget = context
+            .resource_budget()
+            .expect("Failed to call resource_budget");
+        assert!(loaded_budget.transient_available() < budget.transient_available());
+
+        context
+            .flush_context(key_handle.into())
+            .expect("Failed to flush key");
+    }
+
+    #[test]
+    fn test_get_algorithms() {
+        let mut context = create_ctx_without_session();
+
+        let algorithms = context
+            .get_algorithms()
+            .expect("Failed to call get_algorithms");
+        assert!(!algorithms.is_empty());
+        assert!(algorithms
+            .iter()
+            .any(|algorithm| algorithm.algorithm_identifier() == AlgorithmIdentifier::Sha256));
+    }
+
+    #[test]
+    fn test_get_commands() {
+        let mut context = create_ctx_without_session();
+
+        let commands = context.get_commands().expect("Failed to call get_commands");
+        assert!(!commands.is_empty());
+        assert!(commands.iter().any(|attributes| {
+            !attributes.is_vendor_specific()
+                && CommandCode::try_from(u32::from(attributes.command_index()))
+                    == Ok(CommandCode::GetCapability)
+        }));
+    }
+
+    #[test]
+    fn test_pp_commands() {
+        let mut context = create_ctx_without_session();
+
+        let pp_commands = context.pp_commands().expect("Failed to call pp_commands");
+        // The list is sorted by command code and may be empty.
+        assert!(pp_commands
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test]
+    fn test_audit_commands() {
+        let mut context = create_ctx_without_session();
+
+        let audit_commands = context
+            .audit_commands()
+            .expect("Failed to call audit_commands");
+        assert!(audit_commands
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test]
+    fn test_get_pcr_banks() {
+        let mut context = create_ctx_without_session();
+
+        let pcr_banks = context
+            .get_pcr_banks()
+            .expect("Failed to call get_pcr_banks");
+        assert!(!pcr_banks.is_empty());
+        assert!(pcr_banks.contains(&HashingAlgorithm::Sha256));
+    }
+
+    #[test]
+    fn test_supported_ecc_curves() {
+        let mut context = create_ctx_without_session();
+
+        let ecc_curves = context
+            .supported_ecc_curves()
+            .expect("Failed to call supported_ecc_curves");
+        assert!(ecc_curves.contains(&EccCurve::NistP256));
+    }
+
+    #[test]
+    fn test_supported_ecc_curves_cached() {
+        let mut context = create_ctx_with_intercepted_tcti();
+
+        let ecc_curves = context
+            .supported_ecc_curves()
+            .expect("Failed to call supported_ecc_curves");
+        let commands = capture_commands(&mut context);
+        let cached_ecc_curves = context
+            .supported_ecc_curves()
+            .expect("Failed to call supported_ecc_curves");
+        assert_eq!(ecc_curves, cached_ecc_curves);
+        // The ECC curves are cached, so no command was sent to the TPM.
+        assert!(commands.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_supported_ecc_curves_unknown_last_curve() {
+        // The first page ends with a curve that is not known by the crate.
+        let simulator = MockSimulator::start(vec![
+            vec![
+                0x80, 0x01, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+                0x08, 0x00, 0x00, 0x00, 0x02, 0x00, 0x03, 0x0f, 0xff,
+            ],
+            vec![
+                0x80, 0x01, 0x00, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04,
+            ],
+        ]);
+        let mut context = Context::new(simulator.tcti()).expect("Failed to create context");
+
+        let ecc_curves = context
+            .supported_ecc_curves()
+            .expect("Failed to call supported_ecc_curves");
+        assert_eq!(ecc_curves, vec![EccCurve::NistP256, EccCurve::NistP384]);
+
+        let _ = simulator.received_command();
+        let command = simulator.received_command();
+        // The second page starts after the unknown curve.
+        assert_eq!(command[14..18], 0x1000u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_refresh_capabilities() {
+        let mut context = create_ctx_with_intercepted_tcti();
+
+        let _ = context
+            .get_pcr_banks()
+            .expect("Failed to call get_pcr_banks");
+        context.refresh_capabilities();
+        let commands = capture_commands(&mut context);
+        let _ = context
+            .get_pcr_banks()
+            .expect("Failed to call get_pcr_banks");
+        // The PCR banks are no longer cached, so they are fetched again.
+        let commands = commands.lock().unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0][6..10], TPM2_CC_GetCapability.to_be_bytes());
+    }
+
+    #[test]
+    fn test_handles_in_range_transient() {
+        let mut context = create_ctx_without_session();
+        context.set_sessions((Some(AuthSession::Password), None, None));
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key")
+            .key_handle;
+
+        let transient_handles = context
+            .handles_in_range(HandleRange::Transient)
+            .expect("Failed to call handles_in_range");
+        context
+            .flush_context(key_handle.into())
+            .expect("Failed to flush the primary key");
+
+        assert!(!transient_handles.is_empty());
+        assert!(transient_handles
+            .iter()
+            .all(|handle| matches!(handle, TpmHandle::Transient(_))));
+    }
+
+    #[test]
+    fn test_handles_in_range_persistent() {
+        let persistent_tpm_handle =
+            PersistentTpmHandle::new(u32::from_be_bytes([0x81, 0x00, 0x00, 0x13]))
+                .expect("Failed to create persistent tpm handle");
+        let mut context = create_ctx_without_session();
+        context.set_sessions((Some(AuthSession::Password), None, None));
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key")
+            .key_handle;
+        let persistent_key_handle = context
+            .evict_control(
+                Provision::Owner,
+                key_handle.into(),
+                Persistent::Persistent(persistent_tpm_handle),
+            )
+            .expect("Failed to make the primary key persistent");
+
+        let persistent_handles = context
+            .handles_in_range(HandleRange::Persistent)
+            .expect("Failed to call handles_in_range");
+        let _ = context
+            .evict_control(
+                Provision::Owner,
+                persistent_key_handle,
+                Persistent::Persistent(persistent_tpm_handle),
+            )
+            .expect("Failed to remove the persistent key");
+        context
+            .flush_context(key_handle.into())
+            .expect("Failed to flush the primary key");
+
+        assert!(persistent_handles.contains(&TpmHandle::Persistent(persistent_tpm_handle)));
+        assert!(persistent_handles
+            .iter()
+            .all(|handle| matches!(handle, TpmHandle::Persistent(_))));
+    }
 }
@@ -110,7 +110,13 @@ mod test_ctx_load {
 
 mod test_flush_context {
     use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
-    use tss_esapi::{interface_types::reserved_handles::Hierarchy, structures::Auth};
+    use tss_esapi::{
+        constants::SessionType,
+        handles::SessionHandle,
+        interface_types::{algorithm::HashingAlgorithm, reserved_handles::Hierarchy},
+        structures::{Auth, SymmetricDefinition},
+        Error, WrapperErrorKind,
+    };
 
     #[test]
     fn test_flush_ctx() {
@@ -170,6 +176,159 @@ mod test_flush_context {
         context.flush_context(prim_key_handle.into()).unwrap();
         let _ = context.read_public(key_handle).unwrap();
     }
+
+    #[test]
+    fn test_use_flushed_session() {
+        let mut context = create_ctx_with_session();
+        let session = context
+            .sessions()
+            .0
+            .expect("Context did not have a session");
+        context
+            .flush_context(SessionHandle::from(session).into())
+            .expect("Failed to flush session");
+
+        // The flushed session is still set in the context.
+        let result = context.create_primary(
+            Hierarchy::Owner,
+            decryption_key_pub(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            result.map(|_| ()),
+            Err(Error::WrapperError(WrapperErrorKind::SessionAlreadyFlushed))
+        );
+
+        // A new session can be started and used.
+        let new_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Hmac,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to start new session");
+        context.set_sessions((new_session, None, None));
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key using new session")
+            .key_handle;
+        context.flush_context(key_handle.into()).unwrap();
+    }
+
+    #[test]
+    fn test_use_flushed_session_in_other_slots() {
+        let mut context = create_ctx_with_session();
+        let session = context.sessions().0;
+        let flushed_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Hmac,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to start session")
+            .expect("Received invalid handle");
+        context
+            .flush_context(SessionHandle::from(flushed_session).into())
+            .expect("Failed to flush session");
+
+        context.set_sessions((session, Some(flushed_session), None));
+        assert_eq!(
+            context.get_random(8).map(|_| ()),
+            Err(Error::WrapperError(WrapperErrorKind::SessionAlreadyFlushed))
+        );
+
+        context.set_sessions((session, None, Some(flushed_session)));
+        assert_eq!(
+            context.get_random(8).map(|_| ()),
+            Err(Error::WrapperError(WrapperErrorKind::SessionAlreadyFlushed))
+        );
+
+        // The sessions that have not been flushed can still be used.
+        context.set_sessions((session, None, None));
+        let _ = context
+            .get_random(8)
+            .expect("Failed to call get_random with the remaining session");
+    }
+
+    #[cfg(has_esys_tr_get_tpm_handle)]
+    #[test]
+    fn test_reuse_flushed_session_handle() {
+        let mut context = create_ctx_with_session();
+        let session = context
+            .sessions()
+            .0
+            .expect("Context did not have a session");
+        let tpm_handle = context
+            .tr_get_tpm_handle(SessionHandle::from(session).into())
+            .expect("Failed to get TPM handle of session");
+        context
+            .flush_context(SessionHandle::from(session).into())
+            .expect("Failed to flush session");
+
+        // The TPM reuses the handle value of the flushed session.
+        let new_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Hmac,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to start new session")
+            .expect("Received invalid handle");
+        assert_eq!(
+            context
+                .tr_get_tpm_handle(SessionHandle::from(new_session).into())
+                .expect("Failed to get TPM handle of new session"),
+            tpm_handle
+        );
+
+        // Only the flushed session is reported as flushed.
+        context.set_sessions((Some(new_session), None, None));
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key using new session")
+            .key_handle;
+        context.flush_context(key_handle.into()).unwrap();
+        context.set_sessions((Some(session), None, None));
+        assert_eq!(
+            context
+                .create_primary(
+                    Hierarchy::Owner,
+                    decryption_key_pub(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .map(|_| ()),
+            Err(Error::WrapperError(WrapperErrorKind::SessionAlreadyFlushed))
+        );
+    }
 }
 
 mod test_evict_control {
@@ -287,3 +446,171 @@ mod test_evict_control {
         assert_ne!(retrieved_persistent_handle, ObjectHandle::None);
     }
 }
+
+mod test_handle_counts {
+    use crate::common::{
+        create_ctx_with_intercepted_tcti, create_ctx_without_session, decryption_key_pub,
+        signing_key_pub,
+    };
+    use std::sync::{Arc, Mutex};
+    use tss_esapi::{
+        constants::tss::{TPM2_CC_CreatePrimary, TPM2_CC_FlushContext, TPM2_CC_Load},
+        handles::KeyHandle,
+        interface_types::{reserved_handles::Hierarchy, session_handles::AuthSession},
+        tss2_esys::TPM2_CC,
+        Context,
+    };
+
+    #[test]
+    fn test_handle_counts_follow_loaded_objects() {
+        let mut context = create_ctx_without_session();
+        assert_eq!(context.tracked_handle_count(), 0);
+        assert_eq!(context.transient_handle_count(), 0);
+
+        let key_handles = context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            (0..2)
+                .map(|_| {
+                    ctx.create_primary(
+                        Hierarchy::Owner,
+                        decryption_key_pub(),
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                    .expect("Failed to create primary key")
+                    .key_handle
+                })
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(context.tracked_handle_count(), key_handles.len());
+        assert_eq!(context.transient_handle_count(), key_handles.len());
+
+        for key_handle in key_handles {
+            context
+                .flush_context(key_handle.into())
+                .expect("Failed to flush key");
+        }
+        assert_eq!(context.tracked_handle_count(), 0);
+        assert_eq!(context.transient_handle_count(), 0);
+    }
+
+    type CapturedBuffers = Arc<Mutex<Vec<(Vec<u8>, Vec<u8>)>>>;
+
+    fn capture_buffers(context: &mut Context) -> CapturedBuffers {
+        let buffers = CapturedBuffers::default();
+        let hook_buffers = buffers.clone();
+        context
+            .on_command_buffers(Box::new(move |command, response| {
+                hook_buffers
+                    .lock()
+                    .unwrap()
+                    .push((command.to_vec(), response.to_vec()))
+            }))
+            .expect("Failed to set the command buffers hook");
+        context.set_buffer_redaction(false);
+        context
+            .set_buffer_capture(true)
+            .expect("Failed to enable the buffer capture");
+        buffers
+    }
+
+    /// Returns the handles in the captured commands with the `command_code`.
+    fn command_handles(buffers: &CapturedBuffers, command_code: TPM2_CC) -> Vec<Vec<u8>> {
+        buffers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(command, _)| command[6..10] == command_code.to_be_bytes())
+            .map(|(command, _)| command[10..14].to_vec())
+            .collect()
+    }
+
+    /// Returns the handles in the responses to the captured commands with the `command_code`.
+    fn response_handles(buffers: &CapturedBuffers, command_code: TPM2_CC) -> Vec<Vec<u8>> {
+        buffers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(command, _)| command[6..10] == command_code.to_be_bytes())
+            .map(|(_, response)| response[10..14].to_vec())
+            .collect()
+    }
+
+    /// Creates a primary key, a child key and a grandchild key.
+    fn create_key_hierarchy(context: &mut Context) -> (KeyHandle, KeyHandle, KeyHandle) {
+        context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            let primary_handle = ctx
+                .create_primary(
+                    Hierarchy::Owner,
+                    decryption_key_pub(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .expect("Failed to create primary key")
+                .key_handle;
+            let child = ctx
+                .create(primary_handle, decryption_key_pub(), None, None, None, None)
+                .expect("Failed to create child key");
+            let child_handle = ctx
+                .load(primary_handle, child.out_private, child.out_public)
+                .expect("Failed to load child key");
+            let grandchild = ctx
+                .create(child_handle, signing_key_pub(), None, None, None, None)
+                .expect("Failed to create grandchild key");
+            let grandchild_handle = ctx
+                .load(child_handle, grandchild.out_private, grandchild.out_public)
+                .expect("Failed to load grandchild key");
+            (primary_handle, child_handle, grandchild_handle)
+        })
+    }
+
+    #[test]
+    fn test_children_are_flushed_before_parents() {
+        let mut context = create_ctx_with_intercepted_tcti();
+        let buffers = capture_buffers(&mut context);
+        let _ = create_key_hierarchy(&mut context);
+        assert_eq!(context.transient_handle_count(), 3);
+
+        let primary_handle = response_handles(&buffers, TPM2_CC_CreatePrimary);
+        let loaded_handles = response_handles(&buffers, TPM2_CC_Load);
+        assert_eq!(primary_handle.len(), 1);
+        assert_eq!(loaded_handles.len(), 2);
+        buffers.lock().unwrap().clear();
+
+        drop(context);
+
+        // The grandchild is flushed first, then the child and the primary key.
+        assert_eq!(
+            command_handles(&buffers, TPM2_CC_FlushContext),
+            vec![
+                loaded_handles[1].clone(),
+                loaded_handles[0].clone(),
+                primary_handle[0].clone(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flushed_parent_is_not_flushed_on_drop() {
+        let mut context = create_ctx_with_intercepted_tcti();
+        let buffers = capture_buffers(&mut context);
+        let (_, child_handle, _) = create_key_hierarchy(&mut context);
+        let child_tpm_handle = response_handles(&buffers, TPM2_CC_Load)[0].clone();
+
+        // Flushing the parent removes the relationship with its children.
+        context
+            .flush_context(child_handle.into())
+            .expect("Failed to flush child key");
+        assert_eq!(context.transient_handle_count(), 2);
+        buffers.lock().unwrap().clear();
+
+        // Dropping the context flushes the remaining handles.
+        drop(context);
+        let flushed_handles = command_handles(&buffers, TPM2_CC_FlushContext);
+        assert_eq!(flushed_handles.len(), 2);
+        assert!(!flushed_handles.contains(&child_tpm_handle));
+    }
+}
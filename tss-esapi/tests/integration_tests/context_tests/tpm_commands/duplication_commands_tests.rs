@@ -6,7 +6,7 @@ mod test_duplicate {
     use std::convert::TryInto;
     use tss_esapi::attributes::{ObjectAttributesBuilder, SessionAttributesBuilder};
     use tss_esapi::constants::SessionType;
-    use tss_esapi::handles::ObjectHandle;
+    use tss_esapi::handles::{ObjectHandle, SessionHandle};
     use tss_esapi::interface_types::{
         algorithm::{HashingAlgorithm, PublicAlgorithm},
         ecc::EccCurve,
@@ -15,10 +15,43 @@ mod test_duplicate {
     };
     use tss_esapi::structures::SymmetricDefinition;
     use tss_esapi::structures::{
-        EccPoint, EccScheme, KeyDerivationFunctionScheme, PublicBuilder,
+        EccPoint, EccScheme, KeyDerivationFunctionScheme, Public, PublicBuilder,
         PublicEccParametersBuilder, SymmetricDefinitionObject,
     };
 
+    fn parent_public(symmetric: SymmetricDefinitionObject) -> Public {
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(false)
+            .with_restricted(true)
+            .build()
+            .expect("Attributes to be valid");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new()
+                    .with_ecc_scheme(EccScheme::Null)
+                    .with_curve(EccCurve::NistP256)
+                    .with_is_signing_key(false)
+                    .with_is_decryption_key(true)
+                    .with_restricted(true)
+                    .with_symmetric(symmetric)
+                    .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                    .build()
+                    .expect("Params to be valid"),
+            )
+            .with_ecc_unique_identifier(EccPoint::default())
+            .build()
+            .expect("public to be valid")
+    }
+
     #[test]
     fn test_duplicate_and_import() {
         let mut context = create_ctx_with_session();
@@ -302,4 +335,138 @@ mod test_duplicate {
 
         eprintln!("P: {:?}", private);
     }
+
+    #[test]
+    fn test_duplicate_to_unselected_parent_fails() {
+        let mut context = create_ctx_with_session();
+
+        let new_parent_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                parent_public(SymmetricDefinitionObject::AES_128_CFB),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+        // A parent with a different template has a different name.
+        let other_parent_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                parent_public(SymmetricDefinitionObject::AES_256_CFB),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+        let parent_name = context.read_public(new_parent_handle).unwrap().1;
+
+        // Compute a policy only allowing duplication to the new parent.
+        let sessions = context.sessions();
+        context.clear_sessions();
+        let trial_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let policy_session = PolicySession::try_from(trial_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_duplication_select(
+                policy_session,
+                Vec::<u8>::new().try_into().unwrap(),
+                parent_name.clone(),
+                false,
+            )
+            .expect("Policy duplication select");
+        let digest = context
+            .policy_get_digest(policy_session)
+            .expect("Could retrieve digest");
+        context
+            .flush_context(SessionHandle::from(trial_session).into())
+            .expect("Failed to flush trial session");
+        context.set_sessions(sessions);
+
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(false)
+            .with_fixed_parent(false)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(true)
+            .with_restricted(false)
+            .build()
+            .expect("Attributes to be valid");
+        let public_child = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_auth_policy(digest)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new()
+                    .with_ecc_scheme(EccScheme::Null)
+                    .with_curve(EccCurve::NistP256)
+                    .with_is_signing_key(false)
+                    .with_is_decryption_key(true)
+                    .with_restricted(false)
+                    .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                    .build()
+                    .expect("Params to be valid"),
+            )
+            .with_ecc_unique_identifier(EccPoint::default())
+            .build()
+            .expect("public to be valid");
+
+        let result = context
+            .create(new_parent_handle, public_child, None, None, None, None)
+            .unwrap();
+        let object_to_duplicate_handle: ObjectHandle = context
+            .load(new_parent_handle, result.out_private, result.out_public)
+            .unwrap()
+            .into();
+        let object_name = context
+            .read_public(object_to_duplicate_handle.into())
+            .unwrap()
+            .1;
+
+        context.clear_sessions();
+        let policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Policy,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_duplication_select(policy_session, object_name, parent_name, false)
+            .unwrap();
+        context.set_sessions((Some(policy_auth_session), None, None));
+
+        // The policy session only allows duplication to the selected parent.
+        let error = context
+            .duplicate(
+                object_to_duplicate_handle,
+                other_parent_handle.into(),
+                None,
+                SymmetricDefinitionObject::Null,
+            )
+            .expect_err("Duplication to an unselected parent should fail");
+        assert!(error.is_policy_fail());
+    }
 }
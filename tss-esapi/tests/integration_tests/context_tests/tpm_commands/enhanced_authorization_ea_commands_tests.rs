@@ -138,6 +138,101 @@ mod test_policy_secret {
     }
 }
 
+mod test_policy_ticket {
+    use crate::common::create_ctx_with_session;
+    use std::{convert::TryFrom, time::Duration};
+    use tss_esapi::{
+        constants::SessionType,
+        handles::{AuthHandle, ObjectHandle, SessionHandle},
+        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
+        structures::{AuthTicket, Digest, Nonce, SymmetricDefinition, Ticket, Timeout},
+        traits::{Marshall, UnMarshall},
+        Context,
+    };
+
+    fn start_policy_session(context: &mut Context) -> PolicySession {
+        let policy_auth_session = context
+            .execute_without_session(|ctx| {
+                ctx.start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+            })
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session")
+    }
+
+    #[test]
+    fn test_policy_ticket() {
+        let mut context = create_ctx_with_session();
+        let policy_ref = Nonce::try_from(vec![1, 2, 3]).unwrap();
+
+        let first_session = start_policy_session(&mut context);
+        let nonce_tpm = context
+            .tr_sess_get_nonce_tpm(first_session.into())
+            .expect("Failed to get the nonce of the policy session");
+        let (timeout, ticket) = context
+            .policy_secret_with_ticket(
+                first_session,
+                AuthHandle::Owner,
+                nonce_tpm,
+                Digest::default(),
+                policy_ref.clone(),
+                Duration::from_secs(3600),
+            )
+            .expect("Failed to call policy_secret_with_ticket");
+        assert!(!timeout.is_empty());
+        assert!(!ticket.digest().is_empty());
+
+        let marshalled_timeout = timeout.marshall().expect("Failed to marshall timeout");
+        let marshalled_ticket = ticket.marshall().expect("Failed to marshall ticket");
+        let timeout =
+            Timeout::unmarshall(&marshalled_timeout).expect("Failed to unmarshall timeout");
+        let unmarshalled_ticket =
+            AuthTicket::unmarshall(&marshalled_ticket).expect("Failed to unmarshall ticket");
+        assert_eq!(unmarshalled_ticket.tag(), ticket.tag());
+        assert_eq!(unmarshalled_ticket.hierarchy(), ticket.hierarchy());
+        assert_eq!(unmarshalled_ticket.digest(), ticket.digest());
+
+        let auth_name = context
+            .tr_get_name(ObjectHandle::Owner)
+            .expect("Failed to get the name of the owner hierarchy");
+        let second_session = start_policy_session(&mut context);
+        context
+            .policy_ticket(
+                second_session,
+                timeout,
+                Digest::default(),
+                policy_ref,
+                auth_name,
+                unmarshalled_ticket,
+            )
+            .expect("Failed to call policy_ticket");
+
+        let first_digest = context
+            .policy_get_digest(first_session)
+            .expect("Failed to get the digest of the first session");
+        let second_digest = context
+            .policy_get_digest(second_session)
+            .expect("Failed to get the digest of the second session");
+        assert_eq!(first_digest, second_digest);
+
+        context
+            .flush_context(SessionHandle::from(first_session).into())
+            .expect("Failed to flush the first session");
+        context
+            .flush_context(SessionHandle::from(second_session).into())
+            .expect("Failed to flush the second session");
+    }
+}
+
+#[cfg(feature = "abstraction")]
 mod test_policy_or {
     use crate::common::{create_ctx_without_session, get_pcr_policy_digest};
     use std::convert::TryFrom;
@@ -190,20 +285,58 @@ mod test_policy_or {
     }
 }
 
+#[cfg(feature = "abstraction")]
 mod test_policy_pcr {
     use crate::common::create_ctx_without_session;
-    use std::convert::TryFrom;
+    use sha2::{Digest as _, Sha256};
+    use std::convert::{TryFrom, TryInto};
     use tss_esapi::{
         abstraction::pcr::PcrData,
         attributes::SessionAttributesBuilder,
         constants::SessionType,
+        handles::SessionHandle,
         interface_types::{
             algorithm::HashingAlgorithm, reserved_handles::Hierarchy,
             session_handles::PolicySession,
         },
-        structures::{MaxBuffer, PcrSelectionListBuilder, PcrSlot, SymmetricDefinition},
+        structures::{
+            Digest, MaxBuffer, PcrSelectionList, PcrSelectionListBuilder, PcrSlot,
+            SymmetricDefinition,
+        },
+        Context, Error, WrapperErrorKind,
     };
 
+    fn policy_pcr_digest(
+        context: &mut Context,
+        session_type: SessionType,
+        pcr_policy_digest: Option<Digest>,
+        pcr_selection_list: PcrSelectionList,
+    ) -> Digest {
+        let policy_session: PolicySession = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                session_type,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle")
+            .try_into()
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_pcr(policy_session, pcr_policy_digest, pcr_selection_list)
+            .expect("Failed to call policy_pcr");
+        let policy_digest = context
+            .policy_get_digest(policy_session)
+            .expect("Failed to call policy_get_digest");
+        context
+            .flush_context(SessionHandle::from(policy_session).into())
+            .expect("Failed to flush policy session");
+        policy_digest
+    }
+
     #[test]
     fn test_policy_pcr_sha_256() {
         let mut context = create_ctx_without_session();
@@ -288,9 +421,101 @@ mod test_policy_pcr {
             .expect("Failed to convert auth session into policy session");
         // There should be no errors setting pcr policy for trial session.
         context
-            .policy_pcr(trial_policy_session, hashed_data, pcr_selection_list)
+            .policy_pcr(trial_policy_session, Some(hashed_data), pcr_selection_list)
             .expect("Failed to call policy_pcr");
     }
+
+    #[test]
+    fn test_policy_pcr_empty_and_supplied_digest() {
+        let mut context = create_ctx_without_session();
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+        let (_, _, pcr_digests) = context
+            .pcr_read(pcr_selection_list.clone())
+            .expect("Failed to call pcr_read");
+        let mut hasher = Sha256::new();
+        pcr_digests
+            .value()
+            .iter()
+            .for_each(|pcr_digest| hasher.update(pcr_digest.as_bytes()));
+        let current_pcr_digest = Digest::try_from(hasher.finalize().to_vec()).unwrap();
+
+        // With an empty digest the TPM computes the digest of the current PCR values.
+        let empty_form_policy_digest = policy_pcr_digest(
+            &mut context,
+            SessionType::Policy,
+            None,
+            pcr_selection_list.clone(),
+        );
+        let supplied_form_policy_digest = policy_pcr_digest(
+            &mut context,
+            SessionType::Trial,
+            Some(current_pcr_digest),
+            pcr_selection_list.clone(),
+        );
+        assert_eq!(empty_form_policy_digest, supplied_form_policy_digest);
+
+        // A policy can be computed for PCR values that are not yet present.
+        let future_form_policy_digest = policy_pcr_digest(
+            &mut context,
+            SessionType::Trial,
+            Some(Digest::try_from(vec![0xab; 32]).unwrap()),
+            pcr_selection_list,
+        );
+        assert_ne!(empty_form_policy_digest, future_form_policy_digest);
+    }
+
+    #[test]
+    fn test_policy_pcr_checked() {
+        let mut context = create_ctx_without_session();
+        let trial_policy_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle")
+            .try_into()
+            .expect("Failed to convert auth session into policy session");
+
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+        let (_, _, pcr_digests) = context
+            .pcr_read(pcr_selection_list.clone())
+            .expect("Failed to call pcr_read");
+        let mut hasher = Sha256::new();
+        pcr_digests
+            .value()
+            .iter()
+            .for_each(|pcr_digest| hasher.update(pcr_digest.as_bytes()));
+        let pcr_policy_digest = Digest::try_from(hasher.finalize().to_vec()).unwrap();
+
+        // Fails before the command is sent if the PCR values do not match.
+        assert_eq!(
+            context.policy_pcr_checked(
+                trial_policy_session,
+                Some(Digest::try_from(vec![0xff; 32]).unwrap()),
+                pcr_selection_list.clone(),
+            ),
+            Err(Error::WrapperError(WrapperErrorKind::PcrMismatch))
+        );
+
+        context
+            .policy_pcr_checked(
+                trial_policy_session,
+                Some(pcr_policy_digest),
+                pcr_selection_list,
+            )
+            .expect("Failed to call policy_pcr_checked");
+    }
 }
 
 mod test_policy_locality {
@@ -335,16 +560,60 @@ mod test_policy_locality {
             .policy_locality(trial_policy_session, LocalityAttributes::LOCALITY_THREE)
             .unwrap();
     }
+
+    #[test]
+    fn test_policy_locality_zero_digest() {
+        // policyDigest = H(0...0 || TPM_CC_PolicyLocality || 0x01)
+        const EXPECTED_POLICY_DIGEST: [u8; 32] = [
+            0xdd, 0xee, 0x6a, 0xf1, 0x4b, 0xf3, 0xc4, 0xe8, 0x12, 0x7c, 0xed, 0x87, 0xbc, 0xf9,
+            0xa5, 0x7e, 0x1c, 0x0c, 0x8d, 0xdb, 0x5e, 0x67, 0x73, 0x5c, 0x85, 0x05, 0xf9, 0x6f,
+            0x07, 0xb8, 0xdb, 0xb8,
+        ];
+        let mut context = create_ctx_without_session();
+        let trial_policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let trial_policy_session = PolicySession::try_from(trial_policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        context
+            .policy_locality(trial_policy_session, LocalityAttributes::LOCALITY_ZERO)
+            .expect("Failed to call policy_locality");
+        let policy_digest = context
+            .policy_get_digest(trial_policy_session)
+            .expect("Failed to call policy_get_digest");
+
+        assert_eq!(policy_digest.as_bytes(), EXPECTED_POLICY_DIGEST);
+    }
 }
 
+#[cfg(feature = "abstraction")]
 mod test_policy_command_code {
-    use crate::common::create_ctx_without_session;
+    use crate::common::{create_ctx_with_session, create_ctx_without_session, decryption_key_pub};
     use std::convert::TryFrom;
     use tss_esapi::{
-        attributes::SessionAttributesBuilder,
-        constants::{CommandCode, SessionType},
-        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
-        structures::SymmetricDefinition,
+        abstraction::policy,
+        attributes::{ObjectAttributesBuilder, SessionAttributesBuilder},
+        constants::{return_code::TpmFormatOneError, CommandCode, SessionType},
+        error::TpmResponseCode,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm},
+            reserved_handles::Hierarchy,
+            session_handles::PolicySession,
+        },
+        structures::{
+            Auth, KeyedHashScheme, PublicBuilder, PublicKeyedHashParameters, SensitiveData,
+            SymmetricDefinition,
+        },
+        Context, Error, ReturnCode,
     };
     #[test]
     fn test_policy_command_code() {
@@ -379,6 +648,131 @@ mod test_policy_command_code {
             .policy_command_code(trial_policy_session, CommandCode::Unseal)
             .unwrap();
     }
+
+    #[test]
+    fn test_policy_command_code_only_authorizes_unseal() {
+        let mut context = create_ctx_with_session();
+        let primary_key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key")
+            .key_handle;
+
+        // Policy that only authorizes TPM2_Unseal.
+        let auth_policy = policy::compute_policy_digest(
+            &mut context,
+            HashingAlgorithm::Sha256,
+            |ctx, policy_session| {
+                ctx.execute_without_session(|ctx| {
+                    ctx.policy_command_code(policy_session, CommandCode::Unseal)
+                })
+            },
+        )
+        .expect("Failed to compute policy digest");
+
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_no_da(true)
+            .with_admin_with_policy(true)
+            .with_user_with_auth(false)
+            .build()
+            .expect("Failed to create object attributes");
+        let sealed_object_public = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::KeyedHash)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_auth_policy(auth_policy)
+            .with_keyed_hash_parameters(PublicKeyedHashParameters::new(KeyedHashScheme::Null))
+            .with_keyed_hash_unique_identifier(Default::default())
+            .build()
+            .expect("Failed to create public structure");
+        let sealed_data = SensitiveData::try_from(vec![1, 2, 3, 4, 5, 6, 7, 8])
+            .expect("Failed to create sensitive data");
+        let create_result = context
+            .create(
+                primary_key_handle,
+                sealed_object_public,
+                None,
+                Some(sealed_data.clone()),
+                None,
+                None,
+            )
+            .expect("Failed to create sealed object");
+        let sealed_object_handle = context
+            .load(
+                primary_key_handle,
+                create_result.out_private,
+                create_result.out_public,
+            )
+            .expect("Failed to load sealed object");
+
+        let start_unseal_policy_session = |context: &mut Context| {
+            let policy_auth_session = context
+                .execute_without_session(|ctx| {
+                    ctx.start_auth_session(
+                        None,
+                        None,
+                        None,
+                        SessionType::Policy,
+                        SymmetricDefinition::AES_256_CFB,
+                        HashingAlgorithm::Sha256,
+                    )
+                })
+                .expect("Start auth session failed")
+                .expect("Start auth session returned a NONE handle");
+            let (session_attributes, session_attributes_mask) =
+                SessionAttributesBuilder::new().build();
+            context
+                .tr_sess_set_attributes(
+                    policy_auth_session,
+                    session_attributes,
+                    session_attributes_mask,
+                )
+                .expect("tr_sess_set_attributes call failed");
+            let policy_session = PolicySession::try_from(policy_auth_session)
+                .expect("Failed to convert auth session into policy session");
+            context
+                .execute_without_session(|ctx| {
+                    ctx.policy_command_code(policy_session, CommandCode::Unseal)
+                })
+                .expect("Call to policy_command_code failed");
+            policy_auth_session
+        };
+
+        // Unsealing is authorized by the policy.
+        let policy_auth_session = start_unseal_policy_session(&mut context);
+        let unsealed_data = context
+            .execute_with_session(Some(policy_auth_session), |ctx| {
+                ctx.unseal(sealed_object_handle.into())
+            })
+            .expect("Failed to unseal using the policy session");
+        assert_eq!(sealed_data, unsealed_data);
+
+        // Any other command is rejected.
+        let policy_auth_session = start_unseal_policy_session(&mut context);
+        let object_change_auth_result =
+            context.execute_with_session(Some(policy_auth_session), |ctx| {
+                ctx.object_change_auth(
+                    sealed_object_handle.into(),
+                    primary_key_handle.into(),
+                    Auth::try_from(vec![1, 2, 3, 4]).expect("Failed to create auth"),
+                )
+            });
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatOne(error)))) =
+            object_change_auth_result
+        {
+            assert_eq!(error.error_number(), TpmFormatOneError::PolicyCc);
+        } else {
+            panic!("object_change_auth should have failed with a policy command code error");
+        }
+    }
 }
 
 mod test_policy_physical_presence {
@@ -423,6 +817,39 @@ mod test_policy_physical_presence {
             .policy_physical_presence(trial_policy_session)
             .unwrap();
     }
+
+    #[test]
+    fn test_policy_physical_presence_digest() {
+        // policyDigest = H(0...0 || TPM_CC_PolicyPhysicalPresence)
+        const EXPECTED_POLICY_DIGEST: [u8; 32] = [
+            0x0d, 0x7c, 0x67, 0x47, 0xb1, 0xb9, 0xfa, 0xcb, 0xba, 0x03, 0x49, 0x20, 0x97, 0xaa,
+            0x9d, 0x5a, 0xf7, 0x92, 0xe5, 0xef, 0xc0, 0x73, 0x46, 0xe0, 0x5f, 0x9d, 0xaa, 0x8b,
+            0x3d, 0x9e, 0x13, 0xb5,
+        ];
+        let mut context = create_ctx_without_session();
+        let trial_policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let trial_policy_session = PolicySession::try_from(trial_policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        context
+            .policy_physical_presence(trial_policy_session)
+            .expect("Failed to call policy_physical_presence");
+        let policy_digest = context
+            .policy_get_digest(trial_policy_session)
+            .expect("Failed to call policy_get_digest");
+
+        assert_eq!(policy_digest.as_bytes(), EXPECTED_POLICY_DIGEST);
+    }
 }
 
 mod test_policy_cp_hash {
@@ -525,6 +952,7 @@ mod test_policy_name_hash {
     }
 }
 
+#[cfg(feature = "abstraction")]
 mod test_policy_authorize {
     use crate::common::{create_ctx_with_session, get_pcr_policy_digest, signing_key_pub};
     use std::convert::TryFrom;
@@ -675,6 +1103,7 @@ mod test_policy_password {
     }
 }
 
+#[cfg(feature = "abstraction")]
 mod test_policy_get_digest {
     use crate::common::create_ctx_without_session;
     use std::convert::TryFrom;
@@ -686,7 +1115,7 @@ mod test_policy_get_digest {
             algorithm::HashingAlgorithm, reserved_handles::Hierarchy,
             session_handles::PolicySession,
         },
-        structures::{MaxBuffer, PcrSelectionListBuilder, PcrSlot, SymmetricDefinition},
+        structures::{Digest, MaxBuffer, PcrSelectionListBuilder, PcrSlot, SymmetricDefinition},
     };
     #[test]
     fn get_policy_digest() {
@@ -772,7 +1201,7 @@ mod test_policy_get_digest {
             .unwrap();
         // There should be no errors setting pcr policy for trial session.
         context
-            .policy_pcr(trial_policy_session, hashed_data, pcr_selection_list)
+            .policy_pcr(trial_policy_session, Some(hashed_data), pcr_selection_list)
             .unwrap();
 
         // There is now a policy digest that can be retrieved and used.
@@ -781,17 +1210,174 @@ mod test_policy_get_digest {
         // The algorithm is SHA256 so the expected size of the digest should be 32.
         assert_eq!(retrieved_policy_digest.as_bytes().len(), 32);
     }
+
+    #[test]
+    fn get_policy_digest_matches_expected_value() {
+        let mut context = create_ctx_without_session();
+        let trial_policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let trial_policy_session = PolicySession::try_from(trial_policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+        // A trial session uses the provided PCR digest as is.
+        let pcr_digest = Digest::try_from(vec![0xab; 32]).expect("Failed to create digest");
+        context
+            .policy_pcr(
+                trial_policy_session,
+                Some(pcr_digest.clone()),
+                pcr_selection_list,
+            )
+            .expect("Failed to call policy_pcr");
+
+        let retrieved_policy_digest = context
+            .policy_get_digest(trial_policy_session)
+            .expect("Failed to call policy_get_digest");
+
+        // policyDigest = H(0...0 || TPM_CC_PolicyPCR || pcrs || pcrDigest)
+        use sha2::Digest as _;
+        let mut hasher = sha2::Sha256::new();
+        hasher.update([0u8; 32]);
+        hasher.update(0x0000017Fu32.to_be_bytes());
+        hasher.update([
+            0x00, 0x00, 0x00, 0x01, // count
+            0x00, 0x0B, // TPM2_ALG_SHA256
+            0x03, 0x03, 0x00, 0x00, // sizeofSelect and pcrSelect
+        ]);
+        hasher.update(pcr_digest.as_bytes());
+        let expected_policy_digest = hasher.finalize();
+
+        assert_eq!(
+            retrieved_policy_digest.as_bytes(),
+            expected_policy_digest.as_slice()
+        );
+    }
 }
 
 mod test_policy_nv_written {
-    use crate::common::create_ctx_without_session;
+    use crate::common::{create_ctx_with_session, create_ctx_without_session};
     use std::convert::TryFrom;
     use tss_esapi::{
-        attributes::SessionAttributesBuilder,
+        attributes::{NvIndexAttributesBuilder, SessionAttributesBuilder},
         constants::SessionType,
-        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
-        structures::SymmetricDefinition,
+        handles::{NvIndexHandle, NvIndexTpmHandle, SessionHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{NvAuth, Provision},
+            session_handles::PolicySession,
+        },
+        structures::{MaxNvBuffer, NvPublicBuilder, SymmetricDefinition},
+        Context, Result,
     };
+
+    fn write_with_nv_written_policy(
+        context: &mut Context,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        let sessions = context.sessions();
+        context.clear_sessions();
+        let policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Policy,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_nv_written(policy_session, false)
+            .expect("Failed to call policy_nv_written");
+
+        context.set_sessions((Some(policy_auth_session), None, None));
+        let result = context.nv_write(
+            NvAuth::NvIndex(nv_index_handle),
+            nv_index_handle,
+            MaxNvBuffer::try_from(vec![1, 2, 3, 4]).expect("Failed to create data"),
+            0,
+        );
+        context.set_sessions(sessions);
+        result
+    }
+
+    #[test]
+    fn test_policy_nv_written_not_written() {
+        let mut context = create_ctx_with_session();
+
+        // Compute a policy which requires the NV index to not have been written.
+        let sessions = context.sessions();
+        context.clear_sessions();
+        let trial_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let trial_session = PolicySession::try_from(trial_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_nv_written(trial_session, false)
+            .expect("Failed to call policy_nv_written");
+        let digest = context
+            .policy_get_digest(trial_session)
+            .expect("Failed to get policy digest");
+        context
+            .flush_context(SessionHandle::from(trial_auth_session).into())
+            .expect("Failed to flush trial session");
+        context.set_sessions(sessions);
+
+        let nv_index = NvIndexTpmHandle::new(0x0150002F).unwrap();
+        let nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_policy_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create nv index attributes");
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(nv_index_attributes)
+            .with_index_auth_policy(digest)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic");
+        let nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, nv_public)
+            .expect("Call to nv_define_space failed");
+
+        // The policy is satisfied as long as the index has not been written.
+        let first_write_result = write_with_nv_written_policy(&mut context, nv_index_handle);
+        let second_write_result = write_with_nv_written_policy(&mut context, nv_index_handle);
+
+        context
+            .nv_undefine_space(Provision::Owner, nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        first_write_result.expect("First write should satisfy the policy");
+        assert!(second_write_result
+            .expect_err("Second write should not satisfy the policy")
+            .is_policy_fail());
+    }
     #[test]
     fn test_policy_nv_written() {
         let mut context = create_ctx_without_session();
@@ -887,17 +1473,21 @@ mod test_policy_template {
 
 mod test_policy_authorize_nv {
     use crate::common::{create_ctx_with_session, write_nv_index};
-    use std::convert::TryFrom;
+    use sha2::{Digest as _, Sha256};
+    use std::convert::{TryFrom, TryInto};
     use tss_esapi::{
-        attributes::SessionAttributesBuilder,
-        constants::SessionType,
-        handles::{NvIndexHandle, NvIndexTpmHandle},
+        attributes::{NvIndexAttributesBuilder, SessionAttributesBuilder},
+        constants::{AlgorithmIdentifier, SessionType},
+        handles::{NvIndexHandle, NvIndexTpmHandle, SessionHandle},
         interface_types::{
             algorithm::HashingAlgorithm,
             reserved_handles::{NvAuth, Provision},
             session_handles::PolicySession,
         },
-        structures::SymmetricDefinition,
+        structures::{
+            Digest, MaxNvBuffer, NvPublicBuilder, PcrSelectionListBuilder, PcrSlot,
+            SymmetricDefinition,
+        },
     };
 
     #[test]
@@ -948,4 +1538,122 @@ mod test_policy_authorize_nv {
 
         policy_result.unwrap();
     }
+
+    #[test]
+    fn test_policy_authorize_nv_with_pcr_policy() {
+        let mut context = create_ctx_with_session();
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+
+        // Compute the PCR policy digest using a trial session.
+        let (_, _, pcr_digests) = context
+            .pcr_read(pcr_selection_list.clone())
+            .expect("Failed to call pcr_read");
+        let mut hasher = Sha256::new();
+        pcr_digests
+            .value()
+            .iter()
+            .for_each(|pcr_digest| hasher.update(pcr_digest.as_bytes()));
+        let pcr_digest = Digest::try_from(hasher.finalize().to_vec()).unwrap();
+        let trial_policy_session: PolicySession = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle")
+            .try_into()
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_pcr(
+                trial_policy_session,
+                Some(pcr_digest),
+                pcr_selection_list.clone(),
+            )
+            .expect("Failed to call policy_pcr");
+        let pcr_policy_digest = context
+            .policy_get_digest(trial_policy_session)
+            .expect("Failed to call policy_get_digest");
+        context
+            .flush_context(SessionHandle::from(trial_policy_session).into())
+            .expect("Failed to flush trial policy session");
+
+        // Store the policy digest in the NV index as a TPMT_HA.
+        let nv_index = NvIndexTpmHandle::new(0x01500501).unwrap();
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(
+                NvIndexAttributesBuilder::new()
+                    .with_owner_write(true)
+                    .with_owner_read(true)
+                    .build()
+                    .expect("Failed to create owner nv index attributes"),
+            )
+            .with_data_area_size(34)
+            .build()
+            .expect("Failed to build NvPublic");
+        let nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, nv_public)
+            .expect("Call to nv_define_space failed");
+        let policy_ha = [
+            &u16::from(AlgorithmIdentifier::Sha256).to_be_bytes()[..],
+            pcr_policy_digest.as_bytes(),
+        ]
+        .concat();
+        let write_result = context.nv_write(
+            NvAuth::Owner,
+            nv_index_handle,
+            MaxNvBuffer::try_from(policy_ha).unwrap(),
+            0,
+        );
+
+        // A policy session that satisfies the PCR policy is authorized by the NV index.
+        let start_policy_session = |context: &mut tss_esapi::Context| -> PolicySession {
+            context
+                .start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+                .expect("Start auth session failed")
+                .expect("Start auth session returned a NONE handle")
+                .try_into()
+                .expect("Failed to convert auth session into policy session")
+        };
+        let policy_session = start_policy_session(&mut context);
+        let authorized_result = context
+            .policy_pcr(policy_session, None, pcr_selection_list)
+            .and_then(|_| {
+                context.policy_authorize_nv(policy_session, NvAuth::Owner, nv_index_handle)
+            });
+        context
+            .flush_context(SessionHandle::from(policy_session).into())
+            .expect("Failed to flush policy session");
+
+        // A policy session that does not satisfy it is rejected.
+        let unsatisfied_policy_session = start_policy_session(&mut context);
+        let unauthorized_result =
+            context.policy_authorize_nv(unsatisfied_policy_session, NvAuth::Owner, nv_index_handle);
+        context
+            .flush_context(SessionHandle::from(unsatisfied_policy_session).into())
+            .expect("Failed to flush policy session");
+
+        context
+            .nv_undefine_space(Provision::Owner, nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        write_result.expect("Call to nv_write failed");
+        authorized_result.expect("Failed to authorize policy session with policy_authorize_nv");
+        assert!(unauthorized_result.is_err());
+    }
 }
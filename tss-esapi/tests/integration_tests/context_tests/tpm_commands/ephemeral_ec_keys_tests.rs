@@ -1,2 +1,86 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_commit {
+    use crate::common::create_ctx_with_session;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm},
+            ecc::EccCurve,
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            CommitCounter, EcDaaScheme, EccPoint, EccScheme, KeyDerivationFunctionScheme, Public,
+            PublicBuilder, PublicEccParametersBuilder,
+        },
+    };
+
+    fn ecdaa_key_pub() -> Public {
+        let ecc_parms = PublicEccParametersBuilder::new()
+            .with_ecc_scheme(EccScheme::EcDaa(EcDaaScheme::new(
+                HashingAlgorithm::Sha256,
+                0,
+            )))
+            .with_curve(EccCurve::BnP256)
+            .with_is_signing_key(true)
+            .with_is_decryption_key(false)
+            .with_restricted(false)
+            .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+            .build()
+            .expect("Failed to build ECC parameters");
+
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(false)
+            .with_sign_encrypt(true)
+            .with_restricted(false)
+            .build()
+            .expect("Failed to build object attributes");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_ecc_parameters(ecc_parms)
+            .with_ecc_unique_identifier(EccPoint::default())
+            .build()
+            .expect("Failed to build public")
+    }
+
+    #[test]
+    fn test_commit_counter_increments() {
+        let mut context = create_ctx_with_session();
+        let key_handle = context
+            .create_primary(Hierarchy::Owner, ecdaa_key_pub(), None, None, None, None)
+            .expect("Failed to create ECDAA key")
+            .key_handle;
+
+        let (k_1, l_1, e_1, counter_1) = context
+            .commit(key_handle, None, None, None)
+            .expect("Failed to perform first commit");
+        let (_, _, e_2, counter_2) = context
+            .commit(key_handle, None, None, None)
+            .expect("Failed to perform second commit");
+
+        // Without s2 and y2 only E is computed.
+        assert!(k_1.x().is_empty());
+        assert!(l_1.x().is_empty());
+        assert!(!e_1.x().is_empty());
+        assert_ne!(e_1, e_2);
+        assert!(counter_2 > counter_1);
+    }
+
+    #[test]
+    fn test_max_commit_id() {
+        let mut context = create_ctx_with_session();
+        assert_eq!(
+            context
+                .max_commit_id()
+                .expect("Failed to get the maximum commit id"),
+            CommitCounter::MAX.value()
+        );
+    }
+}
@@ -1,2 +1,80 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_field_upgrade_start {
+    use crate::common::{create_ctx_with_session, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::return_code::TpmFormatZeroError,
+        error::{TpmFormatZeroResponseCode, TpmResponseCode},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{Hierarchy, Platform},
+        },
+        structures::{Digest, HashAgile, Signature},
+        Error, ReturnCode,
+    };
+
+    #[test]
+    fn test_field_upgrade_start() {
+        let mut context = create_ctx_with_session();
+        let key_handle = context
+            .create_primary(Hierarchy::Owner, signing_key_pub(), None, None, None, None)
+            .expect("Failed to create primary key")
+            .key_handle;
+
+        let fu_digest = Digest::try_from(vec![0xab; 32]).expect("Failed to create digest");
+        let manifest_signature = Signature::Hmac(HashAgile::new(
+            HashingAlgorithm::Sha256,
+            Digest::try_from(vec![0xcd; 32]).expect("Failed to create digest"),
+        ));
+
+        let result = context.field_upgrade_start(
+            Platform::Platform,
+            key_handle,
+            fu_digest,
+            manifest_signature,
+        );
+        context
+            .flush_context(key_handle.into())
+            .expect("Failed to flush key");
+
+        // The simulator does not implement field upgrades, so the parameters
+        // are marshalled and sent but the command is rejected.
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+            TpmFormatZeroResponseCode::Error(error),
+        )))) = result
+        {
+            assert_eq!(error.error_number(), TpmFormatZeroError::CommandCode);
+        } else {
+            panic!("field_upgrade_start should have failed with a command code error");
+        }
+    }
+}
+
+mod test_field_upgrade_data {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::return_code::TpmFormatZeroError,
+        error::{TpmFormatZeroResponseCode, TpmResponseCode},
+        structures::MaxBuffer,
+        Error, ReturnCode,
+    };
+
+    #[test]
+    fn test_field_upgrade_data() {
+        let mut context = create_ctx_without_session();
+        let fu_data = MaxBuffer::try_from(vec![0xff; 64]).expect("Failed to create MaxBuffer");
+
+        // The simulator does not implement field upgrades, so the parameters
+        // are marshalled and sent but the command is rejected.
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+            TpmFormatZeroResponseCode::Error(error),
+        )))) = context.field_upgrade_data(fu_data)
+        {
+            assert_eq!(error.error_number(), TpmFormatZeroError::CommandCode);
+        } else {
+            panic!("field_upgrade_data should have failed with a command code error");
+        }
+    }
+}
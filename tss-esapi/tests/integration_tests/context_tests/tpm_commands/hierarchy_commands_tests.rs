@@ -1,9 +1,16 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 mod test_create_primary {
-    use crate::common::{create_ctx_with_session, decryption_key_pub};
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub, HASH};
+    use std::convert::TryFrom;
     use tss_esapi::{
-        handles::ObjectHandle, interface_types::reserved_handles::Hierarchy, structures::Auth,
+        handles::{ObjectHandle, PersistentTpmHandle},
+        interface_types::{
+            data_handles::Persistent,
+            reserved_handles::{Hierarchy, Provision},
+            session_handles::AuthSession,
+        },
+        structures::{Auth, Digest, SignatureScheme},
     };
 
     #[test]
@@ -26,6 +33,58 @@ mod test_create_primary {
             .key_handle;
         assert!(ObjectHandle::from(key_handle) != ObjectHandle::Null);
     }
+
+    #[test]
+    fn test_create_primary_in_each_hierarchy() {
+        let mut context = create_ctx_with_session();
+        for hierarchy in [
+            Hierarchy::Owner,
+            Hierarchy::Endorsement,
+            Hierarchy::Platform,
+            Hierarchy::Null,
+        ] {
+            let key_handle = context
+                .create_primary(hierarchy, decryption_key_pub(), None, None, None, None)
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to create primary in {:?} hierarchy: {}",
+                        hierarchy, e
+                    )
+                })
+                .key_handle;
+            context
+                .flush_context(key_handle.into())
+                .expect("Failed to flush key");
+        }
+    }
+
+    #[test]
+    fn test_create_primary_in_null_hierarchy() {
+        let mut context = create_ctx_with_session();
+        let key_handle = context
+            .create_primary(Hierarchy::Null, signing_key_pub(), None, None, None, None)
+            .expect("Failed to create primary in the null hierarchy")
+            .key_handle;
+
+        let _ = context
+            .sign(
+                key_handle,
+                Digest::try_from(HASH[..32].to_vec()).expect("Failed to create digest"),
+                SignatureScheme::Null,
+                None,
+            )
+            .expect("Failed to sign with the ephemeral key");
+
+        let persistent = Persistent::Persistent(
+            PersistentTpmHandle::new(0x81000012).expect("Failed to create persistent handle"),
+        );
+        let error = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.evict_control(Provision::Owner, key_handle.into(), persistent)
+            })
+            .expect_err("A key of the null hierarchy was made persistent");
+        assert!(error.is_attributes());
+    }
 }
 
 mod test_clear {
@@ -56,11 +115,54 @@ mod test_clear_control {
 }
 
 mod test_change_auth {
-    use crate::common::{create_ctx_with_session, decryption_key_pub};
+    use crate::common::{create_ctx_with_session, decryption_key_pub, HASH};
+    use std::convert::TryFrom;
     use tss_esapi::{
-        handles::AuthHandle, interface_types::reserved_handles::Hierarchy, structures::Auth,
+        attributes::ObjectAttributesBuilder,
+        handles::AuthHandle,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
+            key_bits::RsaKeyBits,
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            Auth, Digest, Public, PublicBuilder, PublicKeyRsa, PublicRsaParametersBuilder,
+            RsaExponent, RsaScheme, SignatureScheme,
+        },
     };
 
+    // Signing key that is not subject to dictionary attack protection,
+    // so that a failed authorization does not lock out the TPM.
+    fn no_da_signing_key_pub() -> Public {
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_no_da(true)
+            .with_sign_encrypt(true)
+            .build()
+            .expect("Failed to build object attributes");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Rsa)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_rsa_parameters(
+                PublicRsaParametersBuilder::new_unrestricted_signing_key(
+                    RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+                        .expect("Failed to create RSA scheme"),
+                    RsaKeyBits::Rsa2048,
+                    RsaExponent::default(),
+                )
+                .build()
+                .expect("Failed to build RSA parameters"),
+            )
+            .with_rsa_unique_identifier(PublicKeyRsa::default())
+            .build()
+            .expect("Failed to build public")
+    }
+
     #[test]
     fn test_object_change_auth() {
         let mut context = create_ctx_with_session();
@@ -106,6 +208,70 @@ mod test_change_auth {
             .unwrap();
     }
 
+    #[test]
+    fn test_object_change_auth_replaces_auth() {
+        let mut context = create_ctx_with_session();
+
+        let prim_key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+
+        let old_auth = Auth::try_from(vec![1, 2, 3, 4]).unwrap();
+        let new_auth = Auth::try_from(vec![5, 6, 7, 8]).unwrap();
+        let keyresult = context
+            .create(
+                prim_key_handle,
+                no_da_signing_key_pub(),
+                Some(old_auth.clone()),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let old_key = context
+            .load(
+                prim_key_handle,
+                keyresult.out_private,
+                keyresult.out_public.clone(),
+            )
+            .unwrap();
+        context
+            .tr_set_auth(old_key.into(), old_auth.clone())
+            .unwrap();
+
+        let new_private = context
+            .object_change_auth(old_key.into(), prim_key_handle.into(), new_auth.clone())
+            .unwrap();
+        context.flush_context(old_key.into()).unwrap();
+        let new_key = context
+            .load(prim_key_handle, new_private, keyresult.out_public)
+            .unwrap();
+        let digest = Digest::try_from(HASH[..32].to_vec()).unwrap();
+
+        // The old auth value no longer authorizes the key.
+        context.tr_set_auth(new_key.into(), old_auth).unwrap();
+        let error = context
+            .sign(new_key, digest.clone(), SignatureScheme::Null, None)
+            .unwrap_err();
+        assert!(error.is_bad_auth());
+
+        context.tr_set_auth(new_key.into(), new_auth).unwrap();
+        context
+            .sign(new_key, digest, SignatureScheme::Null, None)
+            .unwrap();
+
+        context.flush_context(new_key.into()).unwrap();
+        context.flush_context(prim_key_handle.into()).unwrap();
+    }
+
     #[test]
     fn test_hierarchy_change_auth() {
         let mut context = create_ctx_with_session();
@@ -125,4 +291,53 @@ mod test_change_auth {
             .hierarchy_change_auth(AuthHandle::Platform, Default::default())
             .unwrap();
     }
+
+    #[test]
+    fn test_hierarchy_change_auth_requires_new_auth() {
+        let mut context = create_ctx_with_session();
+        let new_auth = Auth::try_from(vec![9, 8, 7, 6]).unwrap();
+
+        // NOTE: The owner auth is reset before checking the results, so that
+        //  the tests running afterwards can still use the owner hierarchy.
+        context
+            .hierarchy_change_auth(AuthHandle::Owner, new_auth.clone())
+            .unwrap();
+
+        context
+            .tr_set_auth(Hierarchy::Owner.into(), Auth::default())
+            .unwrap();
+        let empty_auth_result = context.create_primary(
+            Hierarchy::Owner,
+            decryption_key_pub(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        context
+            .tr_set_auth(Hierarchy::Owner.into(), new_auth)
+            .unwrap();
+        let new_auth_result = context.create_primary(
+            Hierarchy::Owner,
+            decryption_key_pub(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        context
+            .hierarchy_change_auth(AuthHandle::Owner, Default::default())
+            .unwrap();
+
+        assert!(empty_auth_result
+            .map(|_| ())
+            .expect_err("The empty auth authorized the owner hierarchy")
+            .is_bad_auth());
+        let key_handle = new_auth_result
+            .expect("The new auth did not authorize the owner hierarchy")
+            .key_handle;
+        context.flush_context(key_handle.into()).unwrap();
+    }
 }
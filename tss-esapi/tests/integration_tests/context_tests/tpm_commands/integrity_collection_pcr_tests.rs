@@ -1,5 +1,6 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+#[cfg(feature = "abstraction")]
 mod test_pcr_extend_reset {
     use crate::common::create_ctx_with_session;
     use std::convert::TryFrom;
@@ -146,6 +147,40 @@ mod test_pcr_extend_reset {
     }
 }
 
+mod test_pcr_extend {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        handles::PcrHandle,
+        interface_types::algorithm::HashingAlgorithm,
+        structures::{Digest, DigestValues},
+        Error, WrapperErrorKind,
+    };
+
+    #[test]
+    fn test_pcr_extend_with_invalid_digest_size() {
+        let mut context = create_ctx_with_session();
+        let pcr_ses = context.sessions().0;
+
+        let mut vals = DigestValues::new();
+        vals.set(
+            HashingAlgorithm::Sha1,
+            Digest::try_from(vec![0xff; 20]).expect("Failed to create sha1 digest"),
+        );
+        // A SHA-1 sized digest for the SHA-256 bank.
+        vals.set(
+            HashingAlgorithm::Sha256,
+            Digest::try_from(vec![0xff; 20]).expect("Failed to create digest"),
+        );
+        let result =
+            context.execute_with_session(pcr_ses, |ctx| ctx.pcr_extend(PcrHandle::Pcr16, vals));
+        assert_eq!(
+            Err(Error::WrapperError(WrapperErrorKind::WrongParamSize)),
+            result
+        );
+    }
+}
+
 mod test_pcr_read {
     use crate::common::create_ctx_without_session;
     use tss_esapi::{
@@ -248,3 +283,97 @@ mod test_pcr_read {
         assert_ne!(pcr_selection_list_in, pcr_selection_list_out);
     }
 }
+
+mod test_pcr_set_auth {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        handles::{AuthHandle, ObjectHandle, PcrHandle},
+        interface_types::{algorithm::HashingAlgorithm, session_handles::AuthSession},
+        structures::{Auth, Digest},
+        Context,
+    };
+
+    // PCR 20 is in the authorization and policy groups of the simulator.
+    const GROUP_PCR: PcrHandle = PcrHandle::Pcr20;
+
+    /// Restores the default authorization value of the PCR when dropped,
+    /// so that a failed assertion does not leave it changed.
+    struct PcrAuthGuard<'a> {
+        context: &'a mut Context,
+        auth: Auth,
+    }
+
+    impl Drop for PcrAuthGuard<'_> {
+        fn drop(&mut self) {
+            let pcr = ObjectHandle::from(GROUP_PCR);
+            let result = self
+                .context
+                .tr_set_auth(pcr, self.auth.clone())
+                .and_then(|_| self.context.pcr_set_auth_value(GROUP_PCR, Auth::default()))
+                .and_then(|_| self.context.tr_set_auth(pcr, Auth::default()));
+            // Panicking while unwinding would abort the tests.
+            if !std::thread::panicking() {
+                result.expect("Failed to restore the auth value");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcr_set_auth_value() {
+        let mut context = create_ctx_without_session();
+        context.set_sessions((Some(AuthSession::Password), None, None));
+        let auth = Auth::from_bytes(b"pcr auth").expect("Failed to create auth");
+
+        context
+            .pcr_set_auth_value(GROUP_PCR, auth.clone())
+            .expect("Failed to call pcr_set_auth_value");
+        let mut guard = PcrAuthGuard {
+            context: &mut context,
+            auth: auth.clone(),
+        };
+
+        // The old authorization value is no longer accepted.
+        assert!(guard
+            .context
+            .pcr_set_auth_value(GROUP_PCR, auth.clone())
+            .expect_err("The auth value was set with the old auth value")
+            .is_bad_auth());
+
+        // The new authorization value is accepted.
+        guard
+            .context
+            .tr_set_auth(ObjectHandle::from(GROUP_PCR), auth.clone())
+            .expect("Failed to call tr_set_auth");
+        guard
+            .context
+            .pcr_set_auth_value(GROUP_PCR, auth)
+            .expect("Failed to set the auth value with the new auth value");
+    }
+
+    #[test]
+    fn test_pcr_set_auth_policy() {
+        let mut context = create_ctx_without_session();
+        context.set_sessions((Some(AuthSession::Password), None, None));
+        let policy = Digest::try_from(vec![0x01; 32]).expect("Failed to create digest");
+
+        context
+            .pcr_set_auth_policy(
+                AuthHandle::Platform,
+                policy,
+                HashingAlgorithm::Sha256,
+                GROUP_PCR,
+            )
+            .expect("Failed to call pcr_set_auth_policy");
+
+        // Remove the policy.
+        context
+            .pcr_set_auth_policy(
+                AuthHandle::Platform,
+                Digest::default(),
+                HashingAlgorithm::Null,
+                GROUP_PCR,
+            )
+            .expect("Failed to remove the PCR auth policy");
+    }
+}
@@ -1,2 +1,71 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_set_algorithm_set {
+    use crate::common::MockSimulator;
+    use tss_esapi::{
+        constants::tss::{
+            TPM2_CC_SetAlgorithmSet, TPM2_RC_COMMAND_CODE, TPM2_RH_PLATFORM, TPM2_RS_PW,
+            TPM2_ST_NO_SESSIONS, TPM2_ST_SESSIONS,
+        },
+        handles::AuthHandle,
+        interface_types::session_handles::AuthSession,
+        Context, Error, WrapperErrorKind,
+    };
+
+    /// Response to a command with a single password session.
+    fn success_response() -> Vec<u8> {
+        let mut response = Vec::new();
+        response.extend_from_slice(&TPM2_ST_SESSIONS.to_be_bytes());
+        response.extend_from_slice(&19u32.to_be_bytes());
+        response.extend_from_slice(&0u32.to_be_bytes());
+        // No response parameters.
+        response.extend_from_slice(&0u32.to_be_bytes());
+        // Empty nonce, continueSession and empty HMAC.
+        response.extend_from_slice(&[0x00, 0x00, 0x01, 0x00, 0x00]);
+        response
+    }
+
+    fn error_response(response_code: u32) -> Vec<u8> {
+        let mut response = Vec::new();
+        response.extend_from_slice(&TPM2_ST_NO_SESSIONS.to_be_bytes());
+        response.extend_from_slice(&10u32.to_be_bytes());
+        response.extend_from_slice(&response_code.to_be_bytes());
+        response
+    }
+
+    #[test]
+    fn test_set_algorithm_set() {
+        let simulator = MockSimulator::start(vec![success_response()]);
+        let mut context = Context::new(simulator.tcti()).expect("Failed to create context");
+
+        context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.set_algorithm_set(AuthHandle::Platform, 0x0102_0304)
+            })
+            .expect("Failed to call set_algorithm_set");
+
+        let command = simulator.received_command();
+        assert_eq!(command.len(), 31);
+        assert_eq!(command[6..10], TPM2_CC_SetAlgorithmSet.to_be_bytes());
+        // The platform hierarchy is the authorization handle...
+        assert_eq!(command[10..14], TPM2_RH_PLATFORM.to_be_bytes());
+        // ...authorized with a password session.
+        assert_eq!(command[18..22], TPM2_RS_PW.to_be_bytes());
+        assert_eq!(command[27..31], 0x0102_0304u32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_set_algorithm_set_unsupported() {
+        let simulator = MockSimulator::start(vec![error_response(TPM2_RC_COMMAND_CODE)]);
+        let mut context = Context::new(simulator.tcti()).expect("Failed to create context");
+
+        assert_eq!(
+            context
+                .execute_with_session(Some(AuthSession::Password), |ctx| {
+                    ctx.set_algorithm_set(AuthHandle::Platform, 0)
+                })
+                .expect_err("set_algorithm_set succeeded on a TPM not implementing it"),
+            Error::WrapperError(WrapperErrorKind::UnsupportedCommand)
+        );
+    }
+}
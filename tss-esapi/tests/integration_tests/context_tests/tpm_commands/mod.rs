@@ -3,6 +3,7 @@
 mod asymmetric_primitives_tests;
 mod attached_components_tests;
 mod attestation_commands_tests;
+#[cfg(has_act)]
 mod authenticated_countdown_timer_tests;
 mod capability_commands_tests;
 mod clocks_and_timers_tests;
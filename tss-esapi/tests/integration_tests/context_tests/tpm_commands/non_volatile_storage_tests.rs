@@ -1,12 +1,22 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 mod test_nv_define_space {
-    use crate::common::create_ctx_with_session;
+    use crate::common::{create_ctx_with_session, MockSimulator};
     use tss_esapi::{
         attributes::NvIndexAttributesBuilder,
+        constants::{
+            tss::{
+                TPM2_CAP_TPM_PROPERTIES, TPM2_PT_NV_INDEX_MAX, TPM2_RC_NV_SPACE,
+                TPM2_ST_NO_SESSIONS,
+            },
+            NvIndexType, PropertyTag,
+        },
         handles::NvIndexTpmHandle,
-        interface_types::{algorithm::HashingAlgorithm, reserved_handles::Provision},
+        interface_types::{
+            algorithm::HashingAlgorithm, reserved_handles::Provision, session_handles::AuthSession,
+        },
         structures::NvPublicBuilder,
+        Context, Error, WrapperErrorKind,
     };
 
     #[test]
@@ -112,6 +122,193 @@ mod test_nv_define_space {
             .nv_undefine_space(Provision::Platform, platform_nv_index_handle)
             .expect("Call to nv_undefine_space failed");
     }
+
+    #[test]
+    fn test_nv_define_space_already_defined() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500031).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, owner_nv_public.clone())
+            .expect("Call to nv_define_space failed");
+
+        let result = context.nv_define_space(Provision::Owner, None, owner_nv_public);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::NvIndexAlreadyDefined)
+        );
+    }
+
+    #[test]
+    fn test_nv_define_space_exhausted() {
+        // Response to TPM2_GetCapability with the maximum NV index size.
+        let mut capability_response = Vec::new();
+        capability_response.extend_from_slice(&TPM2_ST_NO_SESSIONS.to_be_bytes());
+        capability_response.extend_from_slice(&27u32.to_be_bytes());
+        capability_response.extend_from_slice(&0u32.to_be_bytes());
+        capability_response.push(0);
+        capability_response.extend_from_slice(&TPM2_CAP_TPM_PROPERTIES.to_be_bytes());
+        capability_response.extend_from_slice(&1u32.to_be_bytes());
+        capability_response.extend_from_slice(&TPM2_PT_NV_INDEX_MAX.to_be_bytes());
+        capability_response.extend_from_slice(&2048u32.to_be_bytes());
+        // Response to TPM2_NV_DefineSpace when the NV memory is full.
+        let mut define_space_response = Vec::new();
+        define_space_response.extend_from_slice(&TPM2_ST_NO_SESSIONS.to_be_bytes());
+        define_space_response.extend_from_slice(&10u32.to_be_bytes());
+        define_space_response.extend_from_slice(&TPM2_RC_NV_SPACE.to_be_bytes());
+
+        let simulator = MockSimulator::start(vec![capability_response, define_space_response]);
+        let mut context = Context::new(simulator.tcti()).expect("Failed to create context");
+
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(NvIndexTpmHandle::new(0x01500033).unwrap())
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(
+                NvIndexAttributesBuilder::new()
+                    .with_owner_write(true)
+                    .with_owner_read(true)
+                    .build()
+                    .expect("Failed to create owner nv index attributes"),
+            )
+            .with_data_area_size(2048)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        assert_eq!(
+            context
+                .execute_with_session(Some(AuthSession::Password), |ctx| {
+                    ctx.nv_define_space(Provision::Owner, None, nv_public)
+                })
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::NvSpaceExhausted)
+        );
+    }
+
+    #[test]
+    fn test_nv_define_space_ordinary_64_bytes() {
+        let mut context = create_ctx_with_session();
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(NvIndexTpmHandle::new(0x01500032).unwrap())
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(
+                NvIndexAttributesBuilder::new()
+                    .with_owner_write(true)
+                    .with_owner_read(true)
+                    .build()
+                    .expect("Failed to create owner nv index attributes"),
+            )
+            .with_data_area_size(64)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, owner_nv_public.clone())
+            .expect("Call to nv_define_space failed");
+
+        let read_public = context.nv_read_public(owner_nv_index_handle);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        let (read_nv_public, _) = read_public.expect("Call to nv_read_public failed");
+        assert_eq!(read_nv_public.data_size(), 64);
+        assert_eq!(read_nv_public.nv_index(), owner_nv_public.nv_index());
+    }
+
+    #[test]
+    fn test_nv_define_space_oversize() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index_max = context
+            .get_tpm_property(PropertyTag::NvIndexMax)
+            .expect("Failed to get the maximum NV index size")
+            .expect("The TPM did not report a maximum NV index size");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(NvIndexTpmHandle::new(0x01500033).unwrap())
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(
+                NvIndexAttributesBuilder::new()
+                    .with_owner_write(true)
+                    .with_owner_read(true)
+                    .build()
+                    .expect("Failed to create owner nv index attributes"),
+            )
+            .with_data_area_size(nv_index_max as usize + 1)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        assert_eq!(
+            context
+                .nv_define_space(Provision::Owner, None, owner_nv_public)
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InvalidParam)
+        );
+    }
+
+    #[test]
+    fn test_nv_public_builder_size_checks() {
+        let counter_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_nv_index_type(NvIndexType::Counter)
+            .build()
+            .expect("Failed to create counter nv index attributes");
+        let builder = NvPublicBuilder::new()
+            .with_nv_index(NvIndexTpmHandle::new(0x01500033).unwrap())
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(counter_attributes);
+
+        // Counters hold 8 bytes.
+        assert_eq!(
+            builder.clone().with_data_area_size(32).build().unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InconsistentParams)
+        );
+        let _ = builder
+            .with_data_area_size(8)
+            .build()
+            .expect("Failed to build NvPublic for counter");
+
+        // The data size is limited by the size of the TPM2B_MAX_NV_BUFFER size field.
+        assert_eq!(
+            NvPublicBuilder::new()
+                .with_nv_index(NvIndexTpmHandle::new(0x01500033).unwrap())
+                .with_index_name_algorithm(HashingAlgorithm::Sha256)
+                .with_index_attributes(
+                    NvIndexAttributesBuilder::new()
+                        .with_owner_write(true)
+                        .with_owner_read(true)
+                        .build()
+                        .expect("Failed to create owner nv index attributes"),
+                )
+                .with_data_area_size(usize::from(u16::MAX) + 1)
+                .build()
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InvalidParam)
+        );
+    }
 }
 
 mod test_nv_undefine_space {
@@ -155,6 +352,95 @@ mod test_nv_undefine_space {
     }
 }
 
+mod test_nv_undefine_space_special {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::{tss::TPM2_RC_HANDLE, CommandCode, SessionType},
+        handles::{NvIndexTpmHandle, SessionHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::Provision,
+            session_handles::{AuthSession, PolicySession},
+        },
+        structures::{NvPublicBuilder, SymmetricDefinition},
+        Context,
+    };
+
+    fn start_policy_session(context: &mut Context, session_type: SessionType) -> AuthSession {
+        let session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                session_type,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to start auth session")
+            .expect("Received invalid handle");
+        let policy_session =
+            PolicySession::try_from(session).expect("Failed to get policy session");
+        context
+            .policy_command_code(policy_session, CommandCode::NvUndefineSpaceSpecial)
+            .expect("Failed to call policy_command_code");
+        session
+    }
+
+    #[test]
+    fn test_nv_undefine_space_special() {
+        let mut context = create_ctx_without_session();
+
+        // Compute a policy which allows the index to be deleted.
+        let trial_session = start_policy_session(&mut context, SessionType::Trial);
+        let digest = context
+            .policy_get_digest(
+                PolicySession::try_from(trial_session).expect("Failed to get policy session"),
+            )
+            .expect("Failed to get policy digest");
+        context
+            .flush_context(SessionHandle::from(trial_session).into())
+            .expect("Failed to flush trial session");
+
+        let nv_index = NvIndexTpmHandle::new(0x01500030).unwrap();
+        let nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_pp_read(true)
+            .with_platform_create(true)
+            .with_policy_delete(true)
+            .with_policy_write(true)
+            .build()
+            .expect("Failed to create nv index attributes");
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(nv_index_attributes)
+            .with_index_auth_policy(digest)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic");
+        let nv_index_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.nv_define_space(Provision::Platform, None, nv_public)
+            })
+            .expect("Call to nv_define_space failed");
+
+        // The policy session authorizes the index and the password session the platform.
+        let policy_session = start_policy_session(&mut context, SessionType::Policy);
+        context
+            .execute_with_sessions(
+                (Some(policy_session), Some(AuthSession::Password), None),
+                |ctx| ctx.nv_undefine_space_special(Provision::Platform, nv_index_handle),
+            )
+            .expect("Call to nv_undefine_space_special failed");
+
+        assert!(context
+            .tr_from_tpm_public(nv_index.into())
+            .expect_err("The NV index should have been undefined")
+            .is_rc(TPM2_RC_HANDLE));
+    }
+}
+
 mod test_nv_read_public {
     use crate::common::create_ctx_with_session;
     use tss_esapi::{
@@ -421,3 +707,397 @@ mod test_nv_increment {
         assert_eq!(first_value + 1, second_value);
     }
 }
+
+mod test_nv_write_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::return_code::TpmFormatZeroError,
+        error::{TpmFormatZeroResponseCode, TpmResponseCode},
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{NvAuth, Provision},
+        },
+        structures::{MaxNvBuffer, NvPublicBuilder},
+        Error, ReturnCode,
+    };
+
+    #[test]
+    fn test_nv_write_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500028).unwrap();
+
+        // Create owner nv public.
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_write_stclear(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let data = MaxNvBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7])
+            .expect("Failed to create MaxNvBuffer from vec");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let first_nv_write_result =
+            context.nv_write(NvAuth::Owner, owner_nv_index_handle, data.clone(), 0);
+        let nv_write_lock_result = context.nv_write_lock(NvAuth::Owner, owner_nv_index_handle);
+        let second_nv_write_result =
+            context.nv_write(NvAuth::Owner, owner_nv_index_handle, data, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        first_nv_write_result.expect("First call to nv_write failed");
+        nv_write_lock_result.expect("Call to nv_write_lock failed");
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+            TpmFormatZeroResponseCode::Error(error),
+        )))) = second_nv_write_result
+        {
+            assert_eq!(error.error_number(), TpmFormatZeroError::NvLocked);
+        } else {
+            panic!("Writing to a write locked NV index should have failed with NV locked error.");
+        }
+    }
+}
+
+mod test_nv_global_write_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::return_code::TpmFormatZeroError,
+        error::{TpmFormatZeroResponseCode, TpmResponseCode},
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{NvAuth, Provision},
+        },
+        structures::{MaxNvBuffer, NvPublicBuilder},
+        Error, ReturnCode,
+    };
+
+    #[test]
+    fn test_nv_global_write_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500029).unwrap();
+
+        // Create owner nv public.
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_global_lock(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let data = MaxNvBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7])
+            .expect("Failed to create MaxNvBuffer from vec");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let nv_global_write_lock_result = context.nv_global_write_lock(Provision::Owner);
+        let nv_write_result = context.nv_write(NvAuth::Owner, owner_nv_index_handle, data, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        nv_global_write_lock_result.expect("Call to nv_global_write_lock failed");
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+            TpmFormatZeroResponseCode::Error(error),
+        )))) = nv_write_result
+        {
+            assert_eq!(error.error_number(), TpmFormatZeroError::NvLocked);
+        } else {
+            panic!(
+                "Writing to a globally locked NV index should have failed with NV locked error."
+            );
+        }
+    }
+}
+
+mod test_nv_read_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::return_code::TpmFormatZeroError,
+        error::{TpmFormatZeroResponseCode, TpmResponseCode},
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{NvAuth, Provision},
+        },
+        structures::{MaxNvBuffer, NvPublicBuilder},
+        Error, ReturnCode,
+    };
+
+    #[test]
+    fn test_nv_read_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x0150002A).unwrap();
+
+        // Create owner nv public.
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_read_stclear(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let value = [1, 2, 3, 4, 5, 6, 7];
+        let expected_data =
+            MaxNvBuffer::try_from(value.to_vec()).expect("Failed to create MaxBuffer from data");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let nv_write_result = context.nv_write(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            expected_data.clone(),
+            0,
+        );
+        let first_nv_read_result =
+            context.nv_read(NvAuth::Owner, owner_nv_index_handle, value.len() as u16, 0);
+        let nv_read_lock_result = context.nv_read_lock(NvAuth::Owner, owner_nv_index_handle);
+        let second_nv_read_result =
+            context.nv_read(NvAuth::Owner, owner_nv_index_handle, value.len() as u16, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        nv_write_result.expect("Call to nv_write failed");
+        let actual_data = first_nv_read_result.expect("First call to nv_read failed");
+        assert_eq!(expected_data, actual_data);
+        nv_read_lock_result.expect("Call to nv_read_lock failed");
+        if let Err(Error::TssError(ReturnCode::Tpm(TpmResponseCode::FormatZero(
+            TpmFormatZeroResponseCode::Error(error),
+        )))) = second_nv_read_result
+        {
+            assert_eq!(error.error_number(), TpmFormatZeroError::NvLocked);
+        } else {
+            panic!("Reading a read locked NV index should have failed with NV locked error.");
+        }
+    }
+}
+
+#[cfg(feature = "abstraction")]
+mod test_nv_change_auth {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        abstraction::policy,
+        attributes::{NvIndexAttributesBuilder, SessionAttributesBuilder},
+        constants::{CommandCode, SessionType},
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::{NvAuth, Provision},
+            session_handles::PolicySession,
+        },
+        structures::{Auth, MaxNvBuffer, NvPublicBuilder, SymmetricDefinition},
+        Error, WrapperErrorKind,
+    };
+
+    #[test]
+    fn test_nv_change_auth() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x0150002B).unwrap();
+
+        // The admin role requires a policy, so only allow TPM2_NV_ChangeAuth.
+        let auth_policy = policy::compute_policy_digest(
+            &mut context,
+            HashingAlgorithm::Sha256,
+            |ctx, policy_session| {
+                ctx.execute_without_session(|ctx| {
+                    ctx.policy_command_code(policy_session, CommandCode::NvChangeAuth)
+                })
+            },
+        )
+        .expect("Failed to compute policy digest");
+
+        let nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_auth_write(true)
+            .with_auth_read(true)
+            .with_owner_read(true)
+            .with_no_da(true)
+            .build()
+            .expect("Failed to create nv index attributes");
+
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(nv_index_attributes)
+            .with_index_auth_policy(auth_policy)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic");
+
+        let old_auth = Auth::try_from(vec![1, 2, 3, 4]).expect("Failed to create old auth");
+        let new_auth = Auth::try_from(vec![5, 6, 7, 8]).expect("Failed to create new auth");
+        let data = MaxNvBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7])
+            .expect("Failed to create MaxNvBuffer from data");
+
+        let nv_index_handle = context
+            .nv_define_space(Provision::Owner, Some(old_auth.clone()), nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let nv_write_result = context.nv_write(
+            NvAuth::NvIndex(nv_index_handle),
+            nv_index_handle,
+            data.clone(),
+            0,
+        );
+
+        // Authorize the change using a policy session.
+        let policy_auth_session = context
+            .execute_without_session(|ctx| {
+                ctx.start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_128_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+            })
+            .expect("Failed to start policy session")
+            .expect("Received invalid handle");
+        let (session_attributes, session_attributes_mask) = SessionAttributesBuilder::new().build();
+        context
+            .tr_sess_set_attributes(
+                policy_auth_session,
+                session_attributes,
+                session_attributes_mask,
+            )
+            .expect("Failed to set session attributes");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .execute_without_session(|ctx| {
+                ctx.policy_command_code(policy_session, CommandCode::NvChangeAuth)
+            })
+            .expect("Call to policy_command_code failed");
+        let nv_change_auth_result = context
+            .execute_with_session(Some(policy_auth_session), |ctx| {
+                ctx.nv_change_auth(nv_index_handle, new_auth.clone())
+            });
+
+        // Reading with the old auth value should fail.
+        let old_auth_nv_read_result = context
+            .tr_set_auth(nv_index_handle.into(), old_auth)
+            .and_then(|_| {
+                context.nv_read(
+                    NvAuth::NvIndex(nv_index_handle),
+                    nv_index_handle,
+                    data.len() as u16,
+                    0,
+                )
+            });
+        // Reading with the new auth value should succeed.
+        let new_auth_nv_read_result = context
+            .tr_set_auth(nv_index_handle.into(), new_auth)
+            .and_then(|_| {
+                context.nv_read(
+                    NvAuth::NvIndex(nv_index_handle),
+                    nv_index_handle,
+                    data.len() as u16,
+                    0,
+                )
+            });
+
+        context
+            .nv_undefine_space(Provision::Owner, nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        nv_write_result.expect("Call to nv_write failed");
+        nv_change_auth_result.expect("Call to nv_change_auth failed");
+        assert!(
+            old_auth_nv_read_result.is_err(),
+            "Reading with the old auth value should have failed"
+        );
+        assert_eq!(
+            data,
+            new_auth_nv_read_result.expect("Reading with the new auth value failed")
+        );
+    }
+
+    #[test]
+    fn test_nv_change_auth_too_large() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x0150002C).unwrap();
+
+        let nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create nv index attributes");
+
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha1)
+            .with_index_attributes(nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic");
+
+        let nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, nv_public)
+            .expect("Call to nv_define_space failed");
+
+        // The auth value is larger than the SHA-1 digest size.
+        let nv_change_auth_result = context.nv_change_auth(
+            nv_index_handle,
+            Auth::try_from(vec![0xff; 32]).expect("Failed to create auth"),
+        );
+
+        context
+            .nv_undefine_space(Provision::Owner, nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        assert_eq!(
+            nv_change_auth_result,
+            Err(Error::WrapperError(WrapperErrorKind::WrongParamSize))
+        );
+    }
+}
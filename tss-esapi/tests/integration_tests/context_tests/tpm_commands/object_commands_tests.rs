@@ -1,8 +1,18 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 mod test_create {
-    use crate::common::{create_ctx_with_session, decryption_key_pub};
-    use tss_esapi::{interface_types::reserved_handles::Hierarchy, structures::Auth};
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        interface_types::{
+            algorithm::{AsymmetricAlgorithm, HashingAlgorithm},
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            Auth, Data, PcrSelectionListBuilder, PcrSlot, Public, SymmetricDefinitionObject,
+        },
+        Error, WrapperErrorKind,
+    };
 
     #[test]
     fn test_create() {
@@ -34,6 +44,83 @@ mod test_create {
             )
             .unwrap();
     }
+
+    #[test]
+    fn test_create_under_storage_parent() {
+        let mut context = create_ctx_with_session();
+        for asymmetric_algorithm in [AsymmetricAlgorithm::Rsa, AsymmetricAlgorithm::Ecc] {
+            let parent_public = Public::storage_parent(
+                asymmetric_algorithm,
+                HashingAlgorithm::Sha256,
+                SymmetricDefinitionObject::AES_256_CFB,
+            )
+            .expect("Failed to create storage parent template");
+            let parent_handle = context
+                .create_primary(Hierarchy::Owner, parent_public, None, None, None, None)
+                .expect("Failed to create storage parent")
+                .key_handle;
+
+            let child = context
+                .create(parent_handle, signing_key_pub(), None, None, None, None)
+                .expect("Failed to create child key");
+            let child_handle = context
+                .load(parent_handle, child.out_private, child.out_public)
+                .expect("Failed to load child key");
+
+            context.flush_context(child_handle.into()).unwrap();
+            context.flush_context(parent_handle.into()).unwrap();
+        }
+
+        assert_eq!(
+            Public::storage_parent(
+                AsymmetricAlgorithm::Null,
+                HashingAlgorithm::Sha256,
+                SymmetricDefinitionObject::AES_256_CFB,
+            ),
+            Err(Error::WrapperError(WrapperErrorKind::InvalidParam))
+        );
+    }
+
+    #[test]
+    fn test_create_with_creation_pcrs() {
+        let mut context = create_ctx_with_session();
+        let prim_key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+
+        let outside_info = Data::try_from(vec![0xaa; 16]).unwrap();
+        let creation_pcrs = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot7])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+
+        let creation_data = context
+            .create(
+                prim_key_handle,
+                decryption_key_pub(),
+                None,
+                None,
+                Some(outside_info.clone()),
+                Some(creation_pcrs),
+            )
+            .unwrap()
+            .creation_data;
+
+        assert_eq!(creation_data.outside_info(), &outside_info);
+        let selections = creation_data.pcr_select().get_selections();
+        assert_eq!(selections.len(), 1);
+        assert_eq!(selections[0].hashing_algorithm(), HashingAlgorithm::Sha256);
+        assert_eq!(selections[0].selected(), vec![PcrSlot::Slot7]);
+        assert!(!creation_data.pcr_digest().is_empty());
+    }
 }
 
 mod test_load {
@@ -74,6 +161,36 @@ mod test_load {
             .load(prim_key_handle, result.out_private, result.out_public)
             .unwrap();
     }
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn test_load_computed_name() {
+        let mut context = create_ctx_with_session();
+        let prim_key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+
+        let result = context
+            .create(prim_key_handle, signing_key_pub(), None, None, None, None)
+            .unwrap();
+        let computed_name = result.computed_name().expect("Failed to compute the name");
+
+        let key_handle = context
+            .load(prim_key_handle, result.out_private, result.out_public)
+            .unwrap();
+        let (_, name, _) = context
+            .read_public(key_handle)
+            .expect("Failed to read public");
+        assert_eq!(computed_name, name);
+    }
 }
 
 mod test_load_external_public {
@@ -231,8 +348,13 @@ mod test_load_external {
 }
 
 mod test_read_public {
-    use crate::common::{create_ctx_with_session, signing_key_pub};
-    use tss_esapi::{interface_types::reserved_handles::Hierarchy, structures::Auth};
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+    use sha2::Digest as _;
+    use tss_esapi::{
+        constants::tss::{TPM2_ALG_SHA256, TPM2_RH_OWNER},
+        interface_types::reserved_handles::Hierarchy,
+        structures::Auth,
+    };
 
     #[test]
     fn test_read_public() {
@@ -254,6 +376,69 @@ mod test_read_public {
             .key_handle;
         let _ = context.read_public(key_handle).unwrap();
     }
+
+    #[test]
+    fn test_read_public_qualified_name() {
+        let mut context = create_ctx_with_session();
+
+        let primary_key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create primary key")
+            .key_handle;
+        let (_, primary_name, primary_qualified_name) = context
+            .read_public(primary_key_handle)
+            .expect("Failed to read public of the primary key");
+
+        // The qualified name of a primary key is computed
+        // from the handle of its hierarchy.
+        let expected_primary_qualified_name =
+            qualified_name(&TPM2_RH_OWNER.to_be_bytes(), primary_name.value());
+        assert_eq!(
+            primary_qualified_name.value(),
+            expected_primary_qualified_name.as_slice()
+        );
+
+        let result = context
+            .create(
+                primary_key_handle,
+                signing_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create child key");
+        let child_key_handle = context
+            .load(primary_key_handle, result.out_private, result.out_public)
+            .expect("Failed to load child key");
+        let (_, child_name, child_qualified_name) = context
+            .read_public(child_key_handle)
+            .expect("Failed to read public of the child key");
+
+        let expected_child_qualified_name =
+            qualified_name(primary_qualified_name.value(), child_name.value());
+        assert_eq!(
+            child_qualified_name.value(),
+            expected_child_qualified_name.as_slice()
+        );
+    }
+
+    /// Computes the qualified name of an object with a SHA256 name algorithm.
+    fn qualified_name(parent_qualified_name: &[u8], name: &[u8]) -> Vec<u8> {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(parent_qualified_name);
+        hasher.update(name);
+        let mut qualified_name = TPM2_ALG_SHA256.to_be_bytes().to_vec();
+        qualified_name.extend_from_slice(&hasher.finalize());
+        qualified_name
+    }
 }
 
 mod test_make_credential {
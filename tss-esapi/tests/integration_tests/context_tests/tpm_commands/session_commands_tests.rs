@@ -6,7 +6,11 @@ mod test_start_auth_session {
     use tss_esapi::{
         attributes::SessionAttributesBuilder,
         constants::SessionType,
-        interface_types::{algorithm::HashingAlgorithm, reserved_handles::Hierarchy},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            reserved_handles::Hierarchy,
+            session_handles::{AuthSession, HmacSession, PolicySession},
+        },
         structures::{Nonce, SymmetricDefinition},
     };
 
@@ -138,8 +142,64 @@ mod test_start_auth_session {
             .unwrap_err();
         });
     }
+
+    #[test]
+    fn test_builder_sess() {
+        let mut context = create_ctx_without_session();
+        let positional_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Hmac,
+                SymmetricDefinition::AES_128_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .unwrap()
+            .unwrap();
+        let builder_session = AuthSession::builder().start(&mut context).unwrap().unwrap();
+
+        match (positional_session, builder_session) {
+            (
+                AuthSession::HmacSession(HmacSession::HmacSession {
+                    hashing_algorithm: positional_hashing_algorithm,
+                    session_handle: positional_session_handle,
+                }),
+                AuthSession::HmacSession(HmacSession::HmacSession {
+                    hashing_algorithm: builder_hashing_algorithm,
+                    session_handle: builder_session_handle,
+                }),
+            ) => {
+                assert_eq!(positional_hashing_algorithm, builder_hashing_algorithm);
+                assert_ne!(positional_session_handle, builder_session_handle);
+            }
+            _ => panic!("Both sessions should be HMAC sessions"),
+        }
+    }
+
+    #[test]
+    fn test_builder_policy_sess() {
+        let mut context = create_ctx_without_session();
+        let session = AuthSession::builder()
+            .with_session_type(SessionType::Policy)
+            .with_symmetric(SymmetricDefinition::AES_256_CFB)
+            .with_hashing(HashingAlgorithm::Sha384)
+            .start(&mut context)
+            .unwrap()
+            .unwrap();
+
+        let policy_session = PolicySession::try_from(session).unwrap();
+        let PolicySession::PolicySession {
+            hashing_algorithm,
+            session_type,
+            ..
+        } = policy_session;
+        assert_eq!(hashing_algorithm, HashingAlgorithm::Sha384);
+        assert_eq!(session_type, SessionType::Policy);
+    }
 }
 
+#[cfg(feature = "abstraction")]
 mod test_policy_restart {
     use crate::common::{create_ctx_without_session, get_pcr_policy_digest};
     use std::convert::TryFrom;
@@ -147,7 +207,7 @@ mod test_policy_restart {
         attributes::SessionAttributesBuilder,
         constants::SessionType,
         interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
-        structures::{Digest, DigestList, SymmetricDefinition},
+        structures::{Digest, DigestList, PcrSelectionListBuilder, PcrSlot, SymmetricDefinition},
     };
     #[test]
     fn test_policy_restart() {
@@ -212,4 +272,50 @@ mod test_policy_restart {
             .unwrap()
         );
     }
+
+    #[test]
+    fn test_policy_restart_after_policy_pcr() {
+        let mut context = create_ctx_without_session();
+
+        let trial_policy_session = PolicySession::try_from(
+            context
+                .start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Trial,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+                .expect("Start auth session failed")
+                .expect("Start auth session returned a NONE handle"),
+        )
+        .expect("Failed to convert auth session into policy session");
+        let zero_digest = Digest::try_from(vec![0u8; 32]).expect("Failed to create digest");
+
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+        // In a trial session the digest of the current PCR values is used.
+        context
+            .policy_pcr(trial_policy_session, None, pcr_selection_list)
+            .expect("Failed to call policy_pcr");
+        assert_ne!(
+            context
+                .policy_get_digest(trial_policy_session)
+                .expect("Failed to get the policy digest"),
+            zero_digest
+        );
+
+        context
+            .policy_restart(trial_policy_session)
+            .expect("Failed to call policy_restart");
+        assert_eq!(
+            context
+                .policy_get_digest(trial_policy_session)
+                .expect("Failed to get the policy digest"),
+            zero_digest
+        );
+    }
 }
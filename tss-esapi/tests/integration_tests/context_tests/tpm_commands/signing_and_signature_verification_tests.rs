@@ -170,10 +170,90 @@ mod test_sign {
     use crate::common::{create_ctx_with_session, signing_key_pub, HASH};
     use std::convert::TryFrom;
     use tss_esapi::{
-        interface_types::reserved_handles::Hierarchy,
-        structures::{Auth, Digest, SignatureScheme},
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
+            key_bits::RsaKeyBits,
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            Auth, Digest, MaxBuffer, Public, PublicBuilder, PublicKeyRsa,
+            PublicRsaParametersBuilder, RsaExponent, RsaScheme, SignatureScheme,
+        },
     };
 
+    fn restricted_signing_key_pub() -> Public {
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_sign_encrypt(true)
+            .with_restricted(true)
+            .build()
+            .expect("Failed to build object attributes");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Rsa)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_rsa_parameters(
+                PublicRsaParametersBuilder::new()
+                    .with_scheme(
+                        RsaScheme::create(
+                            RsaSchemeAlgorithm::RsaSsa,
+                            Some(HashingAlgorithm::Sha256),
+                        )
+                        .expect("Failed to create RSA scheme"),
+                    )
+                    .with_key_bits(RsaKeyBits::Rsa2048)
+                    .with_exponent(RsaExponent::default())
+                    .with_is_signing_key(true)
+                    .with_restricted(true)
+                    .build()
+                    .expect("Failed to build RSA parameters"),
+            )
+            .with_rsa_unique_identifier(PublicKeyRsa::default())
+            .build()
+            .expect("Failed to build restricted signing key public")
+    }
+
+    #[test]
+    fn test_sign_restricted_key_with_hashcheck_ticket() {
+        let mut context = create_ctx_with_session();
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                restricted_signing_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .expect("Failed to create restricted signing key")
+            .key_handle;
+
+        let (digest, ticket) = context
+            .hash(
+                MaxBuffer::try_from(b"Data to be signed".to_vec()).unwrap(),
+                HashingAlgorithm::Sha256,
+                Hierarchy::Owner,
+            )
+            .expect("Failed to hash data");
+
+        // A restricted key refuses to sign a digest without a hashcheck ticket.
+        let _ = context
+            .sign(key_handle, digest.clone(), SignatureScheme::Null, None)
+            .unwrap_err();
+
+        let signature = context
+            .sign(key_handle, digest.clone(), SignatureScheme::Null, ticket)
+            .expect("Failed to sign with restricted key");
+        let _ = context
+            .verify_signature(key_handle, digest, signature)
+            .expect("Failed to verify signature");
+    }
+
     #[test]
     fn test_sign() {
         let mut context = create_ctx_with_session();
@@ -261,3 +341,95 @@ mod test_sign {
             .unwrap_err();
     }
 }
+
+mod test_mac_verify {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        handles::KeyHandle,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm},
+            reserved_handles::Hierarchy,
+        },
+        structures::{
+            Digest, KeyedHashScheme, MaxBuffer, PublicBuilder, PublicKeyedHashParameters,
+        },
+        Context,
+    };
+
+    fn create_hmac_key(context: &mut Context) -> KeyHandle {
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_sign_encrypt(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .build()
+            .expect("Failed to build object attributes");
+
+        let key_pub = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::KeyedHash)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_keyed_hash_parameters(PublicKeyedHashParameters::new(
+                KeyedHashScheme::HMAC_SHA_256,
+            ))
+            .with_keyed_hash_unique_identifier(Default::default())
+            .build()
+            .expect("Failed to build public structure for key.");
+
+        context
+            .create_primary(Hierarchy::Owner, key_pub, None, None, None, None)
+            .expect("Failed to create HMAC key")
+            .key_handle
+    }
+
+    #[test]
+    fn test_mac_verify() {
+        let mut context = create_ctx_with_session();
+        let key_handle = create_hmac_key(&mut context);
+        let data = vec![1, 2, 3, 4];
+
+        let mac = context
+            .hmac(
+                key_handle.into(),
+                MaxBuffer::try_from(data.clone()).unwrap(),
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to compute HMAC");
+
+        let _ = context
+            .mac_verify(
+                key_handle,
+                Digest::try_from(data).unwrap(),
+                mac,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to verify HMAC");
+    }
+
+    #[test]
+    fn test_mac_verify_tampered_mac() {
+        let mut context = create_ctx_with_session();
+        let key_handle = create_hmac_key(&mut context);
+        let data = vec![1, 2, 3, 4];
+
+        let mac = context
+            .hmac(
+                key_handle.into(),
+                MaxBuffer::try_from(data.clone()).unwrap(),
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Failed to compute HMAC");
+        let mut tampered_mac = mac.as_bytes().to_vec();
+        tampered_mac[0] ^= 0xFF;
+
+        assert!(context
+            .mac_verify(
+                key_handle,
+                Digest::try_from(data).unwrap(),
+                Digest::try_from(tampered_mac).unwrap(),
+                HashingAlgorithm::Sha256,
+            )
+            .is_err());
+    }
+}
@@ -22,3 +22,57 @@ mod test_shutdown {
         context.startup(StartupType::Clear).unwrap();
     }
 }
+
+mod test_startup_clear {
+    use crate::common::{create_ctx_without_session, power_cycle_simulator};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::StartupType,
+        handles::PcrHandle,
+        interface_types::{algorithm::HashingAlgorithm, session_handles::AuthSession},
+        structures::{Digest, DigestValues, PcrSelectionListBuilder, PcrSlot},
+    };
+
+    #[test]
+    fn test_startup_clear_resets_pcrs() {
+        // PCR16 is used because it is extendable from the locality in which
+        // the tests are running and is reset by a clear startup.
+        let mut context = create_ctx_without_session();
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot16])
+            .build()
+            .expect("Failed to create PcrSelectionList");
+
+        let mut vals = DigestValues::new();
+        vals.set(
+            HashingAlgorithm::Sha256,
+            Digest::try_from(vec![0xab; 32]).expect("Failed to create digest"),
+        );
+        context.execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.pcr_extend(PcrHandle::Pcr16, vals)
+                .expect("Failed to extend PCR16")
+        });
+        let (_, _, extended_pcr_digests) = context
+            .pcr_read(pcr_selection_list.clone())
+            .expect("Failed to read PCR16 after extend");
+        assert_ne!(extended_pcr_digests.value()[0].as_bytes(), [0; 32]);
+
+        context
+            .shutdown(StartupType::Clear)
+            .expect("Failed to shut down the TPM");
+        // The PCRs are only reset by a startup following a TPM reset,
+        // which can only be triggered on a simulator.
+        let power_cycled = power_cycle_simulator();
+        context
+            .startup(StartupType::Clear)
+            .expect("Failed to start up the TPM");
+        if !power_cycled {
+            return;
+        }
+
+        let (_, _, reset_pcr_digests) = context
+            .pcr_read(pcr_selection_list)
+            .expect("Failed to read PCR16 after startup");
+        assert_eq!(reset_pcr_digests.value()[0].as_bytes(), [0; 32]);
+    }
+}
@@ -2,9 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 mod test_encrypt_decrypt_2 {
     use crate::common::create_ctx_without_session;
-    use std::convert::{TryFrom, TryInto};
+    use std::convert::TryFrom;
     use tss_esapi::{
-        abstraction::cipher::Cipher,
         attributes::ObjectAttributesBuilder,
         interface_types::{
             algorithm::{HashingAlgorithm, PublicAlgorithm, SymmetricMode},
@@ -14,7 +13,7 @@ mod test_encrypt_decrypt_2 {
         },
         structures::{
             Auth, InitialValue, MaxBuffer, PublicBuilder, RsaExponent, SensitiveData,
-            SymmetricCipherParameters,
+            SymmetricCipherParameters, SymmetricDefinitionObject,
         },
     };
     #[test]
@@ -34,9 +33,7 @@ mod test_encrypt_decrypt_2 {
             ctx.create_primary(
                 Hierarchy::Owner,
                 tss_esapi::utils::create_restricted_decryption_rsa_public(
-                    Cipher::aes_128_cfb()
-                        .try_into()
-                        .expect("Failed to convert from Cipher"),
+                    SymmetricDefinitionObject::AES_128_CFB,
                     RsaKeyBits::Rsa2048,
                     RsaExponent::default(),
                 )
@@ -66,9 +63,7 @@ mod test_encrypt_decrypt_2 {
             .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
             .with_object_attributes(symmetric_key_object_attributes)
             .with_symmetric_cipher_parameters(SymmetricCipherParameters::new(
-                Cipher::aes_128_cfb()
-                    .try_into()
-                    .expect("Failed to create symmteric cipher parameters from cipher"),
+                SymmetricDefinitionObject::AES_128_CFB,
             ))
             .with_symmetric_cipher_unique_identifier(Default::default())
             .build()
@@ -151,6 +146,7 @@ mod test_encrypt_decrypt_2 {
 
 mod test_hash {
     use crate::common::create_ctx_without_session;
+    use sha2::{Digest, Sha256};
     use std::convert::TryFrom;
     use tss_esapi::{
         interface_types::{algorithm::HashingAlgorithm, reserved_handles::Hierarchy},
@@ -179,6 +175,22 @@ mod test_hash {
         assert_eq!(ticket.hierarchy(), expected_hierarchy);
         assert_ne!(ticket.digest().len(), 0); // Should do some better checking of the digest
     }
+
+    #[test]
+    fn test_hash_with_null_hierarchy() {
+        let mut context = create_ctx_without_session();
+        let data = b"There is no spoon";
+        let (actual_hashed_data, ticket) = context
+            .hash(
+                MaxBuffer::try_from(data.to_vec()).unwrap(),
+                HashingAlgorithm::Sha256,
+                Hierarchy::Null,
+            )
+            .unwrap();
+        assert_eq!(&actual_hashed_data[..], &Sha256::digest(data)[..]);
+        assert_eq!(ticket.hierarchy(), Hierarchy::Null);
+        assert!(ticket.digest().is_empty());
+    }
 }
 
 mod test_hmac {
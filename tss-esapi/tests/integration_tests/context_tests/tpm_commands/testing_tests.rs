@@ -11,6 +11,32 @@ mod test_self_test {
     }
 }
 
+mod test_incremental_self_test {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{constants::AlgorithmIdentifier, structures::AlgorithmList};
+
+    #[test]
+    fn test_incremental_self_test() {
+        let mut context = create_ctx_without_session();
+        let to_test =
+            AlgorithmList::try_from(vec![AlgorithmIdentifier::Sha256, AlgorithmIdentifier::Aes])
+                .expect("Failed to create algorithm list");
+        let _ = context
+            .incremental_self_test(to_test)
+            .expect("Failed to run incremental self test");
+
+        // Everything is tested by a full self test.
+        context
+            .self_test(true)
+            .expect("Failed to run full self test");
+        let to_do_list = context
+            .incremental_self_test(AlgorithmList::new())
+            .expect("Failed to run incremental self test");
+        assert!(to_do_list.is_empty());
+    }
+}
+
 mod test_get_test_result {
     use crate::common::create_ctx_without_session;
     #[test]
@@ -19,4 +45,16 @@ mod test_get_test_result {
         let (_, rc) = context.get_test_result().unwrap();
         rc.unwrap();
     }
+
+    #[test]
+    fn test_get_test_result_after_full_self_test() {
+        let mut context = create_ctx_without_session();
+        context
+            .self_test(true)
+            .expect("Failed to run full self test");
+        let (_, rc) = context
+            .get_test_result()
+            .expect("Failed to get test result");
+        rc.expect("The full self test did not succeed");
+    }
 }
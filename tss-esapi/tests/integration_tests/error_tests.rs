@@ -6,7 +6,12 @@ mod wrapper_error_kind_tests;
 use std::{convert::TryFrom, error::Error};
 
 use tss_esapi::{
-    constants::tss::{TPM2_RC_INITIALIZE, TSS2_TPM_RC_LAYER},
+    constants::tss::{
+        TPM2_RC_1, TPM2_RC_2, TPM2_RC_ATTRIBUTES, TPM2_RC_AUTH_FAIL, TPM2_RC_BAD_AUTH, TPM2_RC_H,
+        TPM2_RC_HIERARCHY, TPM2_RC_INITIALIZE, TPM2_RC_NV_LOCKED, TPM2_RC_P, TPM2_RC_POLICY_FAIL,
+        TPM2_RC_S, TSS2_BASE_RC_BAD_REFERENCE, TSS2_ESYS_RC_LAYER, TSS2_RESMGR_TPM_RC_LAYER,
+        TSS2_TPM_RC_LAYER,
+    },
     error::{ReturnCode, WrapperErrorKind},
 };
 
@@ -55,3 +60,71 @@ fn test_display_trait_implementation() {
         format!("{}", tss_error)
     );
 }
+
+fn tss_error(tss2_rc: u32) -> tss_esapi::Error {
+    tss_esapi::Error::TssError(
+        ReturnCode::try_from(tss2_rc)
+            .expect("Failed to convert TSS return code into a ReturnCode object."),
+    )
+}
+
+#[test]
+fn test_is_rc() {
+    let session_bad_auth = tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_BAD_AUTH | TPM2_RC_S | TPM2_RC_1);
+    assert!(session_bad_auth.is_rc(TPM2_RC_BAD_AUTH));
+    assert!(session_bad_auth.is_rc(TPM2_RC_BAD_AUTH | TPM2_RC_S | TPM2_RC_1));
+    assert!(!session_bad_auth.is_rc(TPM2_RC_BAD_AUTH | TPM2_RC_S | TPM2_RC_2));
+    assert!(!session_bad_auth.is_rc(TPM2_RC_AUTH_FAIL));
+
+    let nv_locked = tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_NV_LOCKED);
+    assert!(nv_locked.is_rc(TPM2_RC_NV_LOCKED));
+    assert!(!nv_locked.is_rc(TPM2_RC_INITIALIZE));
+    // The same response code from a different layer is not a match.
+    assert!(!nv_locked.is_rc(TSS2_RESMGR_TPM_RC_LAYER | TPM2_RC_NV_LOCKED));
+
+    let esapi_error = tss_error(TSS2_ESYS_RC_LAYER | TSS2_BASE_RC_BAD_REFERENCE);
+    assert!(esapi_error.is_rc(TSS2_ESYS_RC_LAYER | TSS2_BASE_RC_BAD_REFERENCE));
+    assert!(!esapi_error.is_rc(TSS2_BASE_RC_BAD_REFERENCE));
+
+    let wrapper_error = tss_esapi::Error::WrapperError(WrapperErrorKind::InvalidParam);
+    assert!(!wrapper_error.is_rc(TPM2_RC_BAD_AUTH));
+}
+
+#[test]
+fn test_is_bad_auth() {
+    assert!(tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_BAD_AUTH).is_bad_auth());
+    assert!(tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_BAD_AUTH | TPM2_RC_S | TPM2_RC_1).is_bad_auth());
+    assert!(
+        !tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_AUTH_FAIL | TPM2_RC_S | TPM2_RC_1).is_bad_auth()
+    );
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_POLICY_FAIL).is_bad_auth());
+    assert!(!tss_esapi::Error::WrapperError(WrapperErrorKind::InvalidParam).is_bad_auth());
+}
+
+#[test]
+fn test_is_policy_fail() {
+    assert!(tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_POLICY_FAIL).is_policy_fail());
+    assert!(
+        tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_POLICY_FAIL | TPM2_RC_P | TPM2_RC_2).is_policy_fail()
+    );
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_BAD_AUTH).is_policy_fail());
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_NV_LOCKED).is_policy_fail());
+}
+
+#[test]
+fn test_is_attributes() {
+    assert!(tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_ATTRIBUTES).is_attributes());
+    assert!(
+        tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_ATTRIBUTES | TPM2_RC_H | TPM2_RC_2).is_attributes()
+    );
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_HIERARCHY).is_attributes());
+    assert!(!tss_esapi::Error::WrapperError(WrapperErrorKind::InvalidParam).is_attributes());
+}
+
+#[test]
+fn test_is_nv_locked() {
+    assert!(tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_NV_LOCKED).is_nv_locked());
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_INITIALIZE).is_nv_locked());
+    assert!(!tss_error(TSS2_TPM_RC_LAYER | TPM2_RC_BAD_AUTH).is_nv_locked());
+    assert!(!tss_esapi::Error::WrapperError(WrapperErrorKind::NvIndexNotWritten).is_nv_locked());
+}
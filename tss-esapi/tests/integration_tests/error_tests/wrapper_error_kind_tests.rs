@@ -1,6 +1,10 @@
 // Copyright 2023 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use tss_esapi::error::WrapperErrorKind;
+use std::convert::TryFrom;
+use tss_esapi::{
+    constants::BaseError,
+    error::{TctiReturnCode, WrapperErrorKind},
+};
 
 #[test]
 fn test_display() {
@@ -44,6 +48,49 @@ fn test_display() {
         format!("{}", WrapperErrorKind::InvalidHandleState)
     );
 
+    assert_eq!(
+        "The NV index has not been written.",
+        format!("{}", WrapperErrorKind::NvIndexNotWritten)
+    );
+
+    assert_eq!(
+        "The NV index is already defined.",
+        format!("{}", WrapperErrorKind::NvIndexAlreadyDefined)
+    );
+
+    assert_eq!(
+        "There is not enough NV memory left.",
+        format!("{}", WrapperErrorKind::NvSpaceExhausted)
+    );
+
+    assert_eq!(
+        "The PCR values do not match the expected digest.",
+        format!("{}", WrapperErrorKind::PcrMismatch)
+    );
+
+    assert_eq!(
+        "The session has already been flushed.",
+        format!("{}", WrapperErrorKind::SessionAlreadyFlushed)
+    );
+
+    assert_eq!(
+        "The TPM did not respond in time.",
+        format!("{}", WrapperErrorKind::Timeout)
+    );
+
+    assert_eq!(
+        "The command is not supported by the TPM.",
+        format!("{}", WrapperErrorKind::UnsupportedCommand)
+    );
+
+    assert_eq!(
+        "The TCTI failed to reach the TPM: IO failure.",
+        format!(
+            "{}",
+            WrapperErrorKind::Transport(TctiReturnCode::try_from(BaseError::IoError).unwrap())
+        )
+    );
+
     assert_eq!(
         "An unexpected error occurred within the crate.",
         format!("{}", WrapperErrorKind::InternalError)
@@ -6,6 +6,11 @@ use tss_esapi::{
         ESYS_TR, ESYS_TR_RH_ENDORSEMENT, ESYS_TR_RH_LOCKOUT, ESYS_TR_RH_OWNER, ESYS_TR_RH_PLATFORM,
     },
 };
+#[cfg(has_act)]
+use tss_esapi::{
+    tss2_esys::{ESYS_TR_RH_ACT_FIRST, ESYS_TR_RH_ACT_LAST},
+    Error, WrapperErrorKind,
+};
 
 #[test]
 fn test_constants_conversions() {
@@ -35,3 +40,21 @@ fn test_constants_conversions() {
         AuthHandle::Platform,
     );
 }
+
+#[cfg(has_act)]
+#[test]
+fn test_act_handles() {
+    assert_eq!(
+        ESYS_TR_RH_ACT_FIRST,
+        ESYS_TR::from(AuthHandle::act(0).expect("Failed to create handle for ACT_0"))
+    );
+    assert_eq!(
+        ESYS_TR_RH_ACT_LAST,
+        ESYS_TR::from(AuthHandle::act(15).expect("Failed to create handle for ACT_F"))
+    );
+    assert_eq!(
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam)),
+        AuthHandle::act(16),
+        "Creating a handle for a non existing ACT did not produce the expected error"
+    );
+}
@@ -103,6 +103,19 @@ mod hashing_algorithm_tests {
             WrapperErrorKind::InvalidParam
         )
     }
+
+    #[test]
+    fn test_digest_size() {
+        assert_eq!(HashingAlgorithm::Sha1.digest_size(), Some(20));
+        assert_eq!(HashingAlgorithm::Sha256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha384.digest_size(), Some(48));
+        assert_eq!(HashingAlgorithm::Sha512.digest_size(), Some(64));
+        assert_eq!(HashingAlgorithm::Sm3_256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha3_256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha3_384.digest_size(), Some(48));
+        assert_eq!(HashingAlgorithm::Sha3_512.digest_size(), Some(64));
+        assert_eq!(HashingAlgorithm::Null.digest_size(), None);
+    }
 }
 
 mod keyed_hash_scheme_tests {
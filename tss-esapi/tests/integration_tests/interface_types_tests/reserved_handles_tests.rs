@@ -14,22 +14,28 @@ mod test_hierarchy {
     use super::*;
     #[test]
     fn test_conversions() {
-        let test_conversion =
-            |hierarchy: Hierarchy, tpm_rh: TpmHandle, esys_rh: ObjectHandle, name: &str| {
-                assert_eq!(ObjectHandle::from(hierarchy), esys_rh);
-                assert_eq!(TpmHandle::from(hierarchy), tpm_rh);
-                let from_esys_rh = Hierarchy::try_from(esys_rh).unwrap_or_else(|_| {
-                    panic!("Failed to create Hierarchy from ESYS_TR_RH={}", name)
-                });
-                assert_eq!(from_esys_rh, hierarchy);
-                assert_eq!(ObjectHandle::from(from_esys_rh), esys_rh);
-                assert_eq!(TpmHandle::from(from_esys_rh), tpm_rh);
-                let from_tpm_rh = Hierarchy::try_from(tpm_rh)
-                    .unwrap_or_else(|_| panic!("Failed to create Hierarchy from TPM2_RH={}", name));
-                assert_eq!(from_tpm_rh, hierarchy);
-                assert_eq!(ObjectHandle::from(from_tpm_rh), esys_rh);
-                assert_eq!(TpmHandle::from(from_tpm_rh), tpm_rh);
-            };
+        let test_conversion = |hierarchy: Hierarchy,
+                               tpm_rh: TpmHandle,
+                               esys_rh: ObjectHandle,
+                               name: &str| {
+            assert_eq!(ObjectHandle::from(hierarchy), esys_rh);
+            assert_eq!(TpmHandle::from(hierarchy), tpm_rh);
+            assert_eq!(AuthHandle::from(hierarchy), AuthHandle::from(esys_rh));
+            assert_eq!(ESYS_TR::from(hierarchy), ESYS_TR::from(esys_rh));
+            let from_auth_handle = Hierarchy::try_from(AuthHandle::from(esys_rh))
+                .unwrap_or_else(|_| panic!("Failed to create Hierarchy from AuthHandle={}", name));
+            assert_eq!(from_auth_handle, hierarchy);
+            let from_esys_rh = Hierarchy::try_from(esys_rh)
+                .unwrap_or_else(|_| panic!("Failed to create Hierarchy from ESYS_TR_RH={}", name));
+            assert_eq!(from_esys_rh, hierarchy);
+            assert_eq!(ObjectHandle::from(from_esys_rh), esys_rh);
+            assert_eq!(TpmHandle::from(from_esys_rh), tpm_rh);
+            let from_tpm_rh = Hierarchy::try_from(tpm_rh)
+                .unwrap_or_else(|_| panic!("Failed to create Hierarchy from TPM2_RH={}", name));
+            assert_eq!(from_tpm_rh, hierarchy);
+            assert_eq!(ObjectHandle::from(from_tpm_rh), esys_rh);
+            assert_eq!(TpmHandle::from(from_tpm_rh), tpm_rh);
+        };
 
         test_conversion(
             Hierarchy::Owner,
@@ -13,6 +13,7 @@ use tss_esapi::{
         TPMS_CREATION_INFO, TPMS_NV_CERTIFY_INFO, TPMS_QUOTE_INFO, TPMS_SESSION_AUDIT_INFO,
         TPMS_TIME_ATTEST_INFO, TPMS_TIME_INFO,
     },
+    Error, WrapperErrorKind,
 };
 
 use std::convert::{TryFrom, TryInto};
@@ -412,6 +413,50 @@ fn test_marshall_and_unmarshall() {
     }
 }
 
+fn create_marshalled_certify_attest() -> Vec<u8> {
+    let attest_info = AttestInfo::Certify {
+        info: TPMS_CERTIFY_INFO {
+            name: Name::try_from(vec![0xffu8; 64])
+                .expect("Failed to create name")
+                .into(),
+            qualifiedName: Name::try_from(vec![0x0fu8; 64])
+                .expect("Failed to create qualified name")
+                .into(),
+        }
+        .try_into()
+        .expect("Failed to convert TPMS_CERTIFY_INFO to CertifyInfo"),
+    };
+    let (attest, _) = create_validated_test_parameters(attest_info, AttestationType::Certify);
+    attest.marshall().expect("Failed to marshall data")
+}
+
+#[test]
+fn test_unmarshall_with_invalid_magic() {
+    let mut marshalled_attest = create_marshalled_certify_attest();
+    // The magic value is the first field.
+    marshalled_attest[..4].copy_from_slice(&0xdeadbeefu32.to_be_bytes());
+
+    assert_eq!(
+        Error::WrapperError(WrapperErrorKind::InvalidParam),
+        Attest::unmarshall(&marshalled_attest)
+            .expect_err("Attest with an invalid magic value was unmarshalled"),
+    );
+}
+
+#[test]
+fn test_unmarshall_with_invalid_type() {
+    let mut marshalled_attest = create_marshalled_certify_attest();
+    // The type follows the magic value, TPM2_ST_NO_SESSIONS
+    // is not an attestation type.
+    marshalled_attest[4..6].copy_from_slice(&0x8001u16.to_be_bytes());
+
+    assert_eq!(
+        Error::WrapperError(WrapperErrorKind::InvalidParam),
+        Attest::unmarshall(&marshalled_attest)
+            .expect_err("Attest with an invalid type was unmarshalled"),
+    );
+}
+
 fn create_validated_test_parameters(
     expected_attest_info: AttestInfo,
     expected_attestation_type: AttestationType,
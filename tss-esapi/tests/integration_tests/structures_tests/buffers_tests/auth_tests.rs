@@ -55,4 +55,10 @@ mod test_auth {
             assert_eq!(expected, actual);
         }
     }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let auth = Auth::try_from(vec![0x73, 0x65, 0x63, 0x72, 0x65, 0x74]).unwrap();
+        assert_eq!(format!("{:?}", auth), "Auth([redacted])");
+    }
 }
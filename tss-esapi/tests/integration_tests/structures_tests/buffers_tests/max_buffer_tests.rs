@@ -60,4 +60,31 @@ mod test_auth {
         }
         accepts_returns_as_ref(MaxBuffer::from_bytes(&[1, 2, 3]).unwrap()).unwrap();
     }
+
+    #[test]
+    fn test_chunks() {
+        let data: Vec<u8> = (0..(2 * MaxBuffer::MAX_SIZE + 10))
+            .map(|i| i as u8)
+            .collect();
+        let chunks: Vec<MaxBuffer> = MaxBuffer::chunks(&data).collect();
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.len())
+                .collect::<Vec<usize>>(),
+            vec![MaxBuffer::MAX_SIZE, MaxBuffer::MAX_SIZE, 10]
+        );
+        assert_eq!(
+            chunks
+                .iter()
+                .flat_map(|chunk| chunk.iter().copied())
+                .collect::<Vec<u8>>(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_chunks_empty_data() {
+        assert_eq!(MaxBuffer::chunks(&[]).count(), 0);
+    }
 }
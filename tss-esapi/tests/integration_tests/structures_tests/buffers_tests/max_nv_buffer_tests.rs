@@ -0,0 +1,68 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{structures::MaxNvBuffer, Error, WrapperErrorKind};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[test]
+fn test_chunks() {
+    let data: Vec<u8> = (0..100).map(|i| i as u8).collect();
+    let chunks: Vec<MaxNvBuffer> = MaxNvBuffer::chunks(&data, 32)
+        .expect("Failed to create chunks")
+        .collect();
+    assert_eq!(
+        chunks
+            .iter()
+            .map(|chunk| chunk.len())
+            .collect::<Vec<usize>>(),
+        vec![32, 32, 32, 4]
+    );
+    assert_eq!(
+        chunks
+            .iter()
+            .flat_map(|chunk| chunk.iter().copied())
+            .collect::<Vec<u8>>(),
+        data
+    );
+}
+
+#[test]
+fn test_chunks_buffer_max_above_max_size() {
+    let data = vec![0xff; MaxNvBuffer::MAX_SIZE + 1];
+    let chunks: Vec<MaxNvBuffer> = MaxNvBuffer::chunks(&data, MaxNvBuffer::MAX_SIZE * 2)
+        .expect("Failed to create chunks")
+        .collect();
+    assert_eq!(
+        chunks
+            .iter()
+            .map(|chunk| chunk.len())
+            .collect::<Vec<usize>>(),
+        vec![MaxNvBuffer::MAX_SIZE, 1]
+    );
+    assert_eq!(
+        chunks
+            .iter()
+            .flat_map(|chunk| chunk.iter().copied())
+            .collect::<Vec<u8>>(),
+        data
+    );
+}
+
+#[test]
+fn test_chunks_zero_buffer_max() {
+    assert_eq!(
+        MaxNvBuffer::chunks(&[1, 2, 3], 0).err(),
+        Some(Error::WrapperError(WrapperErrorKind::WrongParamSize))
+    );
+}
+
+#[test]
+fn test_zeroize_on_drop() {
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<MaxNvBuffer>();
+
+    let mut buffer = MaxNvBuffer::try_from(vec![0xff; 32]).expect("Failed to create buffer");
+    buffer.zeroize();
+    assert!(buffer.is_empty());
+    assert_eq!(buffer, MaxNvBuffer::default());
+}
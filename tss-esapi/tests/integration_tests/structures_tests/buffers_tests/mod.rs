@@ -5,8 +5,10 @@ mod auth_tests;
 mod data_tests;
 mod digest_tests;
 mod max_buffer_tests;
+mod max_nv_buffer_tests;
 mod nonce_tests;
 mod private;
 mod public;
+mod public_key_rsa_tests;
 mod sensitive;
 mod sensitive_create_buffer_tests;
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::convert::TryFrom;
-use tss_esapi::structures::Private;
+use tss_esapi::structures::{EncryptedSecret, Private};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[test]
 fn marshall_unmarshall() {
@@ -24,3 +25,23 @@ fn marshall_unmarshall_offset() {
     let private = Private::try_from([0xff; 100].to_vec()).unwrap();
     crate::common::check_marshall_unmarshall_offset(&private);
 }
+
+#[test]
+fn zeroize_on_drop() {
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<Private>();
+    assert_zeroize_on_drop::<EncryptedSecret>();
+}
+
+#[test]
+fn zeroize() {
+    let mut private = Private::try_from([0xff; 100].to_vec()).unwrap();
+    private.zeroize();
+    assert!(private.is_empty());
+    assert_eq!(private, Private::default());
+
+    let mut encrypted_secret = EncryptedSecret::try_from([0xff; 100].to_vec()).unwrap();
+    encrypted_secret.zeroize();
+    assert!(encrypted_secret.is_empty());
+    assert_eq!(encrypted_secret, EncryptedSecret::default());
+}
@@ -0,0 +1,43 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use tss_esapi::{
+    interface_types::key_bits::RsaKeyBits, structures::PublicKeyRsa, Error, WrapperErrorKind,
+};
+
+#[test]
+fn test_from_be_bytes_padded_restores_stripped_leading_zero() {
+    let mut modulus = vec![0u8; 256];
+    modulus[1..].iter_mut().for_each(|byte| *byte = 0xab);
+    // Big number libraries strip the leading zero byte.
+    let stripped_modulus = &modulus[1..];
+
+    let public_key_rsa = PublicKeyRsa::from_be_bytes_padded(stripped_modulus, RsaKeyBits::Rsa2048)
+        .expect("Failed to create PublicKeyRsa from stripped modulus");
+    assert_eq!(public_key_rsa.len(), 256);
+    assert_eq!(public_key_rsa.as_bytes(), modulus.as_slice());
+
+    let public_key_rsa = PublicKeyRsa::from_be_bytes_padded(&modulus, RsaKeyBits::Rsa2048)
+        .expect("Failed to create PublicKeyRsa from full modulus");
+    assert_eq!(public_key_rsa.as_bytes(), modulus.as_slice());
+}
+
+#[test]
+fn test_from_be_bytes_padded_removes_sign_byte() {
+    let modulus = vec![0xff; 128];
+    // ASN.1 encoded integers have a leading zero when the high bit is set.
+    let mut asn1_modulus = vec![0u8];
+    asn1_modulus.extend_from_slice(&modulus);
+
+    let public_key_rsa = PublicKeyRsa::from_be_bytes_padded(&asn1_modulus, RsaKeyBits::Rsa1024)
+        .expect("Failed to create PublicKeyRsa from ASN.1 modulus");
+    assert_eq!(public_key_rsa.len(), 128);
+    assert_eq!(public_key_rsa.as_bytes(), modulus.as_slice());
+}
+
+#[test]
+fn test_from_be_bytes_padded_too_large() {
+    assert_eq!(
+        PublicKeyRsa::from_be_bytes_padded(&[0xff; 257], RsaKeyBits::Rsa2048),
+        Err(Error::WrapperError(WrapperErrorKind::WrongParamSize))
+    );
+}
@@ -3,7 +3,8 @@
 
 use std::convert::TryFrom;
 use tss_esapi::{
-    structures::{Sensitive, SensitiveBuffer},
+    structures::{Auth, Digest, PrivateKeyRsa, Sensitive, SensitiveBuffer},
+    traits::Marshall,
     Error, WrapperErrorKind,
 };
 
@@ -40,3 +41,55 @@ fn marshall_unmarshall() {
         );
     });
 }
+
+#[test]
+fn marshall_rsa_prime() {
+    let prime = vec![0xab; 128];
+    let sensitive = Sensitive::Rsa {
+        auth_value: Auth::try_from(vec![1, 2, 3, 4]).expect("Failed to create auth value"),
+        seed_value: Digest::default(),
+        sensitive: PrivateKeyRsa::try_from(prime.clone()).expect("Failed to create RSA prime"),
+    };
+
+    let mut expected_sensitive = vec![
+        0x00, 0x01, // sensitiveType (TPM2_ALG_RSA)
+        0x00, 0x04, 0x01, 0x02, 0x03, 0x04, // authValue
+        0x00, 0x00, // seedValue
+        0x00, 0x80, // sensitive.rsa size
+    ];
+    expected_sensitive.extend_from_slice(&prime);
+    let mut expected_sensitive_buffer = (expected_sensitive.len() as u16).to_be_bytes().to_vec();
+    expected_sensitive_buffer.extend_from_slice(&expected_sensitive);
+
+    assert_eq!(
+        sensitive.marshall().expect("Failed to marshall Sensitive"),
+        expected_sensitive
+    );
+    assert_eq!(
+        SensitiveBuffer::try_from(sensitive)
+            .expect("Failed to convert from Sensitive to SensitiveBuffer")
+            .marshall()
+            .expect("Failed to marshall SensitiveBuffer"),
+        expected_sensitive_buffer
+    );
+}
+
+#[test]
+fn test_debug_is_redacted() {
+    let sensitive = Sensitive::Rsa {
+        auth_value: Auth::try_from(vec![1, 2, 3, 4]).expect("Failed to create auth value"),
+        seed_value: Digest::try_from(vec![5, 6, 7, 8]).expect("Failed to create seed value"),
+        sensitive: PrivateKeyRsa::try_from(vec![0xab; 128]).expect("Failed to create RSA prime"),
+    };
+    assert_eq!(
+        format!("{:?}", sensitive),
+        "Rsa { auth_value: Auth([redacted]), seed_value: [redacted], sensitive: [redacted] }"
+    );
+
+    let sensitive_buffer =
+        SensitiveBuffer::try_from(sensitive).expect("Failed to create SensitiveBuffer");
+    assert_eq!(
+        format!("{:?}", sensitive_buffer),
+        "SensitiveBuffer([redacted])"
+    );
+}
@@ -133,3 +133,21 @@ fn test_conversion_from_max_size_buffer() {
         .expect("It should be possible to convert maximum amount of data into SensitiveData.");
     let _ = TPM2B_SENSITIVE_DATA::from(sensitive_data);
 }
+
+#[test]
+fn test_debug_is_redacted() {
+    let sensitive_create = SensitiveCreate::new(
+        Auth::try_from(vec![0xaa; 16]).expect("Failed to create Auth"),
+        SensitiveData::try_from(vec![0xbb; 16]).expect("Failed to create SensitiveData"),
+    );
+    assert_eq!(
+        format!("{:?}", sensitive_create),
+        "SensitiveCreate { user_auth: Auth([redacted]), data: SensitiveData([redacted]) }"
+    );
+    let sensitive_create_buffer = SensitiveCreateBuffer::try_from(sensitive_create)
+        .expect("Failed to create SensitiveCreateBuffer");
+    assert_eq!(
+        format!("{:?}", sensitive_create_buffer),
+        "SensitiveCreateBuffer([redacted])"
+    );
+}
@@ -1,8 +1,14 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 
+use tss_esapi::constants::tss::{TPM2_CAP_TPM_PROPERTIES, TPM2_PT_FIXED};
+#[cfg(has_act)]
+use tss_esapi::constants::tss::{TPM2_RH_ACT_0, TPM2_RH_ACT_F};
 use tss_esapi::constants::CapabilityType;
 use tss_esapi::structures::CapabilityData;
+use tss_esapi::traits::{Marshall, UnMarshall};
+#[cfg(has_act)]
+use tss_esapi::tss2_esys::TPM2_HANDLE;
 
 use crate::common::create_ctx_without_session;
 
@@ -158,17 +164,42 @@ fn test_ecc_curves() {
 //     }
 // }
 
-// For these tests to work the tpm2-tss library need to have the
-// actData field in the TPMU_CAPABILITIES union.
-// #[test]
-// fn test_act() {
-//     let mut context = create_ctx_without_session();
+#[cfg(has_act)]
+#[test]
+fn test_act() {
+    let mut context = create_ctx_without_session();
 
-//     let (capabs, _more) = context.get_capability(CapabilityType::Act, 0, 80).unwrap();
+    let (capabs, _more) = context
+        .get_capability(CapabilityType::Act, TPM2_RH_ACT_0, 16)
+        .unwrap();
 
-//     if let CapabilityData::ActData(list) = capabs {
-//         assert!(!list.is_empty());
-//     } else {
-//         panic!("Got wrong type of capability data: {:?}", capabs);
-//     }
-// }
+    // The number of implemented ACTs is TPM dependent so the
+    // list may be empty.
+    if let CapabilityData::ActData(list) = capabs {
+        assert!(list.iter().all(|act_data| {
+            let handle = TPM2_HANDLE::from(act_data.handle());
+            (TPM2_RH_ACT_0..=TPM2_RH_ACT_F).contains(&handle)
+        }));
+    } else {
+        panic!("Got wrong type of capability data: {:?}", capabs);
+    }
+}
+
+#[test]
+fn test_marshall_unmarshall_tpm_properties() {
+    let mut context = create_ctx_without_session();
+
+    let (capabs, _more) = context
+        .get_capability(CapabilityType::TpmProperties, TPM2_PT_FIXED, 10)
+        .unwrap();
+    assert!(matches!(capabs, CapabilityData::TpmProperties(_)));
+
+    let marshalled = capabs
+        .marshall()
+        .expect("Failed to marshall capability data");
+    assert_eq!(&marshalled[..4], &TPM2_CAP_TPM_PROPERTIES.to_be_bytes());
+
+    let unmarshalled =
+        CapabilityData::unmarshall(&marshalled).expect("Failed to unmarshall capability data");
+    assert_eq!(unmarshalled, capabs);
+}
@@ -0,0 +1,26 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Digest, HashAgile},
+};
+
+#[test]
+fn test_marshall_unmarshall() {
+    let hash_agile = HashAgile::new(
+        HashingAlgorithm::Sha256,
+        Digest::try_from(vec![0xab; 32]).expect("Failed to create digest"),
+    );
+    crate::common::check_marshall_unmarshall(&hash_agile);
+    crate::common::check_marshall_unmarshall_offset(&hash_agile);
+}
+
+#[test]
+fn test_marshall_unmarshall_sha1() {
+    let hash_agile = HashAgile::new(
+        HashingAlgorithm::Sha1,
+        Digest::try_from(vec![0xcd; 20]).expect("Failed to create digest"),
+    );
+    crate::common::check_marshall_unmarshall(&hash_agile);
+}
@@ -0,0 +1,68 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    constants::AlgorithmIdentifier,
+    structures::AlgorithmList,
+    tss2_esys::{TPM2_ALG_ID, TPML_ALG},
+    Error, WrapperErrorKind,
+};
+
+#[test]
+fn test_conversions() {
+    let expected_algorithms = vec![
+        AlgorithmIdentifier::Sha256,
+        AlgorithmIdentifier::Rsa,
+        AlgorithmIdentifier::Aes,
+    ];
+    let mut algorithm_list = AlgorithmList::new();
+    for algorithm in expected_algorithms.iter() {
+        algorithm_list
+            .add(*algorithm)
+            .expect("Failed to add algorithm to list");
+    }
+    assert_eq!(expected_algorithms.len(), algorithm_list.len());
+
+    let tpml_alg = TPML_ALG::from(algorithm_list.clone());
+    assert_eq!(expected_algorithms.len(), tpml_alg.count as usize);
+    expected_algorithms
+        .iter()
+        .zip(tpml_alg.algorithms[..tpml_alg.count as usize].iter())
+        .for_each(|(expected, actual)| {
+            assert_eq!(
+                TPM2_ALG_ID::from(*expected),
+                *actual,
+                "The converted TPML_ALG did not contain the expected values"
+            );
+        });
+
+    let converted =
+        AlgorithmList::try_from(tpml_alg).expect("Failed to convert TPML_ALG into AlgorithmList");
+    assert_eq!(algorithm_list, converted);
+    assert_eq!(expected_algorithms, converted.into_inner());
+}
+
+#[test]
+fn test_max_size() {
+    let mut algorithm_list = AlgorithmList::new();
+    for _ in 0..AlgorithmList::MAX_SIZE {
+        algorithm_list
+            .add(AlgorithmIdentifier::Sha256)
+            .expect("Failed to add algorithm to list");
+    }
+    assert_eq!(
+        Err(Error::WrapperError(WrapperErrorKind::WrongParamSize)),
+        algorithm_list.add(AlgorithmIdentifier::Sha256),
+        "Adding more algorithms than the maximum size did not produce the expected error"
+    );
+
+    let invalid_tpml_alg = TPML_ALG {
+        count: AlgorithmList::MAX_SIZE as u32 + 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(Error::WrapperError(WrapperErrorKind::InvalidParam)),
+        AlgorithmList::try_from(invalid_tpml_alg),
+        "Converting a TPML_ALG with an invalid count did not produce the expected error"
+    );
+}
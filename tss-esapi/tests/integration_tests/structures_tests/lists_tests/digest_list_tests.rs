@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::convert::TryFrom;
 use tss_esapi::structures::{Digest, DigestList};
+use tss_esapi::traits::{Marshall, UnMarshall};
 use tss_esapi::tss2_esys::{TPM2B_DIGEST, TPML_DIGEST};
+use tss_esapi::{Error, WrapperErrorKind};
 
 #[test]
 fn test_conversion_from_tss_digest_list() {
@@ -44,3 +46,74 @@ fn test_add_exceeding_max_limit() {
     }
     digest_list.add(digest).unwrap_err();
 }
+
+fn policy_digests(count: u8) -> Vec<Digest> {
+    (0..count)
+        .map(|i| Digest::try_from(vec![i; 32]).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_policy_or_branches_two_digests() {
+    let digest_list = DigestList::policy_or_branches(policy_digests(2)).unwrap();
+    assert_eq!(digest_list.len(), 2);
+}
+
+#[test]
+fn test_policy_or_branches_max_digests() {
+    let digest_list = DigestList::policy_or_branches(policy_digests(8)).unwrap();
+    assert_eq!(digest_list.len(), DigestList::MAX_SIZE);
+}
+
+#[test]
+fn test_policy_or_branches_exceeding_max_digests() {
+    assert_eq!(
+        DigestList::policy_or_branches(policy_digests(9)).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+}
+
+#[test]
+fn test_policy_or_branches_too_few_digests() {
+    for count in 0..2 {
+        assert_eq!(
+            DigestList::policy_or_branches(policy_digests(count)).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::WrongParamSize)
+        );
+    }
+}
+
+#[test]
+fn test_policy_or_branches_inconsistent_digest_size() {
+    assert_eq!(
+        DigestList::policy_or_branches(vec![
+            Digest::try_from(vec![1; 32]).unwrap(),
+            Digest::try_from(vec![2; 48]).unwrap(),
+        ])
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+}
+
+#[test]
+fn test_mixed_digest_sizes() {
+    // Digests of different PCR banks can be held in the same list.
+    let digest_list = DigestList::try_from(vec![
+        Digest::try_from(vec![1; 32]).unwrap(),
+        Digest::try_from(vec![2; 48]).unwrap(),
+    ])
+    .unwrap();
+    assert_eq!(digest_list.len(), 2);
+}
+
+#[test]
+fn test_marshall_unmarshall() {
+    let digest_list = DigestList::try_from(policy_digests(3)).unwrap();
+    let marshalled = digest_list.marshall().unwrap();
+    // count(u32) followed by each digest as size(u16) || buffer
+    assert_eq!(marshalled.len(), 4 + 3 * (2 + 32));
+    assert_eq!(&marshalled[..4], &3u32.to_be_bytes());
+
+    let unmarshalled = DigestList::unmarshall(&marshalled).unwrap();
+    assert_eq!(unmarshalled.value(), digest_list.value());
+}
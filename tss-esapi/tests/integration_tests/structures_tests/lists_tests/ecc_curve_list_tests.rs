@@ -127,6 +127,24 @@ fn test_invalid_size_tpml() {
     );
 }
 
+#[test]
+fn test_unknown_curves_are_skipped() {
+    let mut tpml = TPML_ECC_CURVE {
+        count: 3,
+        eccCurves: [0; 508],
+    };
+    tpml.eccCurves[0] = TPM2_ECC_CURVE::from(EccCurveIdentifier::NistP256);
+    tpml.eccCurves[1] = 0x7fff;
+    tpml.eccCurves[2] = TPM2_ECC_CURVE::from(EccCurveIdentifier::NistP384);
+
+    let ecc_curve_list =
+        EccCurveList::try_from(tpml).expect("Failed to convert from TPML_ECC_CURVE");
+    assert_eq!(
+        vec![EccCurveIdentifier::NistP256, EccCurveIdentifier::NistP384],
+        ecc_curve_list.into_inner()
+    );
+}
+
 #[test]
 fn test_invalid_size_vec() {
     let vec = vec![EccCurveIdentifier::NistP256; EccCurveList::MAX_SIZE + 1];
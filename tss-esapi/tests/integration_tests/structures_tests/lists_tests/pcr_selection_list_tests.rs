@@ -3,7 +3,8 @@
 use std::convert::TryFrom;
 use tss_esapi::{
     interface_types::algorithm::HashingAlgorithm,
-    structures::{PcrSelectSize, PcrSelection, PcrSelectionList, PcrSlot},
+    structures::{PcrSelectSize, PcrSelection, PcrSelectionList, PcrSelectionListBuilder, PcrSlot},
+    traits::{Marshall, UnMarshall},
     tss2_esys::{TPML_PCR_SELECTION, TPMS_PCR_SELECTION},
     Error, WrapperErrorKind,
 };
@@ -466,3 +467,24 @@ fn test_invalid_subtraction() {
         "PcrSelectionList subtract method did not produce expected error for invalid parameters"
     );
 }
+
+#[test]
+fn test_marshall_unmarshall() {
+    let pcr_selection_list = PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot1])
+        .build()
+        .expect("Failed to create PcrSelectionList");
+
+    let marshalled = pcr_selection_list
+        .marshall()
+        .expect("Failed to marshall PcrSelectionList");
+    // count || hash || sizeofSelect || pcrSelect
+    assert_eq!(
+        marshalled,
+        vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x0B, 0x03, 0x03, 0x00, 0x00]
+    );
+    assert_eq!(
+        PcrSelectionList::unmarshall(&marshalled).expect("Failed to unmarshall PcrSelectionList"),
+        pcr_selection_list
+    );
+}
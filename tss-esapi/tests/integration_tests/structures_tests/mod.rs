@@ -10,8 +10,10 @@ mod certify_info_tests;
 mod clock_info_tests;
 mod command_audit_info_tests;
 mod creation_info_tests;
+mod hash_agile_tests;
 mod lists_tests;
 mod nv_certify_info_tests;
+mod nv_pin_counter_tests;
 mod pcr_tests;
 mod quote_info_tests;
 mod session_audit_info_tests;
@@ -0,0 +1,62 @@
+// Copyright 2024 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+use tss_esapi::{
+    structures::{MaxNvBuffer, NvPinCounter},
+    tss2_esys::TPMS_NV_PIN_COUNTER_PARAMETERS,
+    Error, WrapperErrorKind,
+};
+
+#[test]
+fn test_tss_conversions() {
+    let expected_tss_nv_pin_counter = TPMS_NV_PIN_COUNTER_PARAMETERS {
+        pinCount: 1,
+        pinLimit: 5,
+    };
+
+    let nv_pin_counter = NvPinCounter::from(expected_tss_nv_pin_counter);
+    assert_eq!(1, nv_pin_counter.pin_count());
+    assert_eq!(5, nv_pin_counter.pin_limit());
+
+    let actual_tss_nv_pin_counter = TPMS_NV_PIN_COUNTER_PARAMETERS::from(nv_pin_counter);
+    assert_eq!(
+        expected_tss_nv_pin_counter.pinCount,
+        actual_tss_nv_pin_counter.pinCount
+    );
+    assert_eq!(
+        expected_tss_nv_pin_counter.pinLimit,
+        actual_tss_nv_pin_counter.pinLimit
+    );
+}
+
+#[test]
+fn test_nv_data_conversions() {
+    let expected_nv_pin_counter = NvPinCounter::new(0x01020304, 0x0a0b0c0d);
+    let expected_data = [0x01, 0x02, 0x03, 0x04, 0x0a, 0x0b, 0x0c, 0x0d];
+
+    assert_eq!(expected_data, expected_nv_pin_counter.to_be_bytes());
+    assert_eq!(
+        expected_nv_pin_counter,
+        NvPinCounter::from_be_bytes(expected_data)
+    );
+
+    let max_nv_buffer = MaxNvBuffer::try_from(expected_nv_pin_counter)
+        .expect("Failed to convert NvPinCounter into MaxNvBuffer");
+    assert_eq!(&expected_data[..], max_nv_buffer.as_bytes());
+    assert_eq!(
+        expected_nv_pin_counter,
+        NvPinCounter::try_from(max_nv_buffer)
+            .expect("Failed to convert MaxNvBuffer into NvPinCounter")
+    );
+}
+
+#[test]
+fn test_invalid_nv_data_size() {
+    assert_eq!(
+        Err(Error::WrapperError(WrapperErrorKind::WrongParamSize)),
+        NvPinCounter::try_from(
+            MaxNvBuffer::try_from(vec![0u8; 7]).expect("Failed to create MaxNvBuffer")
+        )
+    );
+}
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::convert::TryFrom;
-use tss_esapi::{structures::Signature, tss2_esys::TPMT_SIGNATURE};
+use tss_esapi::{
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{EccParameter, EccSignature, PublicKeyRsa, RsaSignature, Signature},
+    tss2_esys::TPMT_SIGNATURE,
+};
 
 #[test]
 fn marshall_unmarshall() {
@@ -23,3 +27,36 @@ fn tpmt_conversion() {
         );
     });
 }
+
+#[test]
+fn as_ecdsa() {
+    let r = EccParameter::try_from(vec![0x33; 32]).expect("Failed to create r value");
+    let s = EccParameter::try_from(vec![0x44; 32]).expect("Failed to create s value");
+    let ecc_signature = EccSignature::create(HashingAlgorithm::Sha256, r.clone(), s.clone())
+        .expect("Failed to create signature");
+
+    assert_eq!(
+        Signature::EcDsa(ecc_signature.clone()).as_ecdsa(),
+        Some((&r, &s))
+    );
+    assert_eq!(Signature::EcDsa(ecc_signature).as_rsa(), None);
+}
+
+#[test]
+fn as_rsa() {
+    let signature_data =
+        PublicKeyRsa::try_from(vec![0xaa; 256]).expect("Failed to create signature data");
+    let rsa_signature = RsaSignature::create(HashingAlgorithm::Sha256, signature_data.clone())
+        .expect("Failed to create signature");
+
+    assert_eq!(
+        Signature::RsaSsa(rsa_signature.clone()).as_rsa(),
+        Some(&signature_data)
+    );
+    assert_eq!(
+        Signature::RsaPss(rsa_signature.clone()).as_rsa(),
+        Some(&signature_data)
+    );
+    assert_eq!(Signature::RsaSsa(rsa_signature).as_ecdsa(), None);
+    assert_eq!(Signature::Null.as_rsa(), None);
+}
@@ -1,6 +1,6 @@
 // Copyright 2023 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::common::ensure_tpmt_keyedhash_scheme_equality;
+use crate::common::{check_marshall_unmarshall, ensure_tpmt_keyedhash_scheme_equality};
 use std::convert::TryFrom;
 use tss_esapi::{
     constants::AlgorithmIdentifier,
@@ -9,6 +9,7 @@ use tss_esapi::{
         HashingAlgorithm, KeyDerivationFunction, KeyedHashSchemeAlgorithm,
     },
     structures::{HmacScheme, KeyedHashScheme, XorScheme},
+    traits::Marshall,
     tss2_esys::{TPMT_KEYEDHASH_SCHEME, TPMU_SCHEME_KEYEDHASH},
 };
 
@@ -107,3 +108,55 @@ fn test_keyed_hash_scheme_invalid_conversion() {
         panic!("`TPMT_KEYEDHASH_SCHEME` with invalid values did not result in an error when converted to `KeyedHashScheme`.");
     };
 }
+
+#[test]
+fn test_keyed_hash_scheme_constructors_marshall() {
+    let hmac_scheme = KeyedHashScheme::hmac(HashingAlgorithm::Sha256);
+    assert_eq!(
+        hmac_scheme,
+        KeyedHashScheme::Hmac {
+            hmac_scheme: HmacScheme::new(HashingAlgorithm::Sha256)
+        }
+    );
+    assert_eq!(hmac_scheme, KeyedHashScheme::HMAC_SHA_256);
+    // TPM2_ALG_HMAC followed by TPM2_ALG_SHA256.
+    assert_eq!(
+        hmac_scheme
+            .marshall()
+            .expect("Failed to marshall HMAC scheme"),
+        vec![0x00, 0x05, 0x00, 0x0B]
+    );
+
+    let xor_scheme = KeyedHashScheme::xor(
+        HashingAlgorithm::Sha256,
+        KeyDerivationFunction::Kdf1Sp800_108,
+    );
+    assert_eq!(
+        xor_scheme,
+        KeyedHashScheme::Xor {
+            xor_scheme: XorScheme::new(
+                HashingAlgorithm::Sha256,
+                KeyDerivationFunction::Kdf1Sp800_108
+            )
+        }
+    );
+    // TPM2_ALG_XOR followed by TPM2_ALG_SHA256 and TPM2_ALG_KDF1_SP800_108.
+    assert_eq!(
+        xor_scheme
+            .marshall()
+            .expect("Failed to marshall XOR scheme"),
+        vec![0x00, 0x0A, 0x00, 0x0B, 0x00, 0x22]
+    );
+
+    // TPM2_ALG_NULL without details.
+    assert_eq!(
+        KeyedHashScheme::Null
+            .marshall()
+            .expect("Failed to marshall Null scheme"),
+        vec![0x00, 0x10]
+    );
+
+    for scheme in [hmac_scheme, xor_scheme, KeyedHashScheme::Null] {
+        check_marshall_unmarshall(&scheme);
+    }
+}
@@ -1,8 +1,28 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use tss_esapi::tcti_ldr::TctiContext;
+use tss_esapi::{
+    tcti_ldr::{TctiContext, TctiNameConf},
+    Error, WrapperErrorKind,
+};
 
 #[test]
 fn new_context() {
     let _context = TctiContext::initialize(crate::tcti_ldr_tests::name_conf()).unwrap();
 }
+
+#[test]
+fn poll_handles() {
+    let name_conf = crate::tcti_ldr_tests::name_conf();
+    let is_device = matches!(name_conf, TctiNameConf::Device(_));
+    let context = TctiContext::initialize(name_conf).unwrap();
+
+    match context.poll_handles() {
+        Ok(poll_handles) => {
+            assert!(!poll_handles.is_empty());
+            assert!(poll_handles.iter().all(|poll_handle| poll_handle.fd() >= 0));
+        }
+        // Not all TCTIs support polling, but the device TCTI does.
+        Err(Error::WrapperError(WrapperErrorKind::UnsupportedParam)) if !is_device => {}
+        Err(e) => panic!("Failed to get poll handles: {}", e),
+    }
+}